@@ -3,6 +3,11 @@ about = O aplikaci
 repository = Repozitář
 git-description = Git commit {$hash} dne {$date}
 
+# Number formatting
+decimal-separator = ,
+digit-group-separator = {"\u00A0"}
+percent = {$value}{"\u00A0"}%
+
 new-playlist = Nový playlist
 rename = Přejmenovat
 rename-playlist = Přejmenovat playlist
@@ -49,7 +54,6 @@ play = Přehrát
 next = Další
 
 # Dialog
-yes = Ano
 cancel = Zrušit
 create-new-playlist = Vytvořit nový playlist
 playlist-name = Název playlistu
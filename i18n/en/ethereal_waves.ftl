@@ -3,16 +3,41 @@ about = About
 repository = Repository
 git-description = Git commit {$hash} on {$date}
 
+# Number formatting
+decimal-separator = .
+digit-group-separator = ,
+percent = {$value}%
+
 new-playlist = New Playlist
 rename = Rename
 rename-playlist = Rename Playlist
 delete-playlist = Delete Playlist
 library = Library
+favorites = Favorites
+favorite = Favorite
 update-library = Update Library
+undo-library-update-menu = Undo Last Library Update
+preview-library-update-menu = Preview Library Update...
+library-update-preview = Library Update Preview
+library-update-preview-body = {$added} new, {$updated} changed and {$removed} missing audio {$removed ->
+    [one] file
+   *[other] files
+} since the last update. Missing files are removed from the library.
+unreachable-folder-kept = {$path} can't be read, its tracks are kept
 updating-library = Updating Library
+exporting-tracks = Exporting Tracks
+analyzing-loudness = Analyzing Loudness
 scanning-paths = Scanning paths...
+scan-complete = Scan complete — {$count ->
+    [one] 1 track
+   *[other] {$tracks} tracks
+}
+title-progress = {$activity} {$percent}
+update-progress = {$activity} {$current}/{$total} ({$percent})
 loading = Loading...
 untitled-playlist = Untitled Playlist
+quick-add = Quick Add
+playlist-copy-name = {$name} (Copy)
 not-in-library = Track is not in library
 cancel-update = Cancel Update
 artwork-size = Artwork Size
@@ -20,42 +45,126 @@ original = Original
 medium = Medium
 small = Small
 regenerate-thumbnails-on-update = Regenerate Thumbnails on Update
+collapse-tag-whitespace = Collapse Extra Spaces in Tags on Update
+analyze-loudness-on-update = Analyze Loudness After Updating
+analyze-loudness-description = Computes ReplayGain for tracks without it. Decodes every file, which is CPU intensive.
+min-track-duration = Skip Tracks Shorter Than
+off = Off
+seconds = {$value}s
+scan-excludes = Skip Matching Paths
+scan-excludes-description = Glob patterns matched against the full path, such as */Downloads/* or *.backup/*
+scan-exclude-placeholder = Pattern to skip
+library-path-tracks = {$count ->
+    [0] No tracks
+    [one] 1 track
+   *[other] {$tracks} tracks
+}
+network-folder = Network
+network-folder-description = On a network share, updating the library takes longer than from a local disk
+read-only-folder = Read-only
+read-only-folder-description = Mounted read-only, files in this folder can't be changed
 
 # Menu
 file = File
 playlist = Playlist
 new-playlist-menu = New Playlist...
+import-folder-as-playlist-menu = Import Folder as Playlist...
 rename-playlist-menu = Rename Playlist...
+duplicate-playlist-menu = Duplicate Playlist
 delete-playlist-menu = Delete Playlist...
 move-up = Move Up
 move-down = Move Down
+move-column-up = Move {$column} Up
+move-column-down = Move {$column} Down
 view = View
 zoom-in = Zoom In
 zoom-out = Zoom Out
 quit = Quit
 about-ethereal-waves = About Ethereal Waves...
 settings-menu = Settings...
+scan-report-menu = Scan Report...
+help = Help
+view-logs-menu = View Logs...
+command-palette-menu = Command Palette...
+command-palette = Command Palette
+type-a-command = Type a command, page or track
+no-matching-commands = No matching commands
+palette-go-to = Go to {$name}
+palette-track = Track: {$title}
+palette-track-by = Track: {$title} by {$artist}
+palette-album = Album: {$album}
+palette-artist = Artist: {$artist}
 add-selected-to = Add Selected To
 remove-selected = Remove Selected
 remove-selected-from-playlist = Remove Selected Tracks From Playlist
 remove = Remove
+remove-named = Remove {$name}
 tracks-from = Tracks From
 track-info = Track Info
+debug = Debug
+pipeline-state = Pipeline State
+uri = URI
+last-bus-message = Last Bus Message
+position = Position
+image-cache-size = Cached Images
+bus-messages = Bus Messages
+logs = Logs
+scan-report = Scan Report
+no-scan-errors = Every file was read during the last scan
+scan-errors-summary = {$count ->
+    [one] 1 file couldn't be added to the library
+   *[other] {$count} files couldn't be added to the library
+}
+scan-error-missing-plugins = Missing GStreamer plugins: {$plugins}
+scan-error-timeout = Timed out while reading the file
+scan-error-unreadable = Couldn't be read: {$error}
+show-in-folder = Show in Folder
+retry = Retry
+log-level = Log Level
+log-level-error = Errors
+log-level-warn = Warnings
+log-level-info = Info
+log-level-debug = Debug
+log-level-trace = Trace
 shuffle = Shuffle
+shuffle-library = Shuffle Entire Library
+bpm-radio = Play Similar Tempo
 repeat = Repeat
 playback = Playback
 repeat-one = Repeat One
 repeat-all = Repeat All
 add-now-playing-to = Add Now Playing to
+favorite-now-playing = Favorite Now Playing
+reveal-now-playing = Reveal Now Playing in Library
+quick-add-now-playing = Quick Add Now Playing
+copy-now-playing = Copy Now Playing
+copy-now-playing-markdown = Copy Now Playing as Markdown
+go-to-now-playing = Go to Now Playing
+show-footer = Show Playback Controls
+save-queue-as-playlist-menu = Save Queue as Playlist...
 select-all = Select All
+go-to-track-menu = Go to Track...
+analyze-loudness = Analyze Loudness
+reset-play-stats-menu = Reset Play Statistics...
+export-selected-menu = Export Selected...
+rescan-selected-menu = Rescan Selected
 one-track-selected = 1 track selected
 tracks-selected = tracks selected
+playlist-summary = {$count ->
+    [one] 1 track
+   *[other] {$tracks} tracks
+}, {$duration}
 alphabetical = Alphabetical
 title-sort = Title Sort
+sort-ignore-articles = Ignore "The", "A" and "An" When Sorting
+sort-ignore-articles-description = Only used for tracks without sort name tags
 track-number-short = Trk
 show-track-number-column = Show Track Number Column
 mute = Mute
 unmute = Unmute
+volume = Volume
+seek = Seek
+seek-position = {$position} of {$duration}
 volume-up = Volume Up
 volume-down = Volume Down
 group-by = Group By
@@ -75,16 +184,54 @@ add-duplicate-anyway = Add it anyway?
 
 previous = Previous
 play = Play
+pause = Pause
 next = Next
+reveal-in-library = Reveal in Library
+now-playing = Now Playing
+up-next = Up Next
+applied-gain = {$gain} dB
+applied-replay-gain = {$gain} dB RG
+applied-gain-description = Manual gain set in Track Info and ReplayGain applied to this track
+chapters = Chapters
+chapter-number = Chapter {$number}
 
 # Dialog
-yes = Yes
+dont-ask-again = Don't ask again
 cancel = Cancel
 create-new-playlist = Create New Playlist
 playlist-name = Playlist name
 create = Create
+save-queue-as-playlist = Save Queue as Playlist
+import-folder-as-playlist = Import Folder as Playlist
+save = Save
+export-selected = Export Selected Tracks
+export = Export
+export-to = Copy to {$path}
+export-name-template = File name template
+export-template-fields = Fields: title, artist, album, album_artist, genre, track, track_count, disc, disc_count. Wrap them in braces, add :02 to pad numbers and use / for folders.
+export-preview = Preview: {$path}
+export-format = Format
+export-format-copy = Copy As-Is
 delete = Delete
 delete-warning = This action cannot be undone
+reset-play-stats = Reset Play Statistics
+reset = Reset
+reset-play-stats-body = Clear play counts and last played dates for every track in the library?
+playback-finished = Playback Finished
+quitting-in = Quitting in {$seconds} seconds
+sleeping-in = Going to sleep in {$seconds} seconds
+quit-now = Quit Now
+sleep-now = Sleep Now
+go-to-track = Go to Track
+go = Go
+start-playing = Start playing
+track-number-range = Enter a track number from 1 to {$count}
+track-number-out-of-range = Out of range, enter a track number from 1 to {$count}
+folder-already-in-library = {$path} is already in the library
+folder-inside-library-folder = {$path} is already scanned as part of {$parent}
+folder-chooser-unavailable = The folder chooser couldn't be opened. Type the path of the folder to add instead.
+folder-path = Folder path, such as ~/Music
+not-a-folder = No folder exists at this path
 by = by
 from = from
 
@@ -99,14 +246,76 @@ grid-view = Grid View
 settings = Settings
 appearance = Appearance
 theme = Theme
+tint-from-artwork = Tint Playback Controls With Artwork Color
+show-jump-strip = Show A–Z Jump Strip
 add-location = Add Location
 align-rows-top = Align Rows To The Top
+path-fallbacks = Show File Names When Tags Are Missing
+path-fallbacks-description = Untagged tracks show their file name as the title and their folder as the album. Tags aren't changed.
+merge-featured-artists = Group Featured Artists Under the Main Artist
+merge-featured-artists-description = Tracks credited as "Artist feat. Guest" appear with Artist when grouping by artist
 show-album-column = Show Album Column
 show-album-artist-column = Show Album Artist Column
 transition-mode = Transition Mode
 gappless = Gappless
 crossfade = Crossfade
 crossfade-duration = Crossfade Duration
+gapless-within-albums = Gapless Within Albums
+gapless-within-albums-description = Tracks that follow each other on an album play without a crossfade, for live albums and mixes
+balance = Balance
+balance-left = L {$percent}%
+balance-right = R {$percent}%
+center = Center
+clear-now-playing-on-stop = Clear Now Playing When Stopped
+resume-rewind = Rewind When Resuming
+release-device-after = Release Audio Device When Paused For
+release-device-after-description = Lets other apps use the audio device during long pauses. Resuming takes a moment longer.
+accurate-seeking = Accurate Seeking
+accurate-seeking-description = Resumed positions and chapters start exactly where they should, even in VBR files. Takes a moment longer. Dragging the seek bar stays fast.
+replay-gain-normalization = Normalize Loudness With ReplayGain
+replay-gain-normalization-description = Plays tracks at a similar loudness using their ReplayGain tags or a loudness analysis. Tracks without either play at their own level.
+skip-silence = Skip Silence at Track Ends
+skip-silence-description = Starts tracks where their audio begins and moves on once only silence is left. Pauses in the middle of a track always play.
+silence-threshold = Silence Threshold
+silence-threshold-description = Lead-ins and tails quieter than this for at least {$seconds} seconds are skipped
+decibels = {$value} dB
+fade-in = Fade In When Playing
+fade-out = Fade Out When Pausing or Stopping
+fade-out-description = A short fade avoids a click when the audio is cut
+fade-curve = Fade Curve
+fade-curve-description = Used for crossfades and fades. Equal power keeps the loudness steady halfway through a crossfade.
+fade-curve-linear = Linear
+fade-curve-equal-power = Equal Power
+fade-curve-logarithmic = Logarithmic
+previous-restarts-track = Previous Restarts the Current Track
+previous-restart-threshold = Restart After
+continue-to-next-album = Continue to Next Album
+bpm-radio-tolerance = Similar Tempo Range
+bpm-range = ±{$bpm} BPM
+bpm-radio-same-genre = Similar Tempo Keeps to the Same Genre
+when-queue-ends = When the Queue Ends
+empty-play-action = Play With Nothing Loaded
+empty-play-action-description = What the play button does on a fresh launch or after the queue was cleared
+play-current-view = Play Current View
+resume-last-track = Resume Last Track
+stop = Stop
+sleep = Sleep
+follow-current-track = Follow Current Track
+show-waveform-preview = Show Waveform When Seeking
+show-waveform-preview-description = Analyzes each track the first time it plays, which uses extra CPU
+reduce-background-activity = Reduce Background Activity
+show-launcher-progress = Show Progress on Dock Icon
+open-replaces-queue = Play Opened Files Instead of Adding Them to the Queue
+play-view-shuffles = Shuffle When Playing the Whole List
+play-view-shuffles-description = Double-clicking the empty space below the list plays every track shown. While searching, only the matches are played.
+confirm-remove-from-playlist = Confirm Before Removing Tracks From Playlists
+remember-scroll-position = Remember Scroll Position per Playlist
+quick-add-playlist = Quick Add Playlist
+sharing = Sharing
+share-template = Copy Now Playing Template
+share-template-description = Fields: title, artist, album, album_artist, genre, track, track_count, disc, disc_count. Wrap them in braces.
+share-markdown-template = Markdown Template
+quick-add-playlist-description = Ctrl+B adds the playing track here. A Quick Add playlist is created on first use if none is chosen.
 
 number = #
 title = Title
@@ -114,11 +323,24 @@ album = Album
 artist = Artist
 album-artist = Album Artist
 genre = Genre
+grouping = Grouping
+bpm = BPM
 album-disc-number = Album Disc Number
 album-disc-count = Album Disc Count
 track-number = Track Number
 track-count = Track Count
 duration = Duration
+play-count = Play Count
+last-played = Last Played
+manual-gain = Gain
+replay-gain = ReplayGain
+codec = Codec
+container = Container
+bitrate = Bitrate
+sample-rate = Sample Rate
+channels = Channels
+file-size = File Size
+whole-file-size = {$size} (whole file)
 file-path = File path
 track-total-short = Trks
 disc-number-short = Disc
@@ -127,3 +349,41 @@ disc-total-short = Discs
 # Empty library page
 go-to-view = Go to View /
 then-update-library =  / Library to add music. Then update your library!
+empty-playlist = This playlist is empty. Drag tracks here or use Playlist / Add Selected To.
+no-tracks-found = No tracks match your search
+no-albums-found = No albums match your search
+no-artists-found = No artists match your search
+clear-search = Clear Search
+
+# Toasts
+missing-plugins-scan = {$count ->
+    [one] 1 track was skipped
+   *[other] {$count} tracks were skipped
+} because GStreamer plugins are missing: {$plugins}. Install the package that provides them, such as gstreamer-plugins-bad or gstreamer-plugins-ugly.
+no-audio-files-in-folder = No playable audio files found in {$path}
+skipped-unreachable-folder = Skipped unreachable folder: {$path}
+disable = Disable
+invalid-scan-exclude = Invalid pattern {$pattern}: {$error}
+enable = Enable
+tracks-rescanned = {$count ->
+    [one] Updated 1 track
+   *[other] Updated {$count} tracks
+}
+tracks-rescanned-with-failures = {$count ->
+    [one] Updated 1 track
+   *[other] Updated {$count} tracks
+}, {$failed} couldn't be read
+export-failed = {$count ->
+    [one] 1 track couldn't be exported
+   *[other] {$count} tracks couldn't be exported
+}
+export-while-updating = Tracks can be exported once the library update is done
+export-encoder-missing = Can't export as {$format}, no encoder is installed. Install the GStreamer plugins that provide it, such as gstreamer-plugins-good, -bad or -ugly.
+library-update-undone = Restored the library from before the last update
+bpm-radio-no-bpm = The playing track has no BPM tag
+bpm-radio-no-matches = No other tracks have a similar tempo
+suspend-failed = Couldn't put the system to sleep
+quick-added = Added {$title} to {$playlist}
+now-playing-copied = Copied now playing to the clipboard
+quick-add-duplicate = {$title} is already in {$playlist}
+missing-plugins-playback = Can't play this track, GStreamer plugins are missing: {$plugins}
@@ -3,6 +3,11 @@ about = Om
 repository = Källkod
 git-description = Git commit {$hash} på {$date}
 
+# Number formatting
+decimal-separator = ,
+digit-group-separator = {"\u00A0"}
+percent = {$value}{"\u00A0"}%
+
 new-playlist = Ny spellista
 rename = Byt namn
 rename-playlist = Byt namn på spellista
@@ -49,7 +54,6 @@ play = Spela
 next = Nästa
 
 # Dialogrutor
-yes = Ja
 cancel = Avbryt
 create-new-playlist = Skapa ny spellista
 playlist-name = Spellistans namn
@@ -3,6 +3,11 @@ about = Про застосунок
 repository = Сховище
 git-description = Версія {$hash} від {$date}
 
+# Number formatting
+decimal-separator = ,
+digit-group-separator = {"\u00A0"}
+percent = {$value}%
+
 new-playlist = Нова добірка
 rename = Перейменувати
 rename-playlist = Перейменувати добірку
@@ -66,7 +71,6 @@ play = Відтворити
 next = Далі
 
 # Dialog
-yes = Так
 cancel = Скасувати
 create-new-playlist = Створити нову добірку
 playlist-name = Назва добірки
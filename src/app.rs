@@ -1,29 +1,48 @@
 // SPDX-License-Identifier: GPL-3.0
 
 use crate::config::{
-    AppTheme, ArtworkSize, CONFIG_VERSION, Config, GridGroupBy, ListColumn, PlaybackTransitionMode,
-    PlaylistDuplicatePolicy, State, TitleSortMode,
+    AppTheme, ArtworkSize, CONFIG_VERSION, Config, EmptyPlayAction, ExportFormat, FadeCurve,
+    GridGroupBy, ListColumn, LogLevel, PlaybackTransitionMode, PlaylistDuplicatePolicy,
+    QueueEndAction, State, TitleSortMode,
 };
 use crate::constants::*;
+use crate::export::{
+    encoder_available, escape_markdown, export_target, fill_template, transcode_track,
+};
 use crate::fl;
+use crate::format::*;
 use crate::helpers::*;
 use crate::image_store::ImageStore;
 use crate::key_bind::key_binds;
-use crate::library::Library;
+use crate::launcher;
+use crate::library::{Library, LibraryError, MediaMetaData};
+use crate::logger;
+use crate::mounts::{self, MountStatus};
 use crate::mpris::{MediaPlayer2, MediaPlayer2Player, MprisCommand, MprisState};
 use crate::page::{empty_library, grid_view, list_view, loading};
 use crate::playback_state::{PlaybackStatus, RepeatMode};
 use crate::playlist::{Playlist, Track};
-use crate::services::library_service::{LibraryProgress, LibraryService};
+use crate::power;
+use crate::scan_report::{self, ScanError, ScanErrorReason};
+use crate::services::library_service::{
+    LibraryProgress, LibraryService, ProgressSender, ScanPreview, audio_files_in,
+    carry_over_user_data,
+};
 use crate::services::playback_service::{PlaybackEvent, PlaybackService};
 use crate::services::playlist_service::PlaylistService;
-use crate::ui::{footer::footer, menu::menu_bar};
+use crate::silence::{self, SilenceBounds};
+use crate::ui::{
+    command_palette, footer::footer, icon_button::icon_button, jump_strip::jump_strip,
+    menu::menu_bar,
+};
+use crate::waveform;
 
 use cosmic::prelude::*;
+use cosmic::widget::tooltip::Position;
 use cosmic::{
     Action,
     app::context_drawer,
-    cosmic_config::{self, CosmicConfigEntry},
+    cosmic_config::{self, ConfigGet, ConfigSet, CosmicConfigEntry},
     cosmic_theme,
     dialog::file_chooser,
     iced::{
@@ -52,12 +71,15 @@ use std::{
     borrow::Cow,
     cell::RefCell,
     collections::{HashMap, HashSet, VecDeque},
-    path::PathBuf,
+    fs,
+    os::unix::fs::FileTypeExt,
+    path::{Path, PathBuf},
     process,
     sync::{Arc, Mutex},
     time::{Duration, Instant},
 };
-use tokio_stream::wrappers::UnboundedReceiverStream;
+use tokio::sync::Notify;
+use tokio_stream::wrappers::{ReceiverStream, UnboundedReceiverStream};
 use tokio_util::sync::CancellationToken;
 use urlencoding::decode;
 use xdg::BaseDirectories;
@@ -77,11 +99,46 @@ pub struct AppModel {
     core: cosmic::Core,
     /// Display a context drawer with the designated page if defined.
     context_page: ContextPage,
+    // Set from the environment to expose the hidden debug panel
+    debug_enabled: bool,
+    /// Library track the track info page shows in place of the selection, set when it's
+    /// opened for the now playing track and cleared when the page closes
+    track_info_id: Option<String>,
+    /// Library folders skipped by scans until the app restarts
+    disabled_library_paths: HashSet<String>,
+    /// Tracks found under each library path, shown beside it in the settings
+    library_path_track_counts: HashMap<String, usize>,
+    /// Read-only and network mounts of the library paths, checked when the paths change
+    library_path_mounts: HashMap<String, MountStatus>,
+    /// Pattern being typed into the scan excludes setting
+    scan_exclude_input: String,
+    /// Files the last scan couldn't read, cleared when a full scan starts
+    scan_errors: Vec<ScanError>,
+    /// Entry id and path of the track the list last followed
+    followed_track: Option<(u32, PathBuf)>,
+    /// File passed on the command line, played once the library has loaded
+    pending_open: Option<OpenRequest>,
+    /// Folder being imported as a playlist and its files, created once they're in the library
+    pending_folder_import: Option<(PathBuf, Vec<PathBuf>)>,
+    toasts: widget::Toasts<Message>,
+    /// Missing plugins already reported during playback, so skipping tracks doesn't repeat them
+    reported_missing_plugins: HashSet<String>,
+    /// Position under the pointer on the seek slider, as a fraction of the track
+    pub seek_hover: Option<f32>,
+    /// Peak caches for the seek preview by track id, and ids being computed or that failed
+    waveforms: HashMap<String, Vec<f32>>,
+    waveform_pending: HashSet<String>,
+    /// Silent lead-ins and tails by track id, `None` when there's nothing to skip, and ids
+    /// being analyzed
+    silence_bounds: HashMap<TrackId, Option<SilenceBounds>>,
+    silence_pending: HashSet<TrackId>,
     /// The about page this app.
     about: About,
     /// Contains items assigned to the nav bar panel.
     nav: nav_bar::Model,
     nav_dnd_id: widget::dnd_destination::DragId,
+    /// Playlist entry tracks are being dragged over, its icon shows they'll be added
+    nav_drop_target: Option<nav_bar::Id>,
     /// Key bindings for the application's menu bar.
     pub key_binds: HashMap<menu::KeyBind, MenuAction>,
     /// Configuration data that persists between application runs.
@@ -91,7 +148,16 @@ pub struct AppModel {
     title_sort_labels: Vec<String>,
     playlist_duplicate_policy_lables: Vec<String>,
     playback_transition_labels: Vec<String>,
+    fade_curve_labels: Vec<String>,
+    queue_end_action_labels: Vec<String>,
+    empty_play_action_labels: Vec<String>,
+    log_level_labels: Vec<String>,
     artwork_size_labels: Vec<String>,
+    export_format_labels: Vec<String>,
+    export_bitrate_labels: Vec<String>,
+    // Export formats without an encoder, checked in the background when the export dialog
+    // first opens
+    missing_export_encoders: Option<Vec<ExportFormat>>,
 
     pub is_condensed: bool,
 
@@ -106,16 +172,26 @@ pub struct AppModel {
     pub playback_service: PlaybackService,
 
     pub library: Library,
+    /// The library as it was before the last update or rescan this session, one level of undo
+    pub library_undo: Option<Library>,
 
     pub is_updating: bool,
+    // What the footer progress bar is showing while `is_updating`
+    update_activity: UpdateActivity,
     pub update_progress: f32,
     pub update_total: f32,
     pub update_percent: f32,
     pub update_progress_display: String,
+    /// Track count and finish time of a library scan, the footer confirms it for a few seconds
+    pub scan_complete: Option<(usize, Instant)>,
+    /// When the volume last changed, it's saved once it has settled
+    volume_changed_at: Option<Instant>,
 
-    initial_load_complete: bool,
+    pub initial_load_complete: bool,
 
     dialog_pages: DialogPages,
+    // Entries for the query of the open command palette, updated as it's typed
+    command_palette_entries: Vec<(String, Message)>,
 
     list_size_multiplier: f32,
     grid_size_multiplier: f32,
@@ -123,9 +199,18 @@ pub struct AppModel {
     pub list_start: usize,
     pub list_visible_row_count: usize,
     grid_start: usize,
+    // Direction of the last grid scroll, covers are prefetched on that side
+    grid_scrolling_up: bool,
+    // Size of the grid's scroll area as of its last scroll or resize
+    grid_viewport_size: Option<Size>,
+    // List and grid start rows saved per playlist when navigating away
+    scroll_positions: HashMap<PlaylistId, (usize, usize)>,
     view_cache: RefCell<Option<CachedViewBase>>,
     grid_card_cache: RefCell<Option<CachedGridCardBase>>,
     list_last_clicked: Option<Instant>,
+    previous_pressed_at: Option<Instant>,
+    // Quit or sleep pending after the queue ended, and when it happens
+    queue_end_countdown: Option<(QueueEndAction, Instant)>,
     list_last_selected_id: Option<usize>,
 
     control_pressed: u8,
@@ -141,6 +226,12 @@ pub struct AppModel {
     pub playlist_service: PlaylistService,
 
     mpris_state: Arc<Mutex<MprisState>>,
+    mpris_wake: Arc<Notify>,
+    /// Last progress published to the launcher entry and when, used to throttle updates
+    launcher_progress: Option<f64>,
+    launcher_updated_at: Instant,
+    /// Background work progress currently shown in the window title
+    title_progress: Option<String>,
     mpris_connection: Option<zbus::Connection>,
     mpris_connection_rx: Option<std::sync::mpsc::Receiver<Result<zbus::Connection, String>>>,
 }
@@ -149,19 +240,47 @@ pub struct AppModel {
 #[derive(Debug, Clone)]
 pub enum Message {
     AddLibraryDialog,
+    AddScanExclude,
     AddSelectedToPlaylist(PlaylistId),
     AddNowPlayingToPlaylist(PlaylistId),
+    AnalyzeLoudness,
     AppTheme(AppTheme),
+    Balance(i32),
+    BpmRadioTolerance(u32),
     CancelLibraryUpdate,
     ChangeTrack(usize),
     ChangeTracks(Arc<Vec<usize>>),
+    CleanUpArtwork,
+    ClearScanComplete,
+    CommandPalette,
+    CloseToast(widget::ToastId),
     CrossfadeDuration(i32),
     DeletePlaylist,
+    DuplicatePlaylist(PlaylistId),
+    EmptyPlayAction(EmptyPlayAction),
     DialogCancel,
     DialogComplete,
+    ExportDestination(String),
+    ExportEncodersChecked(Vec<ExportFormat>),
+    ExportFinished(usize, usize),
+    ExportProgress(usize, usize),
+    ExportSelected,
+    FadeCurve(FadeCurve),
+    FadeInMs(u32),
+    FadeOutMs(u32),
+    GoToNowPlaying,
+    GoToTrack,
+    GoToTrackNumber(usize),
+    JumpToLetter(char),
     KeyPressed(Modifiers, Key, event::Status),
     KeyReleased(Key),
+    ImportFolderAsPlaylist(PathBuf),
+    ImportFolderDialog,
     LaunchUrl(String),
+    OpenContainingFolder(PathBuf),
+    LogLevel(LogLevel),
+    LibraryLoaded(Library),
+    LibraryPathMounts(HashMap<String, MountStatus>),
     LibraryPathOpenError(Arc<file_chooser::Error>),
     LibraryProgress(LibraryProgress),
     GridViewScroll(scrollable::Viewport),
@@ -173,35 +292,89 @@ pub enum Message {
     ListSelectRows(Arc<Vec<usize>>),
     ListViewScroll(scrollable::Viewport),
     ListViewSort(SortBy),
+    ManualGain(TrackId, f32),
+    ManualGainReleased,
+    MinDurationSecs(u32),
     MoveListColumnDown(ListColumn),
     MoveListColumnUp(ListColumn),
     MoveNavDown,
     MoveNavUp,
+    NavDragEnter(nav_bar::Id),
+    NavDragLeave(nav_bar::Id),
     NavDrop(nav_bar::Id, TrackDropData),
     NewPlaylist,
     Next,
+    PlayQueueIndex(usize),
+    ShuffleLibrary,
+    StartBpmRadio,
     Noop,
+    EnqueueFile(OpenRequest),
+    OpenFile(OpenRequest),
+    PlayAlbum(String),
+    PlayCurrentView,
     PlayPause,
     PlaybackTransitionMode(PlaybackTransitionMode),
     PlaylistDuplicateDialogAction(PlaylistDuplicateDialogAction),
     PlaylistDuplicatePolicy(PlaylistDuplicatePolicy),
     Previous,
+    PreviousRestartThresholdSecs(u32),
+    PreviewLibraryUpdate,
+    QueueEndAction(QueueEndAction),
+    QuickAddCurrent,
+    CopyNowPlaying(ShareFormat),
+    ShareTemplate(ShareFormat, String),
+    QuickAddPlaylist(Option<PlaylistId>),
     Quit,
+    ReleaseAudioDevice,
+    ReleaseDeviceAfterSecs(u32),
     ReleaseSlider,
     RemoveLibraryPath(String),
+    RemoveScanExclude(String),
     RemoveSelectedFromPlaylist,
+    ToggleConfirmRemoveFromPlaylist(bool),
     RenamePlaylist,
+    RescanSelected,
+    RescanTracks(Vec<PathBuf>),
+    RetryScanError(PathBuf),
+    ResetPlayStats(Option<String>),
+    ResumeRewindSecs(u32),
+    RevealInLibrary(String),
+    RevealNowPlayingInLibrary,
+    RunCommand(Box<Message>),
+    SaveQueue,
+    SaveQueueAsPlaylist(String),
+    SaveVolume,
+    ScanPreviewReady(ScanPreview),
+    ScanExcludeInput(String),
     SearchActivate,
+    SeekHover(Option<f32>),
     SearchClear,
     SearchInput(String),
+    SearchLibrary(String),
     SelectAll,
     SetViewMode(ViewMode),
     SelectedPaths(Vec<String>),
-    SetVolume(i32),
+    SetVolume(f64),
+    SeekToChapter(u64),
+    ShowPlaylist(PlaylistId),
+    ShowTrackInfo(String),
+    ShuffleAlbum(String),
+    SilenceBoundsReady(TrackId, Option<SilenceBounds>),
+    SkipSilenceThresholdDb(i32),
     SliderSeek(f32),
+    SuspendFailed(String),
     Surface(surface::Action),
     Tick,
     TitleSort(TitleSortMode),
+    ToggleAccurateSeeking(bool),
+    ToggleAnalyzeLoudnessOnUpdate(bool),
+    ToggleBpmRadioSameGenre(bool),
+    ToggleClearNowPlayingOnStop(bool),
+    ToggleCollapseTagWhitespace(bool),
+    ToggleContinueToNextAlbum(bool),
+    ToggleFollowCurrentTrack(bool),
+    ToggleFooter,
+    ToggleGaplessWithinAlbums(bool),
     ToggleContextPage(ContextPage),
     ToggleListAlbumArtistColumn(bool),
     ToggleListAlbumColumn(bool),
@@ -211,22 +384,43 @@ pub enum Message {
     ToggleListDurationColumn(bool),
     ToggleListFilePathColumn(bool),
     ToggleListGenreColumn(bool),
+    ToggleListBpmColumn(bool),
     ToggleListRowAlignTop(bool),
     ToggleListTextWrap(bool),
+    ToggleMergeFeaturedArtists(bool),
     ToggleSortCaseSensitive(bool),
+    ToggleSortIgnoreArticles(bool),
+    ToggleTintFromArtwork(bool),
     ToggleRegenerateThumbnailsOnUpdate(bool),
+    ToggleReplayGainNormalization(bool),
     ToggleListTitleColumn(bool),
     ToggleListTrackNumberColumn(bool),
     ToggleListTrackTotalColumn(bool),
+    ToggleReduceBackgroundActivity(bool),
+    TogglePreviousRestartsTrack(bool),
+    ToggleRememberScrollPosition(bool),
+    ToggleOpenReplacesQueue(bool),
+    TogglePlayViewShuffles(bool),
+    TogglePathFallbacks(bool),
+    ToggleJumpStrip(bool),
+    ToggleFooterQueue(bool),
+    ToggleShowLauncherProgress(bool),
+    ToggleShowWaveformPreview(bool),
+    ToggleSkipSilence(bool),
+    SetLibraryPathDisabled(String, bool),
+    ToggleFavorite(String),
+    ToggleFavoriteNowPlaying,
     ToggleMute,
     ToggleRepeat,
     ToggleRepeatMode,
     ToggleShuffle,
     UpdateConfig(Config),
     UpdateDialog(DialogPage),
+    UndoLastScan,
     UpdateLibrary,
     VolumeDown,
     VolumeUp,
+    WaveformReady(String, Option<Vec<f32>>),
     WindowResized(Size),
     ZoomIn,
     ZoomOut,
@@ -277,10 +471,14 @@ impl cosmic::Application for AppModel {
         let (mpris_tx, mpris_rx) = tokio::sync::mpsc::unbounded_channel();
         let mpris_state = Arc::new(Mutex::new(MprisState::default()));
         let mpris_state_clone = mpris_state.clone();
+        let mpris_wake = Arc::new(Notify::new());
+        let mpris_wake_clone = mpris_wake.clone();
         let (conn_tx, conn_rx) = std::sync::mpsc::sync_channel(1);
 
         tokio::spawn(async move {
-            let result = AppModel::init_mpris_connection(mpris_tx, mpris_state_clone).await;
+            let result =
+                AppModel::init_mpris_connection(mpris_tx, mpris_state_clone, mpris_wake_clone)
+                    .await;
             let _ = conn_tx.send(result);
         });
 
@@ -298,9 +496,27 @@ impl cosmic::Application for AppModel {
         let mut app = AppModel {
             core,
             context_page: ContextPage::default(),
+            debug_enabled: std::env::var_os(DEBUG_ENV_VAR).is_some(),
+            track_info_id: None,
+            pending_open: _flags.open,
+            pending_folder_import: None,
+            disabled_library_paths: HashSet::new(),
+            library_path_track_counts: HashMap::new(),
+            library_path_mounts: HashMap::new(),
+            scan_errors: scan_report::load(&app_xdg_dirs),
+            scan_exclude_input: String::new(),
+            followed_track: None,
+            toasts: widget::Toasts::new(Message::CloseToast),
+            reported_missing_plugins: HashSet::new(),
+            seek_hover: None,
+            waveforms: HashMap::new(),
+            waveform_pending: HashSet::new(),
+            silence_bounds: HashMap::new(),
+            silence_pending: HashSet::new(),
             about,
             nav,
             nav_dnd_id: widget::dnd_destination::DragId::new(),
+            nav_drop_target: None,
             key_binds: key_binds(),
             config: cosmic_config::Config::new(APP_ID, CONFIG_VERSION)
                 .map(|context| match Config::get_entry(&context) {
@@ -312,7 +528,36 @@ impl cosmic::Application for AppModel {
             title_sort_labels: vec![fl!("alphabetical"), fl!("track-number")],
             playlist_duplicate_policy_lables: vec![fl!("allow"), fl!("disallow"), fl!("ask")],
             playback_transition_labels: vec![fl!("gappless"), fl!("crossfade")],
+            fade_curve_labels: vec![
+                fl!("fade-curve-linear"),
+                fl!("fade-curve-equal-power"),
+                fl!("fade-curve-logarithmic"),
+            ],
+            queue_end_action_labels: vec![fl!("stop"), fl!("quit"), fl!("sleep")],
+            empty_play_action_labels: vec![
+                fl!("play-current-view"),
+                fl!("resume-last-track"),
+                fl!("shuffle-library"),
+            ],
+            log_level_labels: vec![
+                fl!("log-level-error"),
+                fl!("log-level-warn"),
+                fl!("log-level-info"),
+                fl!("log-level-debug"),
+                fl!("log-level-trace"),
+            ],
             artwork_size_labels: vec![fl!("original"), fl!("medium"), fl!("small")],
+            export_format_labels: vec![
+                fl!("export-format-copy"),
+                "MP3".to_string(),
+                "Opus".to_string(),
+                "AAC".to_string(),
+            ],
+            export_bitrate_labels: EXPORT_BITRATES_KBPS
+                .iter()
+                .map(|kbps| format!("{kbps} kbps"))
+                .collect(),
+            missing_export_encoders: None,
             is_condensed: false,
             config_handler: _flags.config_handler,
             state_handler: _flags.state_handler,
@@ -322,25 +567,39 @@ impl cosmic::Application for AppModel {
             library_update_cancel: None,
             playback_service: PlaybackService::new(mpris_rx),
             mpris_state,
+            mpris_wake,
             mpris_connection,
             mpris_connection_rx: Some(conn_rx),
+            launcher_progress: None,
+            launcher_updated_at: Instant::now(),
+            title_progress: None,
             initial_load_complete: false,
             library: Library::new(),
+            library_undo: None,
             is_updating: false,
+            update_activity: UpdateActivity::Library,
+            scan_complete: None,
+            volume_changed_at: None,
             update_progress: 0.0,
             update_total: 0.0,
             update_percent: 0.0,
             update_progress_display: "0".into(),
             dialog_pages: DialogPages::new(),
+            command_palette_entries: Vec::new(),
             list_size_multiplier: _flags.state.effective_list_size_multiplier(),
             grid_size_multiplier: _flags.state.effective_grid_size_multiplier(),
             list_scroll_id: widget::Id::unique(),
             list_start: 0,
             list_visible_row_count: 0,
             grid_start: 0,
+            grid_scrolling_up: false,
+            grid_viewport_size: None,
+            scroll_positions: HashMap::new(),
             view_cache: RefCell::new(None),
             grid_card_cache: RefCell::new(None),
             list_last_clicked: None,
+            previous_pressed_at: None,
+            queue_end_countdown: None,
             list_last_selected_id: None,
             control_pressed: 0,
             shift_pressed: 0,
@@ -351,12 +610,29 @@ impl cosmic::Application for AppModel {
             playlist_service: PlaylistService::new(Arc::new(app_xdg_dirs.clone())),
         };
 
+        app.migrate_volume_from_state();
+
         // Apply persisted playback settings to the engine before any session starts
         app.playback_service
             .set_repeat_state(app.state.repeat_mode.clone(), app.state.repeat);
         app.playback_service
             .set_transition_mode(app.config.playback_transition_mode);
         app.sync_crossfade_duration_from_config();
+        app.playback_service
+            .set_gapless_within_albums(app.config.gapless_within_albums);
+        app.sync_balance_from_config();
+        app.playback_service
+            .set_clear_now_playing_on_stop(app.config.clear_now_playing_on_stop);
+        app.playback_service
+            .set_resume_rewind_secs(app.config.resume_rewind_secs);
+        app.playback_service
+            .set_accurate_seeking(app.config.accurate_seeking);
+        app.playback_service
+            .set_replay_gain_normalization(app.config.replay_gain_normalization);
+        app.playback_service
+            .set_fade_durations(app.config.fade_in_ms, app.config.fade_out_ms);
+        app.playback_service.set_fade_curve(app.config.fade_curve);
+        app.playback_service.set_debug_enabled(app.debug_enabled);
         app.sync_playback_output_from_state();
 
         // Create a startup command that sets the window title.
@@ -365,7 +641,9 @@ impl cosmic::Application for AppModel {
         // Load the master library and playlists
         let load_data = app.load_data();
 
-        (app, Task::batch([update_title, load_data]))
+        let check_mounts = app.check_library_path_mounts();
+
+        (app, Task::batch([update_title, load_data, check_mounts]))
     }
 
     /// Elements to pack at the start of the header bar.
@@ -425,6 +703,26 @@ impl cosmic::Application for AppModel {
                 Message::ToggleContextPage(ContextPage::TrackInfo),
             )
             .title(fl!("track-info")),
+            ContextPage::Chapters => context_drawer::context_drawer(
+                self.chapters_panel(),
+                Message::ToggleContextPage(ContextPage::Chapters),
+            )
+            .title(fl!("chapters")),
+            ContextPage::Debug => context_drawer::context_drawer(
+                self.debug_panel(),
+                Message::ToggleContextPage(ContextPage::Debug),
+            )
+            .title(fl!("debug")),
+            ContextPage::Logs => context_drawer::context_drawer(
+                self.logs_panel(),
+                Message::ToggleContextPage(ContextPage::Logs),
+            )
+            .title(fl!("logs")),
+            ContextPage::ScanReport => context_drawer::context_drawer(
+                self.scan_report_panel(),
+                Message::ToggleContextPage(ContextPage::ScanReport),
+            )
+            .title(fl!("scan-report")),
         })
     }
 
@@ -441,14 +739,32 @@ impl cosmic::Application for AppModel {
 
         let content: Element<_> = match playlist {
             Some(p) if p.is_library() && p.tracks().is_empty() => empty_library::content().into(),
-            Some(_) => match self.config.view_mode {
-                ViewMode::List => list_view::content(self).into(),
-                ViewMode::Grid => grid_view::content(self),
-            },
+            Some(_) if self.visible_track_count() == 0 => self.empty_view_content().into(),
+            Some(p) => {
+                let view: Element<_> = match self.config.view_mode {
+                    ViewMode::List => list_view::content(self).into(),
+                    ViewMode::Grid => grid_view::content(self),
+                };
+                let view: Element<_> = if self.config.show_jump_strip {
+                    widget::row().push(view).push(jump_strip(self)).into()
+                } else {
+                    view
+                };
+
+                if p.is_library() {
+                    view
+                } else {
+                    widget::column()
+                        .push(self.playlist_summary(p))
+                        .push(widget::divider::horizontal::default())
+                        .push(view)
+                        .into()
+                }
+            }
             None => empty_library::content().into(),
         };
 
-        widget::container(widget::column().push(content))
+        widget::toaster(&self.toasts, widget::column().push(content))
             .apply(widget::container)
             .height(Length::Fill)
             .width(Length::Fill)
@@ -489,6 +805,33 @@ impl cosmic::Application for AppModel {
                 dialog
             }
 
+            DialogPage::SaveQueueAsPlaylist(name) => {
+                let complete_maybe = if Self::playlist_name_is_valid(name) {
+                    Some(Message::DialogComplete)
+                } else {
+                    None
+                };
+
+                let dialog = widget::dialog()
+                    .title(fl!("save-queue-as-playlist"))
+                    .primary_action(
+                        widget::button::suggested(fl!("save")).on_press_maybe(complete_maybe),
+                    )
+                    .secondary_action(
+                        widget::button::standard(fl!("cancel")).on_press(Message::DialogCancel),
+                    )
+                    .control(widget::column::with_children(vec![
+                        widget::text_input(fl!("untitled-playlist"), name)
+                            .id(widget::Id::new(SAVE_QUEUE_INPUT_ID))
+                            .on_input(move |name| {
+                                Message::UpdateDialog(DialogPage::SaveQueueAsPlaylist(name))
+                            })
+                            .into(),
+                    ]));
+
+                dialog
+            }
+
             DialogPage::RenamePlaylist { id, name } => {
                 let complete_maybe = if Self::playlist_name_is_valid(name) {
                     Some(Message::DialogComplete)
@@ -521,44 +864,190 @@ impl cosmic::Application for AppModel {
             DialogPage::DeletePlaylist(id) => {
                 let playlist = self.playlist_service.get(*id).ok();
 
-                let dialog = widget::dialog()
-                    .title(fl!("delete-playlist"))
-                    .icon(widget::icon::from_name("dialog-warning").size(64))
-                    .body(format!("{} {}?", fl!("delete"), playlist.unwrap().name()))
-                    .primary_action(
-                        widget::button::suggested(fl!("yes")).on_press(Message::DialogComplete),
-                    )
-                    .secondary_action(
-                        widget::button::standard(fl!("cancel")).on_press(Message::DialogCancel),
-                    )
-                    .control(widget::column::with_children(vec![
-                        widget::text(fl!("delete-warning")).into(),
-                    ]));
-                dialog
+                confirm_dialog(
+                    fl!("delete-playlist"),
+                    format!("{} {}?", fl!("delete"), playlist.unwrap().name()),
+                    fl!("delete"),
+                    Message::DialogComplete,
+                    None,
+                )
+                .control(widget::text(fl!("delete-warning")))
             }
 
-            DialogPage::DeleteSelectedFromPlaylist => {
+            DialogPage::DeleteSelectedFromPlaylist { dont_ask_again } => {
                 let view_playlist = self
                     .playlist_service
                     .get(self.view_playlist.unwrap())
                     .ok()
                     .unwrap();
 
-                let dialog = widget::dialog()
-                    .title(fl!("remove-selected-from-playlist"))
-                    .icon(widget::icon::from_name("dialog-warning").size(64))
-                    .body(format!(
+                confirm_dialog(
+                    fl!("remove-selected-from-playlist"),
+                    format!(
                         "{} {} {} {}?",
                         fl!("remove"),
                         view_playlist.selected_iter().count(),
                         fl!("tracks-from"),
                         view_playlist.name()
-                    ))
+                    ),
+                    fl!("remove"),
+                    Message::DialogComplete,
+                    Some((*dont_ask_again, |dont_ask_again| {
+                        Message::UpdateDialog(DialogPage::DeleteSelectedFromPlaylist {
+                            dont_ask_again,
+                        })
+                    })),
+                )
+            }
+
+            DialogPage::GoToTrack { number, play } => {
+                let track_count = self.visible_track_count();
+                let complete_maybe =
+                    Self::parse_track_number(number, track_count).map(|_| Message::DialogComplete);
+
+                let feedback = if !number.trim().is_empty() && complete_maybe.is_none() {
+                    fl!("track-number-out-of-range", count = track_count)
+                } else {
+                    fl!("track-number-range", count = track_count)
+                };
+
+                let play = *play;
+                let number_for_toggle = number.clone();
+
+                let dialog = widget::dialog()
+                    .title(fl!("go-to-track"))
+                    .primary_action(
+                        widget::button::suggested(fl!("go")).on_press_maybe(complete_maybe),
+                    )
+                    .secondary_action(
+                        widget::button::standard(fl!("cancel")).on_press(Message::DialogCancel),
+                    )
+                    .control(
+                        widget::column::with_children(vec![
+                            widget::text_input(fl!("track-number"), number)
+                                .id(widget::Id::new(GO_TO_TRACK_INPUT_ID))
+                                .on_input(move |number| {
+                                    Message::UpdateDialog(DialogPage::GoToTrack { number, play })
+                                })
+                                .into(),
+                            widget::text(feedback).into(),
+                            widget::row()
+                                .align_y(Alignment::Center)
+                                .push(widget::text(fl!("start-playing")))
+                                .push(widget::space::horizontal())
+                                .push(toggler(play).on_toggle(move |play| {
+                                    Message::UpdateDialog(DialogPage::GoToTrack {
+                                        number: number_for_toggle.clone(),
+                                        play,
+                                    })
+                                }))
+                                .into(),
+                        ])
+                        .spacing(space_xxs),
+                    );
+
+                dialog
+            }
+
+            DialogPage::ResetPlayStats => confirm_dialog(
+                fl!("reset-play-stats"),
+                fl!("reset-play-stats-body"),
+                fl!("reset"),
+                Message::DialogComplete,
+                None,
+            )
+            .control(widget::text(fl!("delete-warning"))),
+
+            DialogPage::ExportTracks {
+                destination,
+                template,
+                format,
+                bitrate_kbps,
+            } => {
+                let (format, bitrate_kbps) = (*format, *bitrate_kbps);
+                let preview = self
+                    .get_active_playlist()
+                    .and_then(|playlist| playlist.selected_iter().next())
+                    .map(|track| {
+                        export_target(destination, template, &track.metadata, &track.path, format)
+                            .display()
+                            .to_string()
+                    })
+                    .unwrap_or_default();
+                let complete_maybe =
+                    (!template.trim().is_empty()).then_some(Message::DialogComplete);
+                let destination_for_input = destination.clone();
+                let destination_for_format = destination.clone();
+                let template_for_format = template.clone();
+                let destination_for_bitrate = destination.clone();
+                let template_for_bitrate = template.clone();
+                let bitrate_selected = EXPORT_BITRATES_KBPS
+                    .iter()
+                    .position(|kbps| *kbps == bitrate_kbps);
+
+                let dialog = widget::dialog()
+                    .title(fl!("export-selected"))
+                    .body(fl!("export-to", path = destination.display().to_string()))
                     .primary_action(
-                        widget::button::suggested(fl!("yes")).on_press(Message::DialogComplete),
+                        widget::button::suggested(fl!("export")).on_press_maybe(complete_maybe),
                     )
                     .secondary_action(
                         widget::button::standard(fl!("cancel")).on_press(Message::DialogCancel),
+                    )
+                    .control(
+                        widget::column::with_children(vec![
+                            widget::text_input(fl!("export-name-template"), template)
+                                .id(widget::Id::new(EXPORT_TEMPLATE_INPUT_ID))
+                                .on_input(move |template| {
+                                    Message::UpdateDialog(DialogPage::ExportTracks {
+                                        destination: destination_for_input.clone(),
+                                        template,
+                                        format,
+                                        bitrate_kbps,
+                                    })
+                                })
+                                .into(),
+                            widget::text::caption(fl!("export-template-fields")).into(),
+                            widget::text(fl!("export-preview", path = preview)).into(),
+                            row()
+                                .align_y(Alignment::Center)
+                                .spacing(space_xxs)
+                                .push(widget::text(fl!("export-format")))
+                                .push(widget::dropdown(
+                                    &self.export_format_labels,
+                                    Some(format.selected_index()),
+                                    move |index| {
+                                        Message::UpdateDialog(DialogPage::ExportTracks {
+                                            destination: destination_for_format.clone(),
+                                            template: template_for_format.clone(),
+                                            format: ExportFormat::ALL
+                                                .get(index)
+                                                .copied()
+                                                .unwrap_or_default(),
+                                            bitrate_kbps,
+                                        })
+                                    },
+                                ))
+                                .push_maybe((format != ExportFormat::Copy).then(|| {
+                                    widget::dropdown(
+                                        &self.export_bitrate_labels,
+                                        bitrate_selected,
+                                        move |index| {
+                                            Message::UpdateDialog(DialogPage::ExportTracks {
+                                                destination: destination_for_bitrate.clone(),
+                                                template: template_for_bitrate.clone(),
+                                                format,
+                                                bitrate_kbps: EXPORT_BITRATES_KBPS
+                                                    .get(index)
+                                                    .copied()
+                                                    .unwrap_or(DEFAULT_EXPORT_BITRATE_KBPS),
+                                            })
+                                        },
+                                    )
+                                }))
+                                .into(),
+                        ])
+                        .spacing(space_xxs),
                     );
 
                 dialog
@@ -608,6 +1097,142 @@ impl cosmic::Application for AppModel {
 
                 dialog
             }
+
+            DialogPage::EnterLibraryPath(path) => {
+                let complete_maybe =
+                    Self::entered_library_path(path).map(|_| Message::DialogComplete);
+
+                let mut controls = vec![
+                    widget::text_input(fl!("folder-path"), path)
+                        .id(widget::Id::new(LIBRARY_PATH_INPUT_ID))
+                        .on_input(|path| Message::UpdateDialog(DialogPage::EnterLibraryPath(path)))
+                        .into(),
+                ];
+                if !path.trim().is_empty() && complete_maybe.is_none() {
+                    controls.push(widget::text(fl!("not-a-folder")).into());
+                }
+
+                let dialog = widget::dialog()
+                    .title(fl!("add-location"))
+                    .body(fl!("folder-chooser-unavailable"))
+                    .primary_action(
+                        widget::button::suggested(fl!("add")).on_press_maybe(complete_maybe),
+                    )
+                    .secondary_action(
+                        widget::button::standard(fl!("cancel")).on_press(Message::DialogCancel),
+                    )
+                    .control(widget::column::with_children(controls).spacing(space_xxs));
+
+                dialog
+            }
+
+            DialogPage::ScanPreview(preview) => {
+                let mut lines: Vec<Element<'_, Message>> = preview
+                    .unreachable_paths
+                    .iter()
+                    .map(|path| {
+                        widget::text(fl!("unreachable-folder-kept", path = path.as_str())).into()
+                    })
+                    .collect();
+
+                if !preview.removed.is_empty() {
+                    let removed = preview
+                        .removed
+                        .iter()
+                        .map(|path| widget::text::caption(path.display().to_string()).into());
+                    lines.push(
+                        widget::scrollable(widget::column::with_children(
+                            removed.collect::<Vec<_>>(),
+                        ))
+                        .height(Length::Fixed(SCAN_PREVIEW_LIST_HEIGHT))
+                        .into(),
+                    );
+                }
+
+                let dialog = widget::dialog()
+                    .title(fl!("library-update-preview"))
+                    .icon(widget::icon::from_name("dialog-information").size(64))
+                    .body(fl!(
+                        "library-update-preview-body",
+                        added = preview.added,
+                        updated = preview.updated,
+                        removed = preview.removed.len()
+                    ))
+                    .primary_action(
+                        widget::button::suggested(fl!("update-library"))
+                            .on_press(Message::DialogComplete),
+                    )
+                    .secondary_action(
+                        widget::button::standard(fl!("cancel")).on_press(Message::DialogCancel),
+                    )
+                    .control(widget::column::with_children(lines).spacing(space_xxs));
+
+                dialog
+            }
+
+            DialogPage::CommandPalette(query) => {
+                let mut controls = vec![
+                    widget::text_input(fl!("type-a-command"), query)
+                        .id(widget::Id::new(COMMAND_PALETTE_INPUT_ID))
+                        .on_input(|query| Message::UpdateDialog(DialogPage::CommandPalette(query)))
+                        .into(),
+                ];
+
+                if self.command_palette_entries.is_empty() {
+                    controls.push(widget::text(fl!("no-matching-commands")).into());
+                }
+
+                // The highlighted first entry is the one Enter runs
+                for (index, (label, message)) in self.command_palette_entries.iter().enumerate() {
+                    let class = if index == 0 {
+                        theme::Button::Suggested
+                    } else {
+                        theme::Button::Text
+                    };
+                    controls.push(
+                        widget::button::custom(widget::text(label.as_str()))
+                            .class(class)
+                            .width(Length::Fill)
+                            .on_press(Message::RunCommand(Box::new(message.clone())))
+                            .into(),
+                    );
+                }
+
+                let dialog = widget::dialog()
+                    .title(fl!("command-palette"))
+                    .secondary_action(
+                        widget::button::standard(fl!("cancel")).on_press(Message::DialogCancel),
+                    )
+                    .control(widget::column::with_children(controls).spacing(space_xxs));
+
+                dialog
+            }
+
+            DialogPage::QueueEnded(action) => {
+                let seconds = self
+                    .queue_end_countdown
+                    .map(|(_, deadline)| {
+                        deadline
+                            .saturating_duration_since(Instant::now())
+                            .as_secs_f32()
+                            .ceil() as u64
+                    })
+                    .unwrap_or(0);
+                let (body, confirm) = match action {
+                    QueueEndAction::Sleep => {
+                        (fl!("sleeping-in", seconds = seconds), fl!("sleep-now"))
+                    }
+                    _ => (fl!("quitting-in", seconds = seconds), fl!("quit-now")),
+                };
+
+                confirm_dialog(
+                    fl!("playback-finished"),
+                    body,
+                    confirm,
+                    Message::DialogComplete,
+                    None,
+                )
+            }
         };
 
         Some(dialog.into())
@@ -644,10 +1269,41 @@ impl cosmic::Application for AppModel {
             }),
         ];
 
-        // Tick
-        subscriptions.push(
-            iced::time::every(Duration::from_millis(TICK_INTERVAL_MS)).map(|_| Message::Tick),
-        );
+        // Tick, unless idle with reduced background activity. MPRIS still has to be polled
+        // until its connection is ready, after that its commands wake the app instead.
+        let idle = self.config.reduce_background_activity
+            && self.playback_service.status() != PlaybackStatus::Playing
+            && self.mpris_connection_rx.is_none()
+            && !self.is_updating
+            && self.title_progress.is_none()
+            && self.queue_end_countdown.is_none();
+
+        if idle {
+            subscriptions.push(Subscription::run_with_id(
+                "mpris-wake",
+                futures::stream::unfold(self.mpris_wake.clone(), |wake| async move {
+                    wake.notified().await;
+                    Some((Message::Tick, wake))
+                }),
+            ));
+        } else {
+            subscriptions.push(
+                iced::time::every(Duration::from_millis(TICK_INTERVAL_MS)).map(|_| Message::Tick),
+            );
+        }
+
+        // Counts down while paused, the subscription restarts whenever playback pauses again
+        if self.config.release_device_after_secs > 0
+            && self.playback_service.status() == PlaybackStatus::Paused
+            && !self.playback_service.device_released()
+        {
+            subscriptions.push(
+                iced::time::every(Duration::from_secs(
+                    self.config.release_device_after_secs as u64,
+                ))
+                .map(|_| Message::ReleaseAudioDevice),
+            );
+        }
 
         Subscription::batch(subscriptions)
     }
@@ -712,6 +1368,27 @@ impl cosmic::Application for AppModel {
 
         match message {
             // Open dialog for adding library locations
+            Message::AddScanExclude => {
+                let pattern = self.scan_exclude_input.trim().to_string();
+                if pattern.is_empty() || self.config.scan_excludes.contains(&pattern) {
+                    return Task::none();
+                }
+
+                if let Err(err) = globset::Glob::new(&pattern) {
+                    return self.show_toast(fl!(
+                        "invalid-scan-exclude",
+                        pattern = pattern,
+                        error = err.kind().to_string()
+                    ));
+                }
+
+                let mut scan_excludes = self.config.scan_excludes.clone();
+                scan_excludes.push(pattern);
+                config_set!(scan_excludes, scan_excludes.clone());
+                self.config.scan_excludes = scan_excludes;
+                self.scan_exclude_input.clear();
+            }
+
             Message::AddLibraryDialog => {
                 return cosmic::task::future(async move {
                     let dialog = file_chooser::open::Dialog::new().title(fl!("add-location"));
@@ -724,7 +1401,7 @@ impl cosmic::Application for AppModel {
                                 if let Ok(decoded) = decode(u.path()) {
                                     paths.push(decoded.into_owned());
                                 } else {
-                                    eprintln!("Can't decode URL.");
+                                    log::error!("Can't decode URL.");
                                 }
                             }
                             Message::SelectedPaths(paths)
@@ -735,72 +1412,331 @@ impl cosmic::Application for AppModel {
                 });
             }
 
-            Message::AppTheme(app_theme) => {
-                config_set!(app_theme, app_theme);
-                return self.update_config();
+            // Pick a folder for exporting the selected tracks
+            Message::ExportSelected => {
+                return cosmic::task::future(async move {
+                    let dialog = file_chooser::open::Dialog::new().title(fl!("export-selected"));
+
+                    match dialog.open_folder().await {
+                        Ok(response) => match decode(response.url().path()) {
+                            Ok(decoded) => Message::ExportDestination(decoded.into_owned()),
+                            Err(_) => {
+                                log::error!("Can't decode URL.");
+                                Message::Noop
+                            }
+                        },
+                        Err(file_chooser::Error::Cancelled) => Message::Noop,
+                        Err(why) => {
+                            log::error!("{why}");
+                            Message::Noop
+                        }
+                    }
+                });
             }
 
-            Message::AddSelectedToPlaylist(destination_id) => {
-                let source_id = match self.view_playlist {
-                    Some(id) => id,
-                    None => return Task::none(),
-                };
-
-                // Get selected tracks from source playlist
-                let selected_tracks: Vec<Track> = match self.playlist_service.get(source_id) {
-                    Ok(playlist) => playlist
-                        .selected()
-                        .iter()
-                        .map(|t| {
-                            let mut track = (*t).clone();
-                            track.selected = false;
-                            track.generate_entry_id();
-                            track.update_date_added();
-                            track
-                        })
-                        .collect(),
-                    Err(_) => return Task::none(),
-                };
+            // Pick a folder to turn into a playlist
+            Message::ImportFolderDialog => {
+                return cosmic::task::future(async move {
+                    let dialog =
+                        file_chooser::open::Dialog::new().title(fl!("import-folder-as-playlist"));
 
-                self.add_tracks_to_playlist_with_duplicate_policy(destination_id, selected_tracks);
+                    match dialog.open_folder().await {
+                        Ok(response) => match decode(response.url().path()) {
+                            Ok(decoded) => {
+                                Message::ImportFolderAsPlaylist(PathBuf::from(decoded.into_owned()))
+                            }
+                            Err(_) => {
+                                log::error!("Can't decode URL.");
+                                Message::Noop
+                            }
+                        },
+                        Err(file_chooser::Error::Cancelled) => Message::Noop,
+                        Err(why) => {
+                            log::error!("{why}");
+                            Message::Noop
+                        }
+                    }
+                });
             }
 
-            Message::AddNowPlayingToPlaylist(destination_id) => {
-                if let Some(now_playing) = self.playback_service.now_playing().clone() {
-                    if let Some(now_playing_data) = self
-                        .library
-                        .from_id(&now_playing.clone().id.unwrap_or_default())
-                    {
-                        let track = Track {
-                            path: now_playing_data.0.clone(),
-                            metadata: now_playing_data.1.clone(),
-                            ..Default::default()
-                        };
+            // Files that aren't in the library yet are read first, the playlist is created
+            // when that finishes
+            Message::ImportFolderAsPlaylist(folder) => {
+                if self.is_updating || !self.initial_load_complete {
+                    return Task::none();
+                }
 
-                        self.add_tracks_to_playlist_with_duplicate_policy(
-                            destination_id,
-                            vec![track],
-                        );
-                    }
+                let files = audio_files_in(&folder);
+                if files.is_empty() {
+                    return self.show_toast(fl!(
+                        "no-audio-files-in-folder",
+                        path = folder.display().to_string()
+                    ));
+                }
+
+                let in_library: HashSet<&Path> = self
+                    .library
+                    .media
+                    .iter()
+                    .map(|(path, metadata)| metadata.media_file(path))
+                    .collect();
+                let missing: Vec<(PathBuf, MediaMetaData)> = files
+                    .iter()
+                    .filter(|file| !in_library.contains(file.as_path()))
+                    .map(|file| (file.clone(), MediaMetaData::new()))
+                    .collect();
+
+                if missing.is_empty() {
+                    return self.create_folder_playlist(&folder, &files);
                 }
+
+                self.pending_folder_import = Some((folder, files));
+                return self.rescan_tracks(missing);
             }
 
-            Message::CancelLibraryUpdate => {
-                if let Some(cancel_token) = self.library_update_cancel.take() {
-                    cancel_token.cancel();
-                    self.is_updating = false;
+            Message::RescanSelected => {
+                let paths: Vec<PathBuf> = self
+                    .get_active_playlist()
+                    .map(|playlist| {
+                        playlist
+                            .selected_iter()
+                            .map(|track| track.path.clone())
+                            .collect()
+                    })
+                    .unwrap_or_default();
+
+                if !paths.is_empty() {
+                    return Task::done(cosmic::Action::App(Message::RescanTracks(paths)));
                 }
             }
 
-            Message::ChangeTrack(index) => {
-                let Some(playlist_id) = self.view_playlist else {
+            // Re-read metadata for only these files, much faster than a full update
+            Message::RescanTracks(paths) => {
+                if self.is_updating || !self.initial_load_complete {
                     return Task::none();
-                };
-
-                let is_in_library = {
-                    let Ok(playlist) = self.playlist_service.get(playlist_id) else {
-                        return Task::none();
-                    };
+                }
+
+                let tracks: Vec<(PathBuf, MediaMetaData)> = paths
+                    .into_iter()
+                    .filter_map(|path| {
+                        let metadata = self.library.media.get(&path)?.clone();
+                        Some((path, metadata))
+                    })
+                    .collect();
+                if tracks.is_empty() {
+                    return self.show_toast(fl!("not-in-library"));
+                }
+
+                return self.rescan_tracks(tracks);
+            }
+
+            // Cue tracks are keyed below their file, a file that never made it into the library
+            // is read from scratch
+            Message::RetryScanError(path) => {
+                if self.is_updating || !self.initial_load_complete {
+                    return Task::none();
+                }
+
+                let mut tracks: Vec<(PathBuf, MediaMetaData)> = self
+                    .library
+                    .media
+                    .iter()
+                    .filter(|(key, metadata)| metadata.media_file(key) == path)
+                    .map(|(key, metadata)| (key.clone(), metadata.clone()))
+                    .collect();
+                if tracks.is_empty() {
+                    tracks.push((path, MediaMetaData::new()));
+                }
+
+                return self.rescan_tracks(tracks);
+            }
+
+            Message::ExportDestination(destination) => {
+                self.dialog_pages.push_back(DialogPage::ExportTracks {
+                    destination: PathBuf::from(destination),
+                    template: self.config.export_name_template.clone(),
+                    format: self.config.export_format,
+                    bitrate_kbps: self.config.export_bitrate_kbps,
+                });
+                let focus = widget::text_input::focus(widget::Id::new(EXPORT_TEMPLATE_INPUT_ID));
+
+                if self.missing_export_encoders.is_some() {
+                    return focus;
+                }
+
+                // Looking for encoders goes through every GStreamer plugin
+                let check_encoders = cosmic::task::future(async {
+                    let missing = tokio::task::spawn_blocking(|| {
+                        ExportFormat::ALL
+                            .into_iter()
+                            .filter(|format| !encoder_available(*format))
+                            .collect()
+                    })
+                    .await
+                    .unwrap_or_default();
+
+                    Message::ExportEncodersChecked(missing)
+                });
+                return Task::batch([focus, check_encoders]);
+            }
+
+            Message::ExportEncodersChecked(missing) => {
+                self.missing_export_encoders = Some(missing);
+            }
+
+            Message::ExportProgress(current, total) => {
+                let percent = if total > 0 {
+                    current as f32 / total as f32 * 100.0
+                } else {
+                    100.0
+                };
+                self.set_update_progress(current as f32, total as f32, percent);
+            }
+
+            Message::ExportFinished(copied, failed) => {
+                self.is_updating = false;
+
+                if failed > 0 {
+                    log::info!("Exported {copied} tracks, {failed} failed");
+                    return self.show_toast(fl!("export-failed", count = failed));
+                } else {
+                    log::info!("Exported {copied} tracks");
+                }
+            }
+
+            Message::AnalyzeLoudness => {
+                if self.is_updating || !self.initial_load_complete {
+                    return Task::none();
+                }
+
+                let mut files: Vec<PathBuf> = self
+                    .library
+                    .media
+                    .iter()
+                    // Cue tracks share a file, analyzing it whole wouldn't describe any of them
+                    .filter(|(_, metadata)| {
+                        metadata.replay_gain_track_db.is_none() && !metadata.is_cue_track()
+                    })
+                    .map(|(path, _)| path.clone())
+                    .collect();
+                if files.is_empty() {
+                    return Task::none();
+                }
+                files.sort();
+
+                self.is_updating = true;
+                self.update_activity = UpdateActivity::LoudnessAnalysis;
+                self.update_progress = 0.0;
+                self.update_total = 0.0;
+                self.update_percent = 0.0;
+
+                let (tx, rx) = ProgressSender::channel();
+
+                let cancel_token = CancellationToken::new();
+                self.library_update_cancel = Some(cancel_token.clone());
+
+                LibraryService::analyze_loudness(files, tx, cancel_token);
+
+                return cosmic::Task::stream(ReceiverStream::new(rx))
+                    .map(|progress| cosmic::Action::App(Message::LibraryProgress(progress)));
+            }
+
+            Message::AppTheme(app_theme) => {
+                config_set!(app_theme, app_theme);
+                return self.update_config();
+            }
+
+            Message::AddSelectedToPlaylist(destination_id) => {
+                let source_id = match self.view_playlist {
+                    Some(id) => id,
+                    None => return Task::none(),
+                };
+
+                // Get selected tracks from source playlist
+                let selected_tracks: Vec<Track> = match self.playlist_service.get(source_id) {
+                    Ok(playlist) => playlist
+                        .selected()
+                        .iter()
+                        .map(|t| {
+                            let mut track = (*t).clone();
+                            track.selected = false;
+                            track.generate_entry_id();
+                            track.update_date_added();
+                            track
+                        })
+                        .collect(),
+                    Err(_) => return Task::none(),
+                };
+
+                self.add_tracks_to_playlist_with_duplicate_policy(destination_id, selected_tracks);
+            }
+
+            Message::AddNowPlayingToPlaylist(destination_id) => {
+                if let Some(now_playing) = self.playback_service.now_playing().clone() {
+                    if let Some(now_playing_data) = self
+                        .library
+                        .from_id(&now_playing.clone().id.unwrap_or_default())
+                    {
+                        let track = Track {
+                            path: now_playing_data.0.clone(),
+                            metadata: now_playing_data.1.clone(),
+                            ..Default::default()
+                        };
+
+                        self.add_tracks_to_playlist_with_duplicate_policy(
+                            destination_id,
+                            vec![track],
+                        );
+                    }
+                }
+            }
+
+            // A scan finishing since this was scheduled keeps its own confirmation up
+            Message::CleanUpArtwork => {
+                let artwork_filenames = self.artwork_filenames_in_use();
+                self.image_store.cleanup_unused(&artwork_filenames);
+            }
+
+            Message::ClearScanComplete => {
+                if self.scan_complete.is_some_and(|(_, finished_at)| {
+                    finished_at.elapsed() >= Duration::from_secs(SCAN_COMPLETE_LINGER_SECS)
+                }) {
+                    self.scan_complete = None;
+                }
+            }
+
+            Message::CloseToast(id) => {
+                self.toasts.remove(id);
+            }
+
+            Message::Balance(balance) => {
+                let balance = balance.clamp(-MAX_BALANCE_PERCENT, MAX_BALANCE_PERCENT);
+
+                if self.config.balance == balance {
+                    return Task::none();
+                }
+
+                config_set!(balance, balance);
+                self.config.balance = balance;
+                self.sync_balance_from_config();
+            }
+
+            Message::CancelLibraryUpdate => {
+                if let Some(cancel_token) = self.library_update_cancel.take() {
+                    cancel_token.cancel();
+                    self.is_updating = false;
+                }
+            }
+
+            Message::ChangeTrack(index) => {
+                let Some(playlist_id) = self.view_playlist else {
+                    return Task::none();
+                };
+
+                let is_in_library = {
+                    let Ok(playlist) = self.playlist_service.get(playlist_id) else {
+                        return Task::none();
+                    };
 
                     let Some(track) = playlist.tracks().get(index) else {
                         return Task::none();
@@ -882,7 +1818,9 @@ impl cosmic::Application for AppModel {
             }
 
             Message::DialogCancel => {
-                let _ = self.dialog_pages.pop_front();
+                if let Some(DialogPage::QueueEnded(_)) = self.dialog_pages.pop_front() {
+                    self.queue_end_countdown = None;
+                }
             }
 
             Message::DialogComplete => {
@@ -891,7 +1829,9 @@ impl cosmic::Application for AppModel {
                 };
 
                 match &dialog_page {
-                    DialogPage::NewPlaylist(name) if !Self::playlist_name_is_valid(name) => {
+                    DialogPage::NewPlaylist(name) | DialogPage::SaveQueueAsPlaylist(name)
+                        if !Self::playlist_name_is_valid(name) =>
+                    {
                         return Task::none();
                     }
                     DialogPage::RenamePlaylist { name, .. }
@@ -899,6 +1839,38 @@ impl cosmic::Application for AppModel {
                     {
                         return Task::none();
                     }
+                    DialogPage::GoToTrack { number, .. }
+                        if Self::parse_track_number(number, self.visible_track_count())
+                            .is_none() =>
+                    {
+                        return Task::none();
+                    }
+                    DialogPage::ExportTracks { template, .. } if template.trim().is_empty() => {
+                        return Task::none();
+                    }
+                    DialogPage::EnterLibraryPath(path)
+                        if Self::entered_library_path(path).is_none() =>
+                    {
+                        return Task::none();
+                    }
+                    // Keep the dialog open until the running update is done
+                    DialogPage::ExportTracks { .. } if self.is_updating => {
+                        return self.show_toast(fl!("export-while-updating"));
+                    }
+                    // Keep the dialog open so another format can be picked
+                    DialogPage::ExportTracks { format, .. }
+                        if self
+                            .missing_export_encoders
+                            .as_ref()
+                            .is_some_and(|missing| missing.contains(format)) =>
+                    {
+                        let name = self
+                            .export_format_labels
+                            .get(format.selected_index())
+                            .cloned()
+                            .unwrap_or_default();
+                        return self.show_toast(fl!("export-encoder-missing", format = name));
+                    }
                     DialogPage::ConfirmPlaylistDuplicate { .. } => {
                         self.handle_playlist_duplicate_dialog_action(
                             PlaylistDuplicateDialogAction::Add,
@@ -921,11 +1893,15 @@ impl cosmic::Application for AppModel {
                                 self.rebuild_nav_from_order(items, id);
                             }
                             Err(err) => {
-                                eprintln!("Error creating playlist: {}", err);
+                                log::error!("Error creating playlist: {}", err);
                             }
                         }
                     }
 
+                    DialogPage::SaveQueueAsPlaylist(name) => {
+                        return Task::done(cosmic::Action::App(Message::SaveQueueAsPlaylist(name)));
+                    }
+
                     DialogPage::RenamePlaylist { id, name } => {
                         match self.playlist_service.rename(id, name) {
                             Ok(_) => {
@@ -936,7 +1912,7 @@ impl cosmic::Application for AppModel {
                                 self.rebuild_nav_from_order(items, id);
                             }
                             Err(err) => {
-                                eprintln!("Error renaming playlist: {}", err);
+                                log::error!("Error renaming playlist: {}", err);
                             }
                         }
                     }
@@ -959,39 +1935,165 @@ impl cosmic::Application for AppModel {
                                 self.rebuild_nav_from_order(items, library_id);
                             }
                             Err(err) => {
-                                eprintln!("Error deleting playlist: {}", err);
+                                log::error!("Error deleting playlist: {}", err);
                             }
                         }
                     }
 
-                    DialogPage::DeleteSelectedFromPlaylist => {
-                        let playlist_id = match self.view_playlist {
-                            Some(id) => id,
-                            None => return Task::none(),
-                        };
+                    DialogPage::DeleteSelectedFromPlaylist { dont_ask_again } => {
+                        if dont_ask_again {
+                            config_set!(confirm_remove_from_playlist, false);
+                            self.config.confirm_remove_from_playlist = false;
+                        }
+
+                        return self.remove_selected_from_view_playlist();
+                    }
+
+                    DialogPage::GoToTrack { number, play } => {
+                        if let Some(number) =
+                            Self::parse_track_number(&number, self.visible_track_count())
+                        {
+                            return self.go_to_track_number(number, play);
+                        }
+                    }
+
+                    DialogPage::ResetPlayStats => {
+                        return self.reset_play_stats(None);
+                    }
 
-                        if let Err(err) = self.playlist_service.remove_selected(playlist_id) {
-                            eprintln!("Error removing tracks: {}", err);
+                    DialogPage::ExportTracks {
+                        destination,
+                        template,
+                        format,
+                        bitrate_kbps,
+                    } => {
+                        // Cue tracks are only a range of their file, they can't be copied on
+                        // their own and count as failed
+                        let mut skipped = 0;
+                        let jobs: Vec<(PathBuf, PathBuf)> = self
+                            .get_active_playlist()
+                            .map(|playlist| {
+                                playlist
+                                    .selected_iter()
+                                    .filter(|track| {
+                                        let is_cue_track = track.metadata.is_cue_track();
+                                        skipped += usize::from(is_cue_track);
+                                        !is_cue_track
+                                    })
+                                    .map(|track| {
+                                        let target = export_target(
+                                            &destination,
+                                            &template,
+                                            &track.metadata,
+                                            &track.path,
+                                            format,
+                                        );
+                                        (track.path.clone(), target)
+                                    })
+                                    .collect()
+                            })
+                            .unwrap_or_default();
+
+                        config_set!(export_name_template, template);
+                        config_set!(export_format, format);
+                        config_set!(export_bitrate_kbps, bitrate_kbps);
+
+                        if jobs.is_empty() {
+                            if skipped > 0 {
+                                return self.show_toast(fl!("export-failed", count = skipped));
+                            }
+                            return Task::none();
                         }
 
-                        self.invalidate_all_caches();
+                        self.is_updating = true;
+                        self.update_activity = UpdateActivity::Export;
+                        self.update_progress = 0.0;
+                        self.update_total = 0.0;
+                        self.update_percent = 0.0;
 
-                        // Reset viewport scroll to top
-                        self.list_start = 0;
-                        self.grid_start = 0;
-                        return scrollable::scroll_to(
-                            self.list_scroll_id.clone(),
-                            AbsoluteOffset {
-                                x: Some(0.0 as f32),
-                                y: Some(0.0 as f32),
-                            },
-                        );
+                        let cancel_token = CancellationToken::new();
+                        self.library_update_cancel = Some(cancel_token.clone());
+
+                        let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
+
+                        // Transcoding takes a while per file, report progress as each finishes
+                        std::thread::spawn(move || {
+                            let total = jobs.len();
+                            let mut copied = 0;
+                            let mut failed = skipped;
+
+                            for (index, (source, target)) in jobs.into_iter().enumerate() {
+                                if cancel_token.is_cancelled() {
+                                    break;
+                                }
+
+                                match transcode_track(&source, &target, format, bitrate_kbps) {
+                                    Ok(_) => copied += 1,
+                                    Err(e) => {
+                                        log::error!("Error exporting {:?}: {}", source, e);
+                                        failed += 1;
+                                    }
+                                }
+
+                                let _ = tx.send(Message::ExportProgress(index + 1, total));
+                            }
+
+                            let _ = tx.send(Message::ExportFinished(copied, failed));
+                        });
+
+                        return cosmic::Task::stream(UnboundedReceiverStream::new(rx))
+                            .map(cosmic::Action::App);
                     }
 
                     DialogPage::ConfirmPlaylistDuplicate { .. } => {}
+
+                    DialogPage::QueueEnded(action) => {
+                        self.queue_end_countdown = None;
+                        return self.run_queue_end_action(action);
+                    }
+
+                    DialogPage::EnterLibraryPath(path) => {
+                        if let Some(path) = Self::entered_library_path(&path) {
+                            return Task::done(cosmic::Action::App(Message::SelectedPaths(vec![
+                                path.to_string_lossy().to_string(),
+                            ])));
+                        }
+                    }
+
+                    DialogPage::CommandPalette(_) => {
+                        if let Some((_, message)) = self.command_palette_entries.first() {
+                            return Task::done(cosmic::Action::App(message.clone()));
+                        }
+                    }
+
+                    DialogPage::ScanPreview(_) => {
+                        return Task::done(cosmic::Action::App(Message::UpdateLibrary));
+                    }
                 };
             }
 
+            Message::JumpToLetter(letter) => {
+                return self.jump_to_letter(letter);
+            }
+
+            Message::ToggleFooterQueue(show_footer_queue) => {
+                if self.config.show_footer_queue == show_footer_queue {
+                    return Task::none();
+                }
+
+                config_set!(show_footer_queue, show_footer_queue);
+                self.config.show_footer_queue = show_footer_queue;
+            }
+
+            Message::ToggleJumpStrip(show_jump_strip) => {
+                if self.config.show_jump_strip == show_jump_strip {
+                    return Task::none();
+                }
+
+                config_set!(show_jump_strip, show_jump_strip);
+                self.config.show_jump_strip = show_jump_strip;
+            }
+
             Message::KeyPressed(modifiers, key, status) => {
                 return self.handle_key_pressed(modifiers, key, status);
             }
@@ -1005,8 +2107,40 @@ impl cosmic::Application for AppModel {
                 }
             }
 
+            Message::LibraryLoaded(library) => {
+                // Only the startup load is applied; scans replace the library themselves
+                if self.initial_load_complete {
+                    return Task::none();
+                }
+
+                return self.finish_load_data(library);
+            }
+
+            Message::LibraryPathMounts(mounts) => {
+                self.library_path_mounts = mounts;
+            }
+
+            // Usually means no file chooser portal is installed, let the folder be typed in
             Message::LibraryPathOpenError(why) => {
-                eprintln!("{why}");
+                log::error!("{why}");
+                self.dialog_pages
+                    .push_back(DialogPage::EnterLibraryPath(String::new()));
+                return widget::text_input::focus(widget::Id::new(LIBRARY_PATH_INPUT_ID));
+            }
+
+            Message::ManualGain(id, gain_db) => {
+                let gain_db = gain_db.clamp(MIN_MANUAL_GAIN_DB, MAX_MANUAL_GAIN_DB);
+                // Zero gain is stored as unset so untouched tracks stay untouched on disk
+                let gain_db = (gain_db != 0.0).then_some(gain_db);
+
+                if let Some(metadata) = self.library.from_id_mut(&id) {
+                    metadata.manual_gain_db = gain_db;
+                    self.playback_service.set_manual_gain(&id, gain_db);
+                }
+            }
+
+            Message::ManualGainReleased => {
+                return self.save_library();
             }
 
             Message::LibraryProgress(progress) => match progress {
@@ -1015,66 +2149,179 @@ impl cosmic::Application for AppModel {
                     total,
                     percent,
                 } => {
-                    self.update_progress = current;
-                    self.update_total = total;
-                    self.update_percent = percent;
-                    self.update_progress_display = format!(
-                        "{} {}/{} ({:.2}%)",
-                        fl!("updating-library"),
-                        current,
-                        total,
-                        percent
-                    );
+                    self.set_update_progress(current, total, percent);
                 }
 
-                LibraryProgress::PartialUpdate(media) => {
+                LibraryProgress::PartialUpdate(mut media) => {
+                    self.keep_user_data(&mut media);
                     self.library.media = media;
-                    if let Err(e) = self.library_service.save(&self.library) {
-                        eprintln!("Error saving partial library update: {}", e);
-                    }
                     self.update_library_playlist();
+                    return self.save_library();
                 }
 
-                LibraryProgress::Complete(library) => {
+                LibraryProgress::Complete(mut library) => {
+                    self.keep_user_data(&mut library.media);
                     self.library = library;
-                    let save_result = self.library_service.save(&self.library);
                     self.update_library_playlist();
 
-                    if let Err(e) = save_result {
-                        eprintln!("Error saving library: {}", e);
-                    } else {
-                        let artwork_filenames = self.artwork_filenames_in_use();
-                        self.image_store.cleanup_unused(&artwork_filenames);
-                    }
+                    // Artwork is only removed once the saved library no longer refers to it
+                    let save = self
+                        .library_service
+                        .save_in_background(self.library.clone());
+                    let save = cosmic::task::future(async move {
+                        match save.await {
+                            Ok(()) => Message::CleanUpArtwork,
+                            Err(e) => {
+                                log::error!("Error saving library: {}", e);
+                                Message::Noop
+                            }
+                        }
+                    });
+
                     self.is_updating = false;
+                    self.scan_complete = Some((self.library.media.len(), Instant::now()));
+
+                    let clear_scan_complete = cosmic::task::future(async {
+                        tokio::time::sleep(Duration::from_secs(SCAN_COMPLETE_LINGER_SECS)).await;
+                        Message::ClearScanComplete
+                    });
+
+                    if self.config.analyze_loudness_on_update {
+                        return Task::batch([
+                            save,
+                            clear_scan_complete,
+                            Task::done(cosmic::Action::App(Message::AnalyzeLoudness)),
+                        ]);
+                    }
+                    return Task::batch([save, clear_scan_complete]);
                 }
 
-                LibraryProgress::Cancelled => {
-                    self.is_updating = false;
+                LibraryProgress::LoudnessUpdate(results) => {
+                    for (path, (gain_db, peak)) in results {
+                        if let Some(metadata) = self.library.media.get_mut(&path) {
+                            metadata.replay_gain_track_db = Some(gain_db);
+                            metadata.replay_gain_track_peak = Some(peak);
+                        }
+                    }
                     self.update_library_playlist();
-                    log::info!("Library update cancelled")
+                    return self.save_library();
                 }
-            },
 
-            Message::GridViewScroll(viewport) => {
-                let scroll_offset = viewport.absolute_offset().y;
-                let viewport_height = viewport.bounds().height;
-                let viewport_width = viewport.bounds().width;
-                let layout = self.grid_layout_metrics(viewport_width);
+                LibraryProgress::TracksRescanned { media, failed } => {
+                    self.is_updating = false;
+                    let updated = media.len();
 
-                let requested_grid_start = if scroll_offset == 0.0 || layout.row_stride == 0.0 {
-                    0
-                } else {
-                    (scroll_offset / layout.row_stride).floor() as usize
-                };
+                    let rescanned: HashSet<PathBuf> = media
+                        .iter()
+                        .map(|(path, metadata)| metadata.media_file(path).to_path_buf())
+                        .collect();
+                    if self
+                        .scan_errors
+                        .iter()
+                        .any(|error| rescanned.contains(&error.path))
+                    {
+                        self.scan_errors
+                            .retain(|error| !rescanned.contains(&error.path));
+                        self.save_scan_report();
+                    }
 
-                let visible_row_count =
-                    Self::grid_visible_row_count_for_stride(viewport_height, layout.row_stride);
-                let visible_card_count = self.visible_grid_card_count();
-                let total_rows = Self::grid_total_rows(visible_card_count, layout.column_count);
-                let max_start = total_rows.saturating_sub(visible_row_count.max(1));
-                let clamped_grid_start = requested_grid_start.min(max_start);
+                    if updated > 0 {
+                        self.library.media.extend(media);
+                        self.library.resolve_duplicate_ids();
+                        if let Err(e) = self.library_service.save(&self.library) {
+                            log::error!("Error saving library: {}", e);
+                        }
+                        self.update_library_playlist();
+                    }
+
+                    let toast = self.show_toast(if failed > 0 {
+                        fl!(
+                            "tracks-rescanned-with-failures",
+                            count = updated,
+                            failed = failed
+                        )
+                    } else {
+                        fl!("tracks-rescanned", count = updated)
+                    });
+
+                    if let Some((folder, files)) = self.pending_folder_import.take() {
+                        return Task::batch([self.create_folder_playlist(&folder, &files), toast]);
+                    }
+                    return toast;
+                }
+
+                LibraryProgress::LoudnessComplete => {
+                    self.is_updating = false;
+                }
+
+                LibraryProgress::UnreachablePaths(paths) => {
+                    let toasts: Vec<_> = paths
+                        .into_iter()
+                        .map(|path| {
+                            let message = fl!("skipped-unreachable-folder", path = path.clone());
+                            // Offer to stop scanning the folder until the next restart
+                            let toast = widget::toaster::Toast::new(message)
+                                .action(fl!("disable"), move |_| {
+                                    Message::SetLibraryPathDisabled(path.clone(), true)
+                                });
+                            self.toasts.push(toast).map(cosmic::Action::App)
+                        })
+                        .collect();
+                    return Task::batch(toasts);
+                }
+
+                LibraryProgress::MissingPlugins {
+                    plugins,
+                    track_count,
+                } => {
+                    return self.show_toast(fl!(
+                        "missing-plugins-scan",
+                        count = track_count,
+                        plugins = plugins.join(", ")
+                    ));
+                }
+
+                // A rescan only replaces the errors of the files it read
+                LibraryProgress::ScanErrors(errors) => {
+                    self.scan_errors
+                        .retain(|existing| !errors.iter().any(|error| error.path == existing.path));
+                    self.scan_errors.extend(errors);
+                    self.scan_errors.sort_by(|a, b| a.path.cmp(&b.path));
+                    self.save_scan_report();
+                }
+
+                LibraryProgress::Cancelled => {
+                    self.is_updating = false;
+                    self.pending_folder_import = None;
+                    self.update_library_playlist();
+                    log::info!("Library update cancelled")
+                }
+            },
+
+            Message::GridViewScroll(viewport) => {
+                let scroll_offset = viewport.absolute_offset().y;
+                let viewport_height = viewport.bounds().height;
+                let viewport_width = viewport.bounds().width;
+                let layout = self.grid_layout_metrics(viewport_width);
+
+                let requested_grid_start = if scroll_offset == 0.0 || layout.row_stride == 0.0 {
+                    0
+                } else {
+                    (scroll_offset / layout.row_stride).floor() as usize
+                };
+
+                let visible_row_count =
+                    Self::grid_visible_row_count_for_stride(viewport_height, layout.row_stride);
+                let visible_card_count = self.visible_grid_card_count();
+                let total_rows = Self::grid_total_rows(visible_card_count, layout.column_count);
+                let max_start = total_rows.saturating_sub(visible_row_count.max(1));
+                let clamped_grid_start = requested_grid_start.min(max_start);
+                if clamped_grid_start != self.grid_start {
+                    self.grid_scrolling_up = clamped_grid_start < self.grid_start;
+                }
                 self.grid_start = clamped_grid_start;
+                self.grid_viewport_size = Some(viewport.bounds().size());
+                self.warm_grid_artwork();
 
                 if layout.row_stride > 0.0 && clamped_grid_start != requested_grid_start {
                     return scrollable::scroll_to(
@@ -1318,10 +2565,17 @@ impl cosmic::Application for AppModel {
                 config_set!(list_column_order, list_column_order);
             }
 
+            Message::OpenContainingFolder(path) => {
+                let folder = path.parent().unwrap_or(&path);
+                if let Err(err) = open::that_detached(folder) {
+                    log::warn!("Failed to open {:?}: {}", folder, err);
+                }
+            }
+
             Message::LaunchUrl(url) => match open::that_detached(&url) {
                 Ok(()) => {}
                 Err(err) => {
-                    eprintln!("failed to open {url:?}: {err}");
+                    log::warn!("failed to open {url:?}: {err}");
                 }
             },
 
@@ -1332,12 +2586,119 @@ impl cosmic::Application for AppModel {
                 return widget::text_input::focus(widget::Id::new(NEW_PLAYLIST_INPUT_ID));
             }
 
+            // Ask for a name before snapshotting the queue
+            Message::SaveQueue => {
+                if self
+                    .playback_service
+                    .session()
+                    .is_none_or(|session| session.order.is_empty())
+                {
+                    return Task::none();
+                }
+
+                self.dialog_pages
+                    .push_back(DialogPage::SaveQueueAsPlaylist(String::new()));
+                return widget::text_input::focus(widget::Id::new(SAVE_QUEUE_INPUT_ID));
+            }
+
+            Message::SaveQueueAsPlaylist(name) => {
+                let Some(session) = self.playback_service.session() else {
+                    return Task::none();
+                };
+
+                let tracks: Vec<Track> = session
+                    .order
+                    .iter()
+                    .cloned()
+                    .map(|mut track| {
+                        track.selected = false;
+                        track.generate_entry_id();
+                        track.update_date_added();
+                        track
+                    })
+                    .collect();
+
+                let id = match self.playlist_service.create(name) {
+                    Ok(id) => id,
+                    Err(err) => {
+                        log::error!("Error creating playlist: {}", err);
+                        return Task::none();
+                    }
+                };
+
+                if let Err(err) = self.playlist_service.add_tracks(id, tracks) {
+                    log::error!("Error adding tracks: {}", err);
+                }
+
+                self.view_playlist = Some(id);
+
+                // Rebuild nav preserving order
+                let items = self.build_ordered_nav_items();
+                self.rebuild_nav_from_order(items, id);
+                self.invalidate_all_caches();
+            }
+
+            Message::SaveVolume => {
+                if self.volume_changed_at.is_some_and(|changed_at| {
+                    changed_at.elapsed() >= Duration::from_millis(VOLUME_SAVE_DELAY_MS)
+                }) {
+                    self.volume_changed_at = None;
+                    self.save_volume();
+                }
+            }
+
+            Message::GoToTrack => {
+                if self.view_playlist.is_none() {
+                    return Task::none();
+                }
+                self.dialog_pages.push_back(DialogPage::GoToTrack {
+                    number: String::new(),
+                    play: false,
+                });
+                return widget::text_input::focus(widget::Id::new(GO_TO_TRACK_INPUT_ID));
+            }
+
+            Message::GoToTrackNumber(number) => {
+                return self.go_to_track_number(number, false);
+            }
+
+            Message::CommandPalette => {
+                if self.dialog_pages.front().is_some() {
+                    return Task::none();
+                }
+                self.command_palette_entries = command_palette::entries(self, "");
+                self.dialog_pages
+                    .push_back(DialogPage::CommandPalette(String::new()));
+                return widget::text_input::focus(widget::Id::new(COMMAND_PALETTE_INPUT_ID));
+            }
+
+            Message::RunCommand(message) => {
+                if let Some(DialogPage::CommandPalette(_)) = self.dialog_pages.front() {
+                    let _ = self.dialog_pages.pop_front();
+                }
+                return Task::done(cosmic::Action::App(*message));
+            }
+
+            Message::ShowPlaylist(playlist_id) => {
+                return self.show_playlist(playlist_id);
+            }
+
+            // Show library tracks matching an album or artist picked in the command palette
+            Message::SearchLibrary(term) => {
+                let Ok(library_id) = self.playlist_service.get_library().map(|l| l.id()) else {
+                    return Task::none();
+                };
+                let task = self.show_playlist(library_id);
+                self.search_term = Some(term);
+                return task;
+            }
+
             Message::Noop => {}
 
             // Kick off the Rename Playlist dialog
             Message::RenamePlaylist => match self.nav.data(self.nav.active()) {
                 Some(Page::Playlist(id)) => {
-                    if self.playlist_service.get(*id).ok().unwrap().is_library() {
+                    if !self.playlist_service.get(*id).ok().unwrap().is_user() {
                         return Task::none();
                     }
                     let name = self.nav.text(self.nav.active()).unwrap_or("");
@@ -1350,11 +2711,206 @@ impl cosmic::Application for AppModel {
                 _ => {}
             },
 
+            Message::MinDurationSecs(secs) => {
+                let min_duration_secs =
+                    Some(u64::from(secs.min(MAX_MIN_DURATION_SECS))).filter(|secs| *secs > 0);
+
+                if self.config.min_duration_secs == min_duration_secs {
+                    return Task::none();
+                }
+
+                config_set!(min_duration_secs, min_duration_secs);
+                self.config.min_duration_secs = min_duration_secs;
+            }
+
+            Message::ResumeRewindSecs(resume_rewind_secs) => {
+                let resume_rewind_secs = resume_rewind_secs.min(MAX_RESUME_REWIND_SECS);
+
+                if self.config.resume_rewind_secs == resume_rewind_secs {
+                    return Task::none();
+                }
+
+                config_set!(resume_rewind_secs, resume_rewind_secs);
+                self.config.resume_rewind_secs = resume_rewind_secs;
+                self.playback_service
+                    .set_resume_rewind_secs(resume_rewind_secs);
+            }
+
+            Message::ReleaseDeviceAfterSecs(secs) => {
+                let release_device_after_secs = secs.min(MAX_RELEASE_DEVICE_AFTER_SECS);
+
+                if self.config.release_device_after_secs == release_device_after_secs {
+                    return Task::none();
+                }
+
+                config_set!(release_device_after_secs, release_device_after_secs);
+                self.config.release_device_after_secs = release_device_after_secs;
+            }
+
+            Message::ReleaseAudioDevice => self.playback_service.release_device(),
+
+            Message::FadeCurve(fade_curve) => {
+                if self.config.fade_curve == fade_curve {
+                    return Task::none();
+                }
+
+                config_set!(fade_curve, fade_curve);
+                self.config.fade_curve = fade_curve;
+                self.playback_service.set_fade_curve(fade_curve);
+            }
+
+            Message::FadeInMs(fade_in_ms) => {
+                let fade_in_ms = fade_in_ms.min(MAX_FADE_MS);
+
+                if self.config.fade_in_ms == fade_in_ms {
+                    return Task::none();
+                }
+
+                config_set!(fade_in_ms, fade_in_ms);
+                self.config.fade_in_ms = fade_in_ms;
+                self.playback_service
+                    .set_fade_durations(fade_in_ms, self.config.fade_out_ms);
+            }
+
+            Message::FadeOutMs(fade_out_ms) => {
+                let fade_out_ms = fade_out_ms.min(MAX_FADE_MS);
+
+                if self.config.fade_out_ms == fade_out_ms {
+                    return Task::none();
+                }
+
+                config_set!(fade_out_ms, fade_out_ms);
+                self.config.fade_out_ms = fade_out_ms;
+                self.playback_service
+                    .set_fade_durations(self.config.fade_in_ms, fade_out_ms);
+            }
+
+            Message::ResetPlayStats(id) => match id {
+                Some(id) => return self.reset_play_stats(Some(&id)),
+                None => self.dialog_pages.push_back(DialogPage::ResetPlayStats),
+            },
+
+            // Show the playing track in the playlist it's playing from
+            Message::GoToNowPlaying => {
+                let Some(session) = self.playback_service.session() else {
+                    return Task::none();
+                };
+                let playlist_id = session.playlist_id;
+                let Some(playing) = session.order.get(session.index) else {
+                    return Task::none();
+                };
+                let Some(index) =
+                    self.playlist_service
+                        .get(playlist_id)
+                        .ok()
+                        .and_then(|playlist| {
+                            playlist.tracks().iter().position(|track| {
+                                track.entry_id == playing.entry_id && track.path == playing.path
+                            })
+                        })
+                else {
+                    return Task::none();
+                };
+
+                let nav_id =
+                    self.nav
+                        .iter()
+                        .find_map(|nav_id| match self.nav.data::<Page>(nav_id) {
+                            Some(Page::Playlist(pid)) if *pid == playlist_id => Some(nav_id),
+                            _ => None,
+                        });
+                if let Some(nav_id) = nav_id {
+                    self.nav.activate(nav_id);
+                }
+                self.view_playlist = Some(playlist_id);
+                self.search_term = None;
+
+                if self.config.view_mode != ViewMode::List {
+                    config_set!(view_mode, ViewMode::List);
+                    self.config.view_mode = ViewMode::List;
+                }
+
+                let _ = self.playlist_service.clear_selection(playlist_id);
+                let _ = self.playlist_service.select_track(playlist_id, index);
+                self.list_last_selected_id = Some(index);
+                self.invalidate_all_caches();
+
+                return Task::batch([self.update_title(), self.scroll_list_to_row(index)]);
+            }
+
+            // Jump from a track, like the now playing one, back to its row in the library list
+            Message::RevealInLibrary(id) => {
+                let Ok(library) = self.playlist_service.get_library() else {
+                    return Task::none();
+                };
+                let library_id = library.id();
+
+                let Some(index) = library
+                    .tracks()
+                    .iter()
+                    .position(|track| track.metadata.id.as_deref() == Some(id.as_str()))
+                else {
+                    return Task::none();
+                };
+
+                let nav_id =
+                    self.nav
+                        .iter()
+                        .find_map(|nav_id| match self.nav.data::<Page>(nav_id) {
+                            Some(Page::Playlist(pid)) if *pid == library_id => Some(nav_id),
+                            _ => None,
+                        });
+                if let Some(nav_id) = nav_id {
+                    self.nav.activate(nav_id);
+                }
+                self.view_playlist = Some(library_id);
+                self.search_term = None;
+
+                if self.config.view_mode != ViewMode::List {
+                    config_set!(view_mode, ViewMode::List);
+                    self.config.view_mode = ViewMode::List;
+                }
+
+                let _ = self.playlist_service.clear_selection(library_id);
+                let _ = self.playlist_service.select_track(library_id, index);
+                self.list_last_selected_id = Some(index);
+                self.invalidate_all_caches();
+
+                return Task::batch([self.update_title(), self.scroll_list_to_row(index)]);
+            }
+
+            Message::RevealNowPlayingInLibrary => {
+                if let Some(id) = self
+                    .playback_service
+                    .now_playing()
+                    .and_then(|metadata| metadata.id.clone())
+                {
+                    return Task::done(cosmic::Action::App(Message::RevealInLibrary(id)));
+                }
+            }
+
+            Message::DuplicatePlaylist(id) => {
+                match self.playlist_service.duplicate(id) {
+                    Ok(new_id) => {
+                        self.view_playlist = Some(new_id);
+
+                        // Rebuild nav preserving order
+                        let items = self.build_ordered_nav_items();
+                        self.rebuild_nav_from_order(items, new_id);
+                        self.invalidate_all_caches();
+                        return self.update_title();
+                    }
+                    Err(err) => {
+                        log::error!("Error duplicating playlist: {}", err);
+                    }
+                }
+            }
+
             // Kick off the delete playlist dialog
             Message::DeletePlaylist => {
                 if let Some(Page::Playlist(id)) = self.nav.data(self.nav.active()) {
                     if let Some(p) = self.playlist_service.get(*id).ok() {
-                        if !p.is_library() {
+                        if p.is_user() {
                             self.dialog_pages.push_back(DialogPage::DeletePlaylist(*id));
                         }
                     }
@@ -1373,7 +2929,17 @@ impl cosmic::Application for AppModel {
                 state_set!(playlist_nav_order, order);
             }
 
+            Message::NavDragEnter(entity) => self.set_nav_drop_target(Some(entity)),
+
+            Message::NavDragLeave(entity) => {
+                if self.nav_drop_target == Some(entity) {
+                    self.set_nav_drop_target(None);
+                }
+            }
+
             Message::NavDrop(entity, data) => {
+                self.set_nav_drop_target(None);
+
                 let Some(Page::Playlist(destination_id)) = self.nav.data(entity) else {
                     return Task::none();
                 };
@@ -1419,6 +2985,19 @@ impl cosmic::Application for AppModel {
                     return Task::none();
                 }
 
+                // Dropping on Favorites marks the tracks rather than copying them
+                if self
+                    .playlist_service
+                    .get(*destination_id)
+                    .is_ok_and(|playlist| playlist.is_favorites())
+                {
+                    let ids: Vec<String> = dragged_tracks
+                        .into_iter()
+                        .filter_map(|track| track.metadata.id)
+                        .collect();
+                    return self.set_favorites(&ids, true);
+                }
+
                 self.add_tracks_to_playlist_with_duplicate_policy(*destination_id, dragged_tracks);
             }
 
@@ -1427,6 +3006,34 @@ impl cosmic::Application for AppModel {
                     .next(self.state.repeat_mode.clone(), self.state.repeat);
             }
 
+            Message::PlayQueueIndex(index) => {
+                self.playback_service.play_session_index(index);
+            }
+
+            Message::ShuffleLibrary => self.shuffle_library(),
+
+            Message::StartBpmRadio => return self.start_bpm_radio(),
+
+            Message::BpmRadioTolerance(tolerance) => {
+                let bpm_radio_tolerance = tolerance.clamp(1, MAX_BPM_RADIO_TOLERANCE);
+
+                if self.config.bpm_radio_tolerance == bpm_radio_tolerance {
+                    return Task::none();
+                }
+
+                config_set!(bpm_radio_tolerance, bpm_radio_tolerance);
+                self.config.bpm_radio_tolerance = bpm_radio_tolerance;
+            }
+
+            Message::ToggleBpmRadioSameGenre(bpm_radio_same_genre) => {
+                if self.config.bpm_radio_same_genre == bpm_radio_same_genre {
+                    return Task::none();
+                }
+
+                config_set!(bpm_radio_same_genre, bpm_radio_same_genre);
+                self.config.bpm_radio_same_genre = bpm_radio_same_genre;
+            }
+
             Message::PlaybackTransitionMode(playback_transition_mode) => {
                 config_set!(playback_transition_mode, playback_transition_mode);
                 self.config.playback_transition_mode = playback_transition_mode;
@@ -1439,6 +3046,154 @@ impl cosmic::Application for AppModel {
                 self.config.playlist_duplicate_policy = playlist_duplicate_policy;
             }
 
+            Message::QueueEndAction(queue_end_action) => {
+                if self.config.queue_end_action == queue_end_action {
+                    return Task::none();
+                }
+                config_set!(queue_end_action, queue_end_action);
+                self.config.queue_end_action = queue_end_action;
+            }
+
+            Message::EmptyPlayAction(empty_play_action) => {
+                if self.config.empty_play_action == empty_play_action {
+                    return Task::none();
+                }
+                config_set!(empty_play_action, empty_play_action);
+                self.config.empty_play_action = empty_play_action;
+            }
+
+            Message::LogLevel(log_level) => {
+                if self.config.log_level == log_level {
+                    return Task::none();
+                }
+                config_set!(log_level, log_level);
+                self.config.log_level = log_level;
+                logger::set_level(log_level.filter());
+            }
+
+            Message::CopyNowPlaying(format) => {
+                let Some(metadata) = self.playback_service.now_playing() else {
+                    return Task::none();
+                };
+                // Opened files aren't in the library, their title falls back to "Untitled"
+                let path = metadata
+                    .id
+                    .as_ref()
+                    .and_then(|id| self.library.from_id(id))
+                    .map(|(path, _)| path.clone())
+                    .unwrap_or_default();
+
+                let text = match format {
+                    ShareFormat::Plain => {
+                        fill_template(&self.config.share_template, metadata, &path, |value| {
+                            value.to_string()
+                        })
+                    }
+                    ShareFormat::Markdown => fill_template(
+                        &self.config.share_markdown_template,
+                        metadata,
+                        &path,
+                        escape_markdown,
+                    ),
+                };
+
+                return Task::batch([
+                    cosmic::iced::clipboard::write(text),
+                    self.show_toast(fl!("now-playing-copied")),
+                ]);
+            }
+
+            Message::ShareTemplate(format, template) => match format {
+                ShareFormat::Plain => {
+                    config_set!(share_template, template.clone());
+                    self.config.share_template = template;
+                }
+                ShareFormat::Markdown => {
+                    config_set!(share_markdown_template, template.clone());
+                    self.config.share_markdown_template = template;
+                }
+            },
+
+            Message::QuickAddCurrent => {
+                let Some((path, metadata)) = self
+                    .playback_service
+                    .now_playing()
+                    .and_then(|now_playing| now_playing.id.clone())
+                    .and_then(|id| self.library.from_id(&id))
+                    .map(|(path, metadata)| (path.clone(), metadata.clone()))
+                else {
+                    return Task::none();
+                };
+
+                let configured = self.config.quick_add_playlist.filter(|id| {
+                    self.playlist_service
+                        .get(*id)
+                        .is_ok_and(|playlist| playlist.is_user())
+                });
+
+                // Created on first use, or again if the chosen playlist was deleted
+                let destination_id = match configured {
+                    Some(id) => id,
+                    None => match self.playlist_service.create(fl!("quick-add")) {
+                        Ok(id) => {
+                            let items = self.build_ordered_nav_items();
+                            self.rebuild_nav_from_order(items, self.view_playlist.unwrap_or(id));
+                            config_set!(quick_add_playlist, Some(id));
+                            self.config.quick_add_playlist = Some(id);
+                            id
+                        }
+                        Err(err) => {
+                            log::error!("Error creating quick add playlist: {}", err);
+                            return Task::none();
+                        }
+                    },
+                };
+
+                let title = display_text(
+                    &non_empty_text(metadata.title.as_deref())
+                        .unwrap_or_else(|| path_display_name(&path)),
+                );
+                let track = Track {
+                    path,
+                    metadata,
+                    ..Default::default()
+                };
+                let is_duplicate = self
+                    .playlist_service
+                    .split_tracks_by_duplicate(destination_id, vec![track.clone()])
+                    .is_ok_and(|(_, duplicates)| !duplicates.is_empty());
+
+                self.add_tracks_to_playlist_with_duplicate_policy(destination_id, vec![track]);
+                self.invalidate_all_caches();
+
+                let playlist = self
+                    .playlist_service
+                    .get(destination_id)
+                    .map(|playlist| playlist.name().to_string())
+                    .unwrap_or_default();
+
+                // Ask shows the duplicate dialog instead
+                return match (is_duplicate, self.config.playlist_duplicate_policy) {
+                    (false, _) | (true, PlaylistDuplicatePolicy::Allow) => {
+                        self.show_toast(fl!("quick-added", title = title, playlist = playlist))
+                    }
+                    (true, PlaylistDuplicatePolicy::Disallow) => self.show_toast(fl!(
+                        "quick-add-duplicate",
+                        title = title,
+                        playlist = playlist
+                    )),
+                    (true, PlaylistDuplicatePolicy::Ask) => Task::none(),
+                };
+            }
+
+            Message::QuickAddPlaylist(id) => {
+                if self.config.quick_add_playlist == id {
+                    return Task::none();
+                }
+                config_set!(quick_add_playlist, id);
+                self.config.quick_add_playlist = id;
+            }
+
             Message::PlaylistDuplicateDialogAction(action) => {
                 self.handle_playlist_duplicate_dialog_action(action);
             }
@@ -1446,6 +3201,23 @@ impl cosmic::Application for AppModel {
             Message::PlayPause => {
                 match self.playback_service.status() {
                     PlaybackStatus::Stopped => {
+                        if self.playback_service.now_playing().is_none() {
+                            match self.config.empty_play_action {
+                                EmptyPlayAction::PlayView => {}
+                                // Falls back to the current view when the track is gone
+                                EmptyPlayAction::ResumeLastTrack => {
+                                    if self.resume_last_track() {
+                                        return Task::none();
+                                    }
+                                }
+                                EmptyPlayAction::ShuffleLibrary => {
+                                    return Task::done(cosmic::Action::App(
+                                        Message::ShuffleLibrary,
+                                    ));
+                                }
+                            }
+                        }
+
                         // Start playback from current view
                         if let Some(playlist_id) = self.view_playlist {
                             let mut started = false;
@@ -1467,26 +3239,101 @@ impl cosmic::Application for AppModel {
                                 started = true;
                             }
 
-                            if started {
-                                self.sync_playback_output_from_state();
-                                self.playback_service.play();
-                            }
-                        }
-                    }
-                    PlaybackStatus::Paused => {
-                        self.playback_service.play();
+                            if started {
+                                self.sync_playback_output_from_state();
+                                self.playback_service.play();
+                            }
+                        }
+                    }
+                    PlaybackStatus::Paused => {
+                        self.playback_service.play();
+                    }
+                    PlaybackStatus::Playing => {
+                        self.playback_service.pause();
+                    }
+                }
+            }
+
+            Message::EnqueueFile(request) => {
+                if !self.initial_load_complete {
+                    self.pending_open = Some(request);
+                    return Task::none();
+                }
+
+                let track = self.track_for_path(&request.path);
+                if !self.playback_service.enqueue(track, request.start_secs) {
+                    return Task::done(cosmic::Action::App(Message::OpenFile(request)));
+                }
+            }
+
+            Message::OpenFile(request) => {
+                // Wait for the library so tracks in it play with their metadata
+                if !self.initial_load_complete {
+                    self.pending_open = Some(request);
+                    return Task::none();
+                }
+
+                let library_index = self
+                    .playlist_service
+                    .get_library()
+                    .ok()
+                    .and_then(|library| {
+                        library
+                            .tracks()
+                            .iter()
+                            .position(|track| track.path == request.path)
+                    });
+
+                match library_index {
+                    Some(index) => {
+                        let Ok(library) = self.playlist_service.get_library() else {
+                            return Task::none();
+                        };
+                        self.playback_service.start_session(library, index, false);
                     }
-                    PlaybackStatus::Playing => {
-                        self.playback_service.pause();
+                    None => {
+                        // Files outside the library play on their own with minimal metadata
+                        let track = self.track_for_path(&request.path);
+                        let mut playlist = Playlist::new(fl!("now-playing"));
+                        playlist.push(track);
+                        self.playback_service.start_session(&playlist, 0, false);
                     }
                 }
+
+                self.sync_playback_output_from_state();
+                self.playback_service.play();
+                if let Some(start_secs) = request.start_secs {
+                    self.playback_service.seek_when_ready(start_secs);
+                }
             }
 
-            Message::Previous => {
-                self.playback_service.prev(self.state.repeat_mode.clone());
+            Message::Previous => self.previous_or_restart(),
+
+            Message::PreviousRestartThresholdSecs(secs) => {
+                let previous_restart_threshold_secs = secs.min(MAX_PREVIOUS_RESTART_THRESHOLD_SECS);
+
+                if self.config.previous_restart_threshold_secs == previous_restart_threshold_secs {
+                    return Task::none();
+                }
+
+                config_set!(
+                    previous_restart_threshold_secs,
+                    previous_restart_threshold_secs
+                );
+                self.config.previous_restart_threshold_secs = previous_restart_threshold_secs;
             }
 
             Message::Quit => {
+                // Remembered for the play button to resume next launch
+                if let Some(session) = self.playback_service.session() {
+                    if let Some(track) = session.order.get(session.index) {
+                        let progress = self.playback_service.progress();
+                        state_set!(last_track, Some((track.path.clone(), progress)));
+                    }
+                }
+                if self.volume_changed_at.take().is_some() {
+                    self.save_volume();
+                }
                 self.playback_service.stop();
                 process::exit(0);
             }
@@ -1503,10 +3350,69 @@ impl cosmic::Application for AppModel {
                 config_set!(library_paths, library_paths);
             }
 
+            Message::RemoveScanExclude(pattern) => {
+                let mut scan_excludes = self.config.scan_excludes.clone();
+                scan_excludes.retain(|existing| *existing != pattern);
+                config_set!(scan_excludes, scan_excludes.clone());
+                self.config.scan_excludes = scan_excludes;
+            }
+
             Message::RemoveSelectedFromPlaylist => {
+                if !self.config.confirm_remove_from_playlist {
+                    return self.remove_selected_from_view_playlist();
+                }
+
                 // Show confirmation dialog
                 self.dialog_pages
-                    .push_back(DialogPage::DeleteSelectedFromPlaylist);
+                    .push_back(DialogPage::DeleteSelectedFromPlaylist {
+                        dont_ask_again: false,
+                    });
+            }
+
+            Message::ToggleConfirmRemoveFromPlaylist(confirm_remove_from_playlist) => {
+                if self.config.confirm_remove_from_playlist == confirm_remove_from_playlist {
+                    return Task::none();
+                }
+
+                config_set!(confirm_remove_from_playlist, confirm_remove_from_playlist);
+                self.config.confirm_remove_from_playlist = confirm_remove_from_playlist;
+            }
+
+            Message::ScanExcludeInput(pattern) => {
+                self.scan_exclude_input = pattern;
+            }
+
+            Message::SeekHover(fraction) => {
+                self.seek_hover = fraction;
+            }
+
+            Message::SilenceBoundsReady(id, bounds) => {
+                // Applied on the next tick if the track is still playing
+                self.silence_pending.remove(&id);
+                self.silence_bounds.insert(id, bounds);
+            }
+
+            Message::SkipSilenceThresholdDb(threshold_db) => {
+                let threshold_db = threshold_db
+                    .clamp(MIN_SKIP_SILENCE_THRESHOLD_DB, MAX_SKIP_SILENCE_THRESHOLD_DB);
+
+                if self.config.skip_silence_threshold_db == threshold_db {
+                    return Task::none();
+                }
+
+                config_set!(skip_silence_threshold_db, threshold_db);
+                self.config.skip_silence_threshold_db = threshold_db;
+                // Found with the old threshold, the playing track is analyzed again
+                self.silence_bounds.clear();
+                self.playback_service.clear_silence_bounds();
+            }
+
+            Message::WaveformReady(id, peaks) => {
+                // Failed tracks stay pending so they aren't analyzed again this session
+                if let Some(peaks) = peaks {
+                    self.waveform_pending.remove(&id);
+                    self.waveforms.insert(id, peaks);
+                }
             }
 
             Message::SearchActivate => {
@@ -1547,7 +3453,7 @@ impl cosmic::Application for AppModel {
             Message::SelectAll => {
                 if let Some(playlist_id) = self.view_playlist {
                     if let Err(err) = self.playlist_service.select_all(playlist_id) {
-                        eprintln!("Error selecting all tracks: {}", err);
+                        log::error!("Error selecting all tracks: {}", err);
                     }
                 }
 
@@ -1560,41 +3466,71 @@ impl cosmic::Application for AppModel {
                 }
                 self.config.view_mode = view_mode;
 
-                let scroll_offset = match view_mode {
-                    ViewMode::List => self
-                        .calculate_list_view()
-                        .map(|view_model| view_model.scroll_offset),
-                    ViewMode::Grid => Some(self.grid_start as f32 * self.grid_row_stride()),
-                }
-                .unwrap_or(0.0);
-
-                return scrollable::scroll_to(
-                    self.list_scroll_id.clone(),
-                    AbsoluteOffset {
-                        x: Some(0.0),
-                        y: Some(scroll_offset),
-                    },
-                );
+                return self.scroll_to_view_start();
             }
 
             // Add selected paths from the Open dialog
+            // Folders already in the library are skipped. Ones inside a library folder are
+            // added with a warning, their tracks are scanned once either way.
             Message::SelectedPaths(paths) => {
                 let mut library_paths = self.config.library_paths.clone();
+                let mut notices = Vec::new();
 
                 for path in paths {
+                    let selected = Path::new(&path);
+
+                    if library_paths
+                        .iter()
+                        .any(|existing| Path::new(existing) == selected)
+                    {
+                        notices.push(fl!("folder-already-in-library", path = path.as_str()));
+                        continue;
+                    }
+
+                    if let Some(parent) = library_paths
+                        .iter()
+                        .find(|existing| selected.starts_with(Path::new(existing)))
+                    {
+                        notices.push(fl!(
+                            "folder-inside-library-folder",
+                            path = path.as_str(),
+                            parent = parent.as_str()
+                        ));
+                    }
+
                     library_paths.insert(path);
                 }
 
                 config_set!(library_paths, library_paths);
+
+                let toasts: Vec<_> = notices
+                    .into_iter()
+                    .map(|notice| self.show_toast(notice))
+                    .collect();
+                return Task::batch(toasts);
             }
 
             Message::SetVolume(volume) => {
-                let volume = volume.clamp(0, 100);
-                state_set!(volume, volume);
-                self.playback_service
-                    .set_volume(Self::normalized_volume(volume));
                 let muted = false;
                 state_set!(muted, muted);
+                return self.change_volume(volume);
+            }
+
+            Message::ShowTrackInfo(id) => {
+                let is_open =
+                    self.context_page == ContextPage::TrackInfo && self.core.window.show_context;
+                self.track_info_id = Some(id);
+
+                if !is_open {
+                    return Task::done(cosmic::Action::App(Message::ToggleContextPage(
+                        ContextPage::TrackInfo,
+                    )));
+                }
+            }
+
+            Message::SeekToChapter(start_ms) => {
+                self.playback_service
+                    .seek_accurate(start_ms as f32 / 1000.0);
             }
 
             Message::SliderSeek(time) => {
@@ -1602,6 +3538,11 @@ impl cosmic::Application for AppModel {
                 self.playback_service.set_progress(time);
             }
 
+            Message::SuspendFailed(err) => {
+                log::error!("{}", err);
+                return self.show_toast(fl!("suspend-failed"));
+            }
+
             Message::Surface(action) => {
                 return cosmic::task::message(cosmic::Action::Cosmic(
                     cosmic::app::Action::Surface(action),
@@ -1612,25 +3553,50 @@ impl cosmic::Application for AppModel {
                 self.poll_mpris_connection_ready();
                 self.playback_service.validate_session();
 
+                // Remember the current track so a finished play can be counted
+                let finished_id = self
+                    .playback_service
+                    .now_playing()
+                    .and_then(|metadata| metadata.id.clone());
+
                 // Process playback events
                 let events = self.playback_service.tick();
+                let mut missing_plugins = Vec::new();
+                let mut played = false;
                 for event in events {
                     match event {
                         PlaybackEvent::TrackEnded => {
+                            played |= self.record_play(finished_id.as_deref());
                             self.playback_service
                                 .next(self.state.repeat_mode.clone(), self.state.repeat);
+
+                            if self.config.continue_to_next_album
+                                && self.playback_service.status() == PlaybackStatus::Stopped
+                            {
+                                self.continue_to_next_album(finished_id.as_deref());
+                            }
+
+                            if self.playback_service.status() == PlaybackStatus::Stopped {
+                                self.start_queue_end_countdown();
+                            }
                         }
                         PlaybackEvent::GaplessTrackAdvanced
                         | PlaybackEvent::CrossfadeTrackAdvanced => {
+                            played |= self.record_play(finished_id.as_deref());
                             // Session index updates inside playback_service.tick()
                             // Just update MPRIS
                             self.update_mpris();
                         }
                         PlaybackEvent::Error(err) => {
-                            eprintln!("Playback error: {}", err);
+                            log::error!("Playback error: {}", err);
                             self.playback_service
                                 .next(self.state.repeat_mode.clone(), self.state.repeat);
                         }
+                        PlaybackEvent::MissingPlugin(plugin) => {
+                            if self.reported_missing_plugins.insert(plugin.clone()) {
+                                missing_plugins.push(plugin);
+                            }
+                        }
                         PlaybackEvent::PositionUpdate(_) => {
                             // Position already updated in service
                         }
@@ -1639,8 +3605,9 @@ impl cosmic::Application for AppModel {
 
                 // Handle MPRIS commands
                 let commands = self.playback_service.process_mpris_commands();
+                let mut save_volume = Task::none();
                 for cmd in commands {
-                    //println!("mpris message: {:?}", cmd);
+                    log::debug!("mpris message: {:?}", cmd);
                     match cmd {
                         MprisCommand::Play => {
                             self.start_session_maybe();
@@ -1659,7 +3626,7 @@ impl cosmic::Application for AppModel {
                         }
                         MprisCommand::Previous => {
                             self.start_session_maybe();
-                            self.playback_service.prev(self.state.repeat_mode.clone());
+                            self.previous_or_restart();
                         }
                         MprisCommand::Seek(offset_us) => {
                             let current = self.playback_service.progress();
@@ -1670,12 +3637,9 @@ impl cosmic::Application for AppModel {
                             self.playback_service.seek(pos_us as f32 / 1_000_000.0);
                         }
                         MprisCommand::SetVolume(vol) => {
-                            let output_volume = vol.clamp(0.0, 1.0);
-                            let volume = (output_volume * 100.0).clamp(0.0, 100.0) as i32;
-                            state_set!(volume, volume);
-                            self.playback_service.set_volume(output_volume);
                             let muted = false;
                             state_set!(muted, muted);
+                            save_volume = self.change_volume(vol);
                         }
                         MprisCommand::SetLoopStatus(status) => match status.as_str() {
                             "None" => {
@@ -1703,6 +3667,40 @@ impl cosmic::Application for AppModel {
                 }
 
                 self.update_mpris();
+                self.update_launcher_progress(false);
+                let mut tasks = vec![
+                    self.follow_current_track(),
+                    self.request_waveform(),
+                    self.skip_silence(),
+                    save_volume,
+                ];
+                if played {
+                    tasks.push(self.save_library());
+                }
+
+                // Playing again calls off a pending quit or sleep
+                if let Some((action, deadline)) = self.queue_end_countdown {
+                    if self.playback_service.status() != PlaybackStatus::Stopped {
+                        self.cancel_queue_end_countdown();
+                    } else if Instant::now() >= deadline {
+                        self.cancel_queue_end_countdown();
+                        tasks.push(self.run_queue_end_action(action));
+                    }
+                }
+
+                // The title only changes with the whole percentage, not every progress message
+                if self.title_progress_text() != self.title_progress {
+                    tasks.push(self.update_title());
+                }
+
+                if !missing_plugins.is_empty() {
+                    tasks.push(self.show_toast(fl!(
+                        "missing-plugins-playback",
+                        plugins = missing_plugins.join(", ")
+                    )));
+                }
+
+                return Task::batch(tasks);
             }
 
             Message::TitleSort(title_sort) => {
@@ -1721,6 +3719,44 @@ impl cosmic::Application for AppModel {
                 self.sort_all_playlists();
             }
 
+            Message::ToggleSortIgnoreArticles(sort_ignore_articles) => {
+                if self.config.sort_ignore_articles == sort_ignore_articles {
+                    return Task::none();
+                }
+
+                config_set!(sort_ignore_articles, sort_ignore_articles);
+                self.config.sort_ignore_articles = sort_ignore_articles;
+                self.sort_all_playlists();
+            }
+
+            Message::ToggleGaplessWithinAlbums(enabled) => {
+                if self.config.gapless_within_albums == enabled {
+                    return Task::none();
+                }
+
+                config_set!(gapless_within_albums, enabled);
+                self.config.gapless_within_albums = enabled;
+                self.playback_service.set_gapless_within_albums(enabled);
+            }
+
+            Message::ToggleMergeFeaturedArtists(merge) => {
+                if self.config.merge_featured_artists == merge {
+                    return Task::none();
+                }
+
+                config_set!(merge_featured_artists, merge);
+                self.config.merge_featured_artists = merge;
+            }
+
+            Message::ToggleTintFromArtwork(tint) => {
+                if self.config.tint_from_artwork == tint {
+                    return Task::none();
+                }
+
+                config_set!(tint_from_artwork, tint);
+                self.config.tint_from_artwork = tint;
+            }
+
             Message::ToggleRegenerateThumbnailsOnUpdate(regenerate) => {
                 if self.config.regenerate_thumbnails_on_update == regenerate {
                     return Task::none();
@@ -1730,7 +3766,92 @@ impl cosmic::Application for AppModel {
                 self.config.regenerate_thumbnails_on_update = regenerate;
             }
 
+            Message::ToggleFollowCurrentTrack(follow_current_track) => {
+                if self.config.follow_current_track == follow_current_track {
+                    return Task::none();
+                }
+
+                config_set!(follow_current_track, follow_current_track);
+                self.config.follow_current_track = follow_current_track;
+            }
+
+            Message::ToggleContinueToNextAlbum(continue_to_next_album) => {
+                if self.config.continue_to_next_album == continue_to_next_album {
+                    return Task::none();
+                }
+
+                config_set!(continue_to_next_album, continue_to_next_album);
+                self.config.continue_to_next_album = continue_to_next_album;
+            }
+
+            Message::ToggleAnalyzeLoudnessOnUpdate(analyze_loudness_on_update) => {
+                if self.config.analyze_loudness_on_update == analyze_loudness_on_update {
+                    return Task::none();
+                }
+
+                config_set!(analyze_loudness_on_update, analyze_loudness_on_update);
+                self.config.analyze_loudness_on_update = analyze_loudness_on_update;
+            }
+
+            Message::ToggleCollapseTagWhitespace(collapse) => {
+                if self.config.collapse_tag_whitespace == collapse {
+                    return Task::none();
+                }
+
+                config_set!(collapse_tag_whitespace, collapse);
+                self.config.collapse_tag_whitespace = collapse;
+            }
+
+            Message::ToggleAccurateSeeking(accurate_seeking) => {
+                if self.config.accurate_seeking == accurate_seeking {
+                    return Task::none();
+                }
+
+                config_set!(accurate_seeking, accurate_seeking);
+                self.config.accurate_seeking = accurate_seeking;
+                self.playback_service.set_accurate_seeking(accurate_seeking);
+            }
+
+            Message::ToggleReplayGainNormalization(replay_gain_normalization) => {
+                if self.config.replay_gain_normalization == replay_gain_normalization {
+                    return Task::none();
+                }
+
+                config_set!(replay_gain_normalization, replay_gain_normalization);
+                self.config.replay_gain_normalization = replay_gain_normalization;
+                self.playback_service
+                    .set_replay_gain_normalization(replay_gain_normalization);
+            }
+
+            Message::ToggleSkipSilence(skip_silence) => {
+                if self.config.skip_silence == skip_silence {
+                    return Task::none();
+                }
+
+                config_set!(skip_silence, skip_silence);
+                self.config.skip_silence = skip_silence;
+                if !skip_silence {
+                    self.playback_service.clear_silence_bounds();
+                }
+            }
+
+            Message::ToggleClearNowPlayingOnStop(clear_now_playing_on_stop) => {
+                if self.config.clear_now_playing_on_stop == clear_now_playing_on_stop {
+                    return Task::none();
+                }
+
+                config_set!(clear_now_playing_on_stop, clear_now_playing_on_stop);
+                self.config.clear_now_playing_on_stop = clear_now_playing_on_stop;
+                self.playback_service
+                    .set_clear_now_playing_on_stop(clear_now_playing_on_stop);
+                self.update_mpris();
+            }
+
             Message::ToggleContextPage(context_page) => {
+                if context_page == ContextPage::Debug && !self.debug_enabled {
+                    return Task::none();
+                }
+
                 if self.context_page == context_page {
                     // Close the context drawer if the toggled context page is the same.
                     self.core.window.show_context = !self.core.window.show_context;
@@ -1740,6 +3861,11 @@ impl cosmic::Application for AppModel {
                     self.core.window.show_context = true;
                 }
 
+                // The track info page goes back to following the selection once it's closed
+                if !self.core.window.show_context || self.context_page != ContextPage::TrackInfo {
+                    self.track_info_id = None;
+                }
+
                 let scroll_offset = match self.config.view_mode {
                     ViewMode::List => self
                         .calculate_list_view()
@@ -1785,6 +3911,109 @@ impl cosmic::Application for AppModel {
                 config_set!(list_show_track_total_column, list_show_track_total_column);
             }
 
+            Message::ToggleReduceBackgroundActivity(reduce_background_activity) => {
+                if self.config.reduce_background_activity == reduce_background_activity {
+                    return Task::none();
+                }
+
+                config_set!(reduce_background_activity, reduce_background_activity);
+                self.config.reduce_background_activity = reduce_background_activity;
+            }
+
+            Message::ToggleShowLauncherProgress(show_launcher_progress) => {
+                if self.config.show_launcher_progress == show_launcher_progress {
+                    return Task::none();
+                }
+
+                config_set!(show_launcher_progress, show_launcher_progress);
+                self.config.show_launcher_progress = show_launcher_progress;
+                self.update_launcher_progress(true);
+            }
+
+            Message::ToggleShowWaveformPreview(show_waveform_preview) => {
+                if self.config.show_waveform_preview == show_waveform_preview {
+                    return Task::none();
+                }
+
+                config_set!(show_waveform_preview, show_waveform_preview);
+                self.config.show_waveform_preview = show_waveform_preview;
+            }
+
+            Message::TogglePreviousRestartsTrack(previous_restarts_track) => {
+                if self.config.previous_restarts_track == previous_restarts_track {
+                    return Task::none();
+                }
+
+                config_set!(previous_restarts_track, previous_restarts_track);
+                self.config.previous_restarts_track = previous_restarts_track;
+            }
+
+            Message::SetLibraryPathDisabled(path, disabled) => {
+                if disabled {
+                    self.disabled_library_paths.insert(path);
+                } else {
+                    self.disabled_library_paths.remove(&path);
+                }
+            }
+
+            Message::ToggleFavorite(id) => {
+                let Some((_, metadata)) = self.library.from_id(&id) else {
+                    return Task::none();
+                };
+                let favorite = !metadata.favorite;
+                return self.set_favorites(&[id], favorite);
+            }
+
+            Message::ToggleFavoriteNowPlaying => {
+                if let Some(id) = self
+                    .playback_service
+                    .now_playing()
+                    .and_then(|metadata| metadata.id.clone())
+                {
+                    return Task::done(cosmic::Action::App(Message::ToggleFavorite(id)));
+                }
+            }
+
+            Message::TogglePathFallbacks(path_fallbacks) => {
+                if self.config.path_fallbacks == path_fallbacks {
+                    return Task::none();
+                }
+
+                config_set!(path_fallbacks, path_fallbacks);
+                self.config.path_fallbacks = path_fallbacks;
+            }
+
+            Message::TogglePlayViewShuffles(play_view_shuffles) => {
+                if self.config.play_view_shuffles == play_view_shuffles {
+                    return Task::none();
+                }
+
+                config_set!(play_view_shuffles, play_view_shuffles);
+                self.config.play_view_shuffles = play_view_shuffles;
+            }
+
+            Message::ToggleOpenReplacesQueue(open_replaces_queue) => {
+                if self.config.open_replaces_queue == open_replaces_queue {
+                    return Task::none();
+                }
+
+                config_set!(open_replaces_queue, open_replaces_queue);
+                self.config.open_replaces_queue = open_replaces_queue;
+            }
+
+            Message::ToggleRememberScrollPosition(remember_scroll_position) => {
+                if self.config.remember_scroll_position == remember_scroll_position {
+                    return Task::none();
+                }
+
+                config_set!(remember_scroll_position, remember_scroll_position);
+                self.config.remember_scroll_position = remember_scroll_position;
+
+                if !remember_scroll_position {
+                    self.scroll_positions.clear();
+                }
+            }
+
             Message::ToggleListDiscNumberColumn(list_show_disc_number_column) => {
                 config_set!(list_show_disc_number_column, list_show_disc_number_column);
             }
@@ -1797,6 +4026,10 @@ impl cosmic::Application for AppModel {
                 config_set!(list_show_genre_column, list_show_genre_column);
             }
 
+            Message::ToggleListBpmColumn(list_show_bpm_column) => {
+                config_set!(list_show_bpm_column, list_show_bpm_column);
+            }
+
             Message::ToggleListFilePathColumn(list_show_file_path_column) => {
                 config_set!(list_show_file_path_column, list_show_file_path_column);
             }
@@ -1809,12 +4042,17 @@ impl cosmic::Application for AppModel {
                 config_set!(list_show_title_column, list_show_title_column);
             }
 
+            Message::ToggleFooter => {
+                let footer_hidden = !self.state.footer_hidden;
+                state_set!(footer_hidden, footer_hidden);
+            }
+
             Message::ToggleMute => {
                 let muted = !self.state.muted;
                 self.playback_service.set_volume(if muted {
                     0.0
                 } else {
-                    Self::normalized_volume(self.state.volume)
+                    Self::clamp_volume(self.config.volume)
                 });
                 state_set!(muted, muted);
             }
@@ -1839,6 +4077,25 @@ impl cosmic::Application for AppModel {
                     .set_repeat_state(self.state.repeat_mode.clone(), self.state.repeat);
             }
 
+            // Album buttons pick the shuffle mode for the whole session
+            // Album cards play or shuffle just that session, the shuffle setting is left alone
+            Message::PlayAlbum(id) => {
+                self.play_album(&id, false);
+                self.update_mpris();
+            }
+
+            Message::PlayCurrentView => {
+                let shuffle = self.config.play_view_shuffles;
+                state_set!(shuffle, shuffle);
+                self.play_current_view(shuffle);
+                self.update_mpris();
+            }
+
+            Message::ShuffleAlbum(id) => {
+                self.play_album(&id, true);
+                self.update_mpris();
+            }
+
             Message::ToggleShuffle => {
                 let shuffle = !self.state.shuffle;
                 state_set!(shuffle, shuffle);
@@ -1850,10 +4107,38 @@ impl cosmic::Application for AppModel {
 
             Message::UpdateConfig(config) => {
                 let playback_transition_mode = config.playback_transition_mode;
+                let library_paths_changed = self.config.library_paths != config.library_paths;
+                let volume = self.config.volume;
                 self.config = config;
+                // A volume still waiting to be saved is newer than the one that was read
+                if self.volume_changed_at.is_some() {
+                    self.config.volume = volume;
+                }
+                if library_paths_changed {
+                    self.update_library_path_track_counts();
+                }
                 self.playback_service
                     .set_transition_mode(playback_transition_mode);
                 self.sync_crossfade_duration_from_config();
+                self.playback_service
+                    .set_gapless_within_albums(self.config.gapless_within_albums);
+                self.sync_balance_from_config();
+                self.playback_service
+                    .set_clear_now_playing_on_stop(self.config.clear_now_playing_on_stop);
+                self.playback_service
+                    .set_resume_rewind_secs(self.config.resume_rewind_secs);
+                self.playback_service
+                    .set_accurate_seeking(self.config.accurate_seeking);
+                self.playback_service
+                    .set_replay_gain_normalization(self.config.replay_gain_normalization);
+                self.playback_service
+                    .set_fade_durations(self.config.fade_in_ms, self.config.fade_out_ms);
+                self.playback_service.set_fade_curve(self.config.fade_curve);
+                logger::set_level(self.config.log_level.filter());
+
+                if library_paths_changed {
+                    return self.check_library_path_mounts();
+                }
             }
 
             Message::UpdateDialog(dialog_page) => match dialog_page {
@@ -1862,6 +4147,11 @@ impl cosmic::Application for AppModel {
                         .update_front(DialogPage::NewPlaylist(name));
                 }
 
+                DialogPage::SaveQueueAsPlaylist(name) => {
+                    self.dialog_pages
+                        .update_front(DialogPage::SaveQueueAsPlaylist(name));
+                }
+
                 DialogPage::RenamePlaylist { id, name } => {
                     self.dialog_pages
                         .update_front(DialogPage::RenamePlaylist { id: id, name: name });
@@ -1872,22 +4162,84 @@ impl cosmic::Application for AppModel {
                         .update_front(DialogPage::DeletePlaylist(id));
                 }
 
-                DialogPage::DeleteSelectedFromPlaylist => {}
+                DialogPage::DeleteSelectedFromPlaylist { dont_ask_again } => {
+                    self.dialog_pages
+                        .update_front(DialogPage::DeleteSelectedFromPlaylist { dont_ask_again });
+                }
+
+                DialogPage::GoToTrack { number, play } => {
+                    self.dialog_pages
+                        .update_front(DialogPage::GoToTrack { number, play });
+                }
+
+                DialogPage::ResetPlayStats => {}
+
+                DialogPage::ExportTracks {
+                    destination,
+                    template,
+                    format,
+                    bitrate_kbps,
+                } => {
+                    self.dialog_pages.update_front(DialogPage::ExportTracks {
+                        destination,
+                        template,
+                        format,
+                        bitrate_kbps,
+                    });
+                }
 
                 DialogPage::ConfirmPlaylistDuplicate { .. } => {}
+
+                DialogPage::QueueEnded(_) => {}
+
+                DialogPage::EnterLibraryPath(path) => {
+                    self.dialog_pages
+                        .update_front(DialogPage::EnterLibraryPath(path));
+                }
+
+                DialogPage::CommandPalette(query) => {
+                    self.command_palette_entries = command_palette::entries(self, &query);
+                    self.dialog_pages
+                        .update_front(DialogPage::CommandPalette(query));
+                }
+
+                DialogPage::ScanPreview(_) => {}
             },
 
-            Message::UpdateLibrary => {
+            // Play statistics and favorites changed since the update are rolled back as well
+            Message::UndoLastScan => {
                 if self.is_updating {
                     return Task::none();
                 }
+                let Some(library) = self.library_undo.take() else {
+                    return Task::none();
+                };
+
+                self.library = library;
+                if let Err(e) = self.library_service.save(&self.library) {
+                    log::error!("Error saving library: {}", e);
+                }
+                self.update_library_playlist();
+                return self.show_toast(fl!("library-update-undone"));
+            }
+
+            Message::UpdateLibrary => {
+                // Wait for the library on disk to load so the scan can't race it
+                if self.is_updating || !self.initial_load_complete {
+                    return Task::none();
+                }
                 self.is_updating = true;
+                self.update_activity = UpdateActivity::Library;
+                self.scan_complete = None;
+                self.library_undo = Some(self.library.clone());
                 self.update_progress = 0.0;
+                self.update_total = 0.0;
+                self.update_percent = 0.0;
 
                 let library_paths = self.config.library_paths.clone();
                 let xdg_dirs = self.app_xdg_dirs.clone();
 
-                let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
+                let (tx, rx) = ProgressSender::channel();
 
                 // Create cancellation token
                 let cancel_token = CancellationToken::new();
@@ -1897,35 +4249,74 @@ impl cosmic::Application for AppModel {
                     self.image_store.clear();
                 }
 
+                self.scan_errors.clear();
+                self.save_scan_report();
+
                 // Spawn the scan with cancellation support
                 LibraryService::scan_library(
                     library_paths,
+                    self.disabled_library_paths.clone(),
+                    self.config.scan_excludes.clone(),
                     xdg_dirs,
                     tx,
                     cancel_token,
                     self.config.regenerate_thumbnails_on_update,
+                    self.config.collapse_tag_whitespace,
+                    self.config.min_duration_secs,
+                    self.library.media.clone(),
                 );
 
-                return cosmic::Task::stream(UnboundedReceiverStream::new(rx))
+                return cosmic::Task::stream(ReceiverStream::new(rx))
                     .map(|progress| cosmic::Action::App(Message::LibraryProgress(progress)));
             }
 
+            // Walk the library folders off the UI thread and show what an update would change
+            Message::PreviewLibraryUpdate => {
+                if self.is_updating || !self.initial_load_complete {
+                    return Task::none();
+                }
+
+                let library_paths = self.config.library_paths.clone();
+                let disabled_paths = self.disabled_library_paths.clone();
+                let scan_excludes = self.config.scan_excludes.clone();
+                let previous_media = self.library.media.clone();
+
+                return cosmic::task::future(async move {
+                    let preview = tokio::task::spawn_blocking(move || {
+                        LibraryService::preview_scan(
+                            library_paths,
+                            disabled_paths,
+                            scan_excludes,
+                            &previous_media,
+                        )
+                    })
+                    .await;
+
+                    match preview {
+                        Ok(preview) => Message::ScanPreviewReady(preview),
+                        Err(err) => {
+                            log::error!("Error previewing library update: {}", err);
+                            Message::Noop
+                        }
+                    }
+                });
+            }
+
+            Message::ScanPreviewReady(preview) => {
+                self.dialog_pages
+                    .push_back(DialogPage::ScanPreview(preview));
+            }
+
             Message::VolumeDown => {
-                let volume = (self.state.volume as f32 - 10.0).clamp(0.0, 100.0) as i32;
-                state_set!(volume, volume);
-                self.playback_service
-                    .set_volume(Self::normalized_volume(volume));
                 let muted = false;
                 state_set!(muted, muted);
+                return self.change_volume(self.config.volume - 0.1);
             }
 
             Message::VolumeUp => {
-                let volume = (self.state.volume as f32 + 10.0).clamp(0.0, 100.0) as i32;
-                state_set!(volume, volume);
-                self.playback_service
-                    .set_volume(Self::normalized_volume(volume));
                 let muted = false;
                 state_set!(muted, muted);
+                return self.change_volume(self.config.volume + 0.1);
             }
 
             Message::WindowResized(size) => {
@@ -1933,6 +4324,7 @@ impl cosmic::Application for AppModel {
                 let window_height = size.height;
                 state_set!(window_width, window_width);
                 state_set!(window_height, window_height);
+                self.warm_grid_artwork();
             }
 
             Message::ZoomIn => match self.config.view_mode {
@@ -1986,9 +4378,9 @@ impl cosmic::Application for AppModel {
             // on_activate: normal click behavior
             nav_activate,
             // on_dnd_enter
-            |_entity, _offered_mimes| Action::None,
+            |entity, _offered_mimes| Action::App(Message::NavDragEnter(entity)),
             // on_dnd_leave
-            |_entity| Action::None,
+            |entity| Action::App(Message::NavDragLeave(entity)),
             // on_dnd_drop
             |entity, data, _action| Action::App(Message::NavDrop(entity, data.unwrap())),
             self.nav_dnd_id,
@@ -2012,33 +4404,73 @@ impl cosmic::Application for AppModel {
         if let Some(Page::Playlist(pid)) = self.nav.data(id) {
             // Check if we're switching to a different playlist
             let is_switching = self.view_playlist != Some(*pid);
+            let previous_playlist = self.view_playlist;
 
             self.view_playlist = Some(*pid);
 
             if is_switching {
                 // Reset state when switching playlists
                 self.list_last_selected_id = None;
-                self.list_start = 0; // Reset scroll position state
-                self.grid_start = 0;
 
-                return Task::batch([
-                    self.update_title(),
-                    scrollable::scroll_to(
-                        self.list_scroll_id.clone(),
-                        AbsoluteOffset {
-                            x: Some(0.0 as f32),
-                            y: Some(0.0 as f32),
-                        },
-                    ),
-                ]);
+                if let Some(previous_id) = previous_playlist {
+                    if self.config.remember_scroll_position {
+                        self.scroll_positions
+                            .insert(previous_id, (self.list_start, self.grid_start));
+                    }
+                }
+
+                // Restore the saved scroll position, or start from the top
+                let (list_start, grid_start) = if self.config.remember_scroll_position {
+                    self.scroll_positions.get(pid).copied().unwrap_or((0, 0))
+                } else {
+                    (0, 0)
+                };
+                self.list_start = list_start;
+                self.grid_start = grid_start;
+
+                return Task::batch([self.update_title(), self.scroll_to_view_start()]);
             }
         }
 
         self.update_title()
     }
 
+    /// Files opened while the app is already running arrive here from the new process
+    #[cfg(feature = "single-instance")]
+    fn dbus_activation(
+        &mut self,
+        msg: cosmic::dbus_activation::Message,
+    ) -> Task<cosmic::Action<Self::Message>> {
+        let requests: Vec<OpenRequest> = match msg.msg {
+            cosmic::dbus_activation::Details::ActivateAction { action, .. } => {
+                OpenRequest::from_remote(&action).into_iter().collect()
+            }
+            cosmic::dbus_activation::Details::Open { url } => url
+                .into_iter()
+                .filter_map(|url| OpenRequest::from_remote(url.as_str()))
+                .collect(),
+            _ => Vec::new(),
+        };
+
+        let tasks = requests.into_iter().enumerate().map(|(index, request)| {
+            let message = if self.config.open_replaces_queue && index == 0 {
+                Message::OpenFile(request)
+            } else {
+                Message::EnqueueFile(request)
+            };
+            Task::done(cosmic::Action::App(message))
+        });
+
+        Task::batch(tasks.collect::<Vec<_>>())
+    }
+
     /// Footer area
     fn footer(&self) -> Option<Element<'_, Message>> {
+        // Only the view is hidden, playback and the tick subscription keep running
+        if self.state.footer_hidden {
+            return None;
+        }
+
         Some(footer(self).into())
     }
 }
@@ -2048,6 +4480,7 @@ impl AppModel {
     async fn init_mpris_connection(
         mpris_tx: tokio::sync::mpsc::UnboundedSender<MprisCommand>,
         mpris_state: Arc<Mutex<MprisState>>,
+        mpris_wake: Arc<Notify>,
     ) -> Result<zbus::Connection, String> {
         let connection = zbus::Connection::session()
             .await
@@ -2066,6 +4499,7 @@ impl AppModel {
                 MediaPlayer2Player {
                     tx: mpris_tx,
                     state: mpris_state,
+                    wake: mpris_wake,
                 },
             )
             .await
@@ -2115,6 +4549,13 @@ impl AppModel {
             window_title.push_str(page.unwrap());
         }
 
+        // Keeps scan progress visible while the window is minimized or the footer is hidden
+        self.title_progress = self.title_progress_text();
+        if let Some(progress) = &self.title_progress {
+            window_title.push_str(" — ");
+            window_title.push_str(progress);
+        }
+
         if let Some(id) = self.core.main_window_id() {
             self.set_window_title(window_title, id)
         } else {
@@ -2122,6 +4563,23 @@ impl AppModel {
         }
     }
 
+    fn title_progress_text(&self) -> Option<String> {
+        if !self.is_updating {
+            return None;
+        }
+
+        // Until the scan knows how many files there are there's no percentage to show
+        Some(if self.update_total == 0.0 {
+            self.update_activity.label()
+        } else {
+            fl!(
+                "title-progress",
+                activity = self.update_activity.label(),
+                percent = format_percent(self.update_percent.floor() as f64, 0)
+            )
+        })
+    }
+
     /// Get display name for track in duplicate dialog
     fn duplicate_track_display_name(track: &Track) -> String {
         track
@@ -2143,20 +4601,47 @@ impl AppModel {
         !name.trim().is_empty()
     }
 
+    /// Folder typed into the add location dialog, `None` unless it's an existing directory.
+    /// A leading `~` stands for the home folder.
+    fn entered_library_path(path: &str) -> Option<PathBuf> {
+        let path = path.trim();
+        let path = match path.strip_prefix('~') {
+            Some(rest) if rest.is_empty() || rest.starts_with('/') => {
+                PathBuf::from(std::env::var_os("HOME")?).join(rest.trim_start_matches('/'))
+            }
+            _ => PathBuf::from(path),
+        };
+
+        path.is_dir().then(|| path.canonicalize().unwrap_or(path))
+    }
+
     fn dialog_primary_message(dialog_page: &DialogPage) -> Option<Message> {
         match dialog_page {
-            DialogPage::NewPlaylist(name) if Self::playlist_name_is_valid(name) => {
+            DialogPage::NewPlaylist(name) | DialogPage::SaveQueueAsPlaylist(name)
+                if Self::playlist_name_is_valid(name) =>
+            {
                 Some(Message::DialogComplete)
             }
             DialogPage::RenamePlaylist { name, .. } if Self::playlist_name_is_valid(name) => {
                 Some(Message::DialogComplete)
             }
-            DialogPage::DeletePlaylist(_) | DialogPage::DeleteSelectedFromPlaylist => {
+            DialogPage::GoToTrack { .. } => Some(Message::DialogComplete),
+            DialogPage::EnterLibraryPath(path) if Self::entered_library_path(path).is_some() => {
+                Some(Message::DialogComplete)
+            }
+            DialogPage::ExportTracks { template, .. } if !template.trim().is_empty() => {
                 Some(Message::DialogComplete)
             }
+            DialogPage::DeletePlaylist(_)
+            | DialogPage::DeleteSelectedFromPlaylist { .. }
+            | DialogPage::ResetPlayStats
+            | DialogPage::QueueEnded(_) => Some(Message::DialogComplete),
             DialogPage::ConfirmPlaylistDuplicate { .. } => Some(
                 Message::PlaylistDuplicateDialogAction(PlaylistDuplicateDialogAction::Add),
             ),
+            DialogPage::CommandPalette(_) | DialogPage::ScanPreview(_) => {
+                Some(Message::DialogComplete)
+            }
             _ => None,
         }
     }
@@ -2193,7 +4678,7 @@ impl AppModel {
                         .playlist_service
                         .add_tracks(destination_id, vec![track])
                     {
-                        eprintln!("Error adding duplicate track: {}", err);
+                        log::error!("Error adding duplicate track: {}", err);
                     }
                 }
 
@@ -2208,7 +4693,7 @@ impl AppModel {
                         .playlist_service
                         .add_tracks(destination_id, remaining_tracks)
                     {
-                        eprintln!("Error adding duplicate tracks: {}", err);
+                        log::error!("Error adding duplicate tracks: {}", err);
                     }
                 }
 
@@ -2240,7 +4725,7 @@ impl AppModel {
         match self.config.playlist_duplicate_policy {
             PlaylistDuplicatePolicy::Allow => {
                 if let Err(err) = self.playlist_service.add_tracks(destination_id, tracks) {
-                    eprintln!("Error adding tracks: {}", err);
+                    log::error!("Error adding tracks: {}", err);
                 }
             }
 
@@ -2255,11 +4740,11 @@ impl AppModel {
                                 .playlist_service
                                 .add_tracks(destination_id, tracks_to_add)
                             {
-                                eprintln!("Error adding tracks: {}", err);
+                                log::error!("Error adding tracks: {}", err);
                             }
                         }
                     }
-                    Err(err) => eprintln!("Error checking playlist duplicates: {}", err),
+                    Err(err) => log::error!("Error checking playlist duplicates: {}", err),
                 }
             }
 
@@ -2274,7 +4759,7 @@ impl AppModel {
                                 .playlist_service
                                 .add_tracks(destination_id, tracks_to_add)
                             {
-                                eprintln!("Error adding tracks: {}", err);
+                                log::error!("Error adding tracks: {}", err);
                             }
                         }
 
@@ -2286,7 +4771,7 @@ impl AppModel {
                                 });
                         }
                     }
-                    Err(err) => eprintln!("Error checking playlist duplicates: {}", err),
+                    Err(err) => log::error!("Error checking playlist duplicates: {}", err),
                 }
             }
         }
@@ -2311,6 +4796,39 @@ impl AppModel {
             .config
             .crossfade_duration_secs
             .clamp(MIN_CROSSFADE_DURATION_SECS, MAX_CROSSFADE_DURATION_SECS);
+        let resume_rewind_secs = self.config.resume_rewind_secs.min(MAX_RESUME_REWIND_SECS);
+        let bpm_radio_tolerance = self
+            .config
+            .bpm_radio_tolerance
+            .clamp(1, MAX_BPM_RADIO_TOLERANCE);
+        let release_device_after_secs = self
+            .config
+            .release_device_after_secs
+            .min(MAX_RELEASE_DEVICE_AFTER_SECS);
+        let fade_in_ms = self.config.fade_in_ms.min(MAX_FADE_MS);
+        let fade_out_ms = self.config.fade_out_ms.min(MAX_FADE_MS);
+        let balance = self
+            .config
+            .balance
+            .clamp(-MAX_BALANCE_PERCENT, MAX_BALANCE_PERCENT);
+        let balance_label = match balance {
+            0 => fl!("center"),
+            percent if percent < 0 => fl!("balance-left", percent = -percent),
+            percent => fl!("balance-right", percent = percent),
+        };
+        let skip_silence_threshold_db = self
+            .config
+            .skip_silence_threshold_db
+            .clamp(MIN_SKIP_SILENCE_THRESHOLD_DB, MAX_SKIP_SILENCE_THRESHOLD_DB);
+        let previous_restart_threshold_secs = self
+            .config
+            .previous_restart_threshold_secs
+            .min(MAX_PREVIOUS_RESTART_THRESHOLD_SECS);
+        let min_duration_secs = self
+            .config
+            .min_duration_secs
+            .unwrap_or(0)
+            .min(u64::from(MAX_MIN_DURATION_SECS)) as u32;
         let title_sort_selected = match self.config.title_sort {
             TitleSortMode::Alphabetical => 0,
             TitleSortMode::TrackNumber => 1,
@@ -2336,8 +4854,10 @@ impl AppModel {
                 .push(
                     widget::column()
                         .push(
-                            widget::button::text(fl!("update-library"))
-                                .on_press(Message::UpdateLibrary),
+                            widget::button::text(fl!("update-library")).on_press_maybe(
+                                (!self.is_updating && self.initial_load_complete)
+                                    .then_some(Message::UpdateLibrary),
+                            ),
                         )
                         .width(Length::FillPortion(1))
                         .align_x(Alignment::End),
@@ -2349,16 +4869,55 @@ impl AppModel {
 
         // Create library path rows
         for (i, path) in self.config.library_paths.iter().enumerate() {
+            let track_count = self
+                .library_path_track_counts
+                .get(path)
+                .copied()
+                .unwrap_or(0);
+            let mount = self
+                .library_path_mounts
+                .get(path)
+                .copied()
+                .unwrap_or_default();
+
             library_column = library_column.push(
                 row()
                     .width(Length::Fill)
                     .padding(space_xxs)
                     // Adds text
                     .push(text::text(path.clone()).width(Length::FillPortion(1)))
+                    .push(text::caption(fl!(
+                        "library-path-tracks",
+                        count = track_count,
+                        tracks = format_count(track_count)
+                    )))
+                    .push_maybe(mount.network.then(|| {
+                        widget::tooltip(
+                            text::caption(fl!("network-folder")),
+                            text::text(fl!("network-folder-description")),
+                            Position::Bottom,
+                        )
+                    }))
+                    .push_maybe(mount.read_only.then(|| {
+                        widget::tooltip(
+                            text::caption(fl!("read-only-folder")),
+                            text::text(fl!("read-only-folder-description")),
+                            Position::Bottom,
+                        )
+                    }))
+                    // Folders skipped for this session can be scanned again
+                    .push_maybe(self.disabled_library_paths.contains(path).then(|| {
+                        widget::button::text(fl!("enable"))
+                            .on_press(Message::SetLibraryPathDisabled(path.clone(), false))
+                    }))
                     // Adds delete button
                     .push(
-                        widget::button::icon(widget::icon::from_name("window-close-symbolic"))
-                            .on_press(Message::RemoveLibraryPath(path.clone())),
+                        icon_button(
+                            widget::icon::from_name("window-close-symbolic"),
+                            fl!("remove-named", name = path.as_str()),
+                        )
+                        .padding(space_xxs)
+                        .on_press(Message::RemoveLibraryPath(path.clone())),
                     ),
             );
 
@@ -2367,6 +4926,50 @@ impl AppModel {
             }
         }
 
+        let mut scan_excludes_column = widget::column().push(
+            settings::item::builder(fl!("scan-excludes"))
+                .description(fl!("scan-excludes-description"))
+                .control(
+                    row()
+                        .align_y(Alignment::Center)
+                        .spacing(space_xxs)
+                        .push(
+                            widget::text_input(
+                                fl!("scan-exclude-placeholder"),
+                                &self.scan_exclude_input,
+                            )
+                            .on_input(Message::ScanExcludeInput)
+                            .on_submit(|_| Message::AddScanExclude)
+                            .width(Length::Fixed(180.0)),
+                        )
+                        .push(
+                            widget::button::text(fl!("add")).on_press_maybe(
+                                (!self.scan_exclude_input.trim().is_empty())
+                                    .then_some(Message::AddScanExclude),
+                            ),
+                        ),
+                ),
+        );
+
+        for pattern in &self.config.scan_excludes {
+            scan_excludes_column = scan_excludes_column
+                .push(widget::divider::horizontal::light())
+                .push(
+                    row()
+                        .width(Length::Fill)
+                        .padding(space_xxs)
+                        .push(text::text(pattern.clone()).width(Length::FillPortion(1)))
+                        .push(
+                            icon_button(
+                                widget::icon::from_name("window-close-symbolic"),
+                                fl!("remove-named", name = pattern.as_str()),
+                            )
+                            .padding(space_xxs)
+                            .on_press(Message::RemoveScanExclude(pattern.clone())),
+                        ),
+                );
+        }
+
         let ordered_columns = self.config.normalized_list_column_order();
         let sorting_section = settings::section()
             .title("Sorting")
@@ -2387,19 +4990,321 @@ impl AppModel {
                     toggler(self.config.sort_case_sensitive)
                         .on_toggle(Message::ToggleSortCaseSensitive),
                 )
+            })
+            .add({
+                settings::item::builder(fl!("sort-ignore-articles"))
+                    .description(fl!("sort-ignore-articles-description"))
+                    .control(
+                        toggler(self.config.sort_ignore_articles)
+                            .on_toggle(Message::ToggleSortIgnoreArticles),
+                    )
+            });
+
+        let mut playback_section = settings::section().title(fl!("playback")).add({
+            settings::item::builder(fl!("transition-mode")).control(widget::dropdown(
+                &self.playback_transition_labels,
+                Some(playback_transition_selected),
+                move |index| {
+                    Message::PlaybackTransitionMode(match index {
+                        1 => PlaybackTransitionMode::Crossfade,
+                        _ => PlaybackTransitionMode::Gapless,
+                    })
+                },
+            ))
+        });
+
+        playback_section = playback_section.add({
+            settings::item::builder(fl!("balance")).control(
+                row()
+                    .align_y(Alignment::Center)
+                    .spacing(space_xxs)
+                    .push(
+                        widget::slider(
+                            -MAX_BALANCE_PERCENT..=MAX_BALANCE_PERCENT,
+                            balance,
+                            Message::Balance,
+                        )
+                        .step(5)
+                        .width(Length::Fixed(180.0)),
+                    )
+                    .push(widget::text(balance_label))
+                    .push(
+                        widget::button::standard(fl!("center"))
+                            .on_press_maybe((balance != 0).then_some(Message::Balance(0))),
+                    ),
+            )
+        });
+
+        playback_section = playback_section.add({
+            settings::item::builder(fl!("clear-now-playing-on-stop")).control(
+                toggler(self.config.clear_now_playing_on_stop)
+                    .on_toggle(Message::ToggleClearNowPlayingOnStop),
+            )
+        });
+
+        playback_section = playback_section.add({
+            settings::item::builder(fl!("reduce-background-activity")).control(
+                toggler(self.config.reduce_background_activity)
+                    .on_toggle(Message::ToggleReduceBackgroundActivity),
+            )
+        });
+
+        playback_section = playback_section.add({
+            settings::item::builder(fl!("open-replaces-queue")).control(
+                toggler(self.config.open_replaces_queue)
+                    .on_toggle(Message::ToggleOpenReplacesQueue),
+            )
+        });
+
+        playback_section = playback_section.add({
+            settings::item::builder(fl!("play-view-shuffles"))
+                .description(fl!("play-view-shuffles-description"))
+                .control(
+                    toggler(self.config.play_view_shuffles)
+                        .on_toggle(Message::TogglePlayViewShuffles),
+                )
+        });
+
+        playback_section = playback_section.add({
+            settings::item::builder(fl!("show-launcher-progress")).control(
+                toggler(self.config.show_launcher_progress)
+                    .on_toggle(Message::ToggleShowLauncherProgress),
+            )
+        });
+
+        playback_section = playback_section.add({
+            settings::item::builder(fl!("show-waveform-preview"))
+                .description(fl!("show-waveform-preview-description"))
+                .control(
+                    toggler(self.config.show_waveform_preview)
+                        .on_toggle(Message::ToggleShowWaveformPreview),
+                )
+        });
+
+        playback_section = playback_section.add({
+            settings::item::builder(fl!("follow-current-track")).control(
+                toggler(self.config.follow_current_track)
+                    .on_toggle(Message::ToggleFollowCurrentTrack),
+            )
+        });
+
+        playback_section = playback_section.add({
+            settings::item::builder(fl!("continue-to-next-album")).control(
+                toggler(self.config.continue_to_next_album)
+                    .on_toggle(Message::ToggleContinueToNextAlbum),
+            )
+        });
+
+        playback_section = playback_section.add({
+            settings::item::builder(fl!("when-queue-ends")).control(widget::dropdown(
+                &self.queue_end_action_labels,
+                Some(self.config.queue_end_action.selected_index()),
+                move |index| {
+                    Message::QueueEndAction(
+                        QueueEndAction::ALL.get(index).copied().unwrap_or_default(),
+                    )
+                },
+            ))
+        });
+
+        playback_section = playback_section.add({
+            settings::item::builder(fl!("empty-play-action"))
+                .description(fl!("empty-play-action-description"))
+                .control(widget::dropdown(
+                    &self.empty_play_action_labels,
+                    Some(self.config.empty_play_action.selected_index()),
+                    move |index| {
+                        Message::EmptyPlayAction(
+                            EmptyPlayAction::ALL.get(index).copied().unwrap_or_default(),
+                        )
+                    },
+                ))
+        });
+
+        playback_section = playback_section.add({
+            settings::item::builder(fl!("resume-rewind")).control(
+                row()
+                    .align_y(Alignment::Center)
+                    .spacing(space_xxs)
+                    .push(
+                        widget::slider(
+                            0..=MAX_RESUME_REWIND_SECS,
+                            resume_rewind_secs,
+                            Message::ResumeRewindSecs,
+                        )
+                        .width(Length::Fixed(180.0)),
+                    )
+                    .push(widget::text(fl!("seconds", value = resume_rewind_secs))),
+            )
+        });
+
+        playback_section = playback_section.add({
+            settings::item::builder(fl!("release-device-after"))
+                .description(fl!("release-device-after-description"))
+                .control(
+                    row()
+                        .align_y(Alignment::Center)
+                        .spacing(space_xxs)
+                        .push(
+                            widget::slider(
+                                0..=MAX_RELEASE_DEVICE_AFTER_SECS,
+                                release_device_after_secs,
+                                Message::ReleaseDeviceAfterSecs,
+                            )
+                            .step(10)
+                            .width(Length::Fixed(180.0)),
+                        )
+                        .push(widget::text(if release_device_after_secs == 0 {
+                            fl!("off")
+                        } else {
+                            fl!("seconds", value = release_device_after_secs)
+                        })),
+                )
+        });
+
+        playback_section = playback_section.add({
+            settings::item::builder(fl!("accurate-seeking"))
+                .description(fl!("accurate-seeking-description"))
+                .control(
+                    toggler(self.config.accurate_seeking).on_toggle(Message::ToggleAccurateSeeking),
+                )
+        });
+
+        playback_section = playback_section.add({
+            settings::item::builder(fl!("replay-gain-normalization"))
+                .description(fl!("replay-gain-normalization-description"))
+                .control(
+                    toggler(self.config.replay_gain_normalization)
+                        .on_toggle(Message::ToggleReplayGainNormalization),
+                )
+        });
+
+        playback_section = playback_section.add({
+            settings::item::builder(fl!("skip-silence"))
+                .description(fl!("skip-silence-description"))
+                .control(toggler(self.config.skip_silence).on_toggle(Message::ToggleSkipSilence))
+        });
+
+        if self.config.skip_silence {
+            playback_section = playback_section.add({
+                settings::item::builder(fl!("silence-threshold"))
+                    .description(fl!(
+                        "silence-threshold-description",
+                        seconds = SKIP_SILENCE_MIN_SECS as u32
+                    ))
+                    .control(
+                        row()
+                            .align_y(Alignment::Center)
+                            .spacing(space_xxs)
+                            .push(
+                                widget::slider(
+                                    MIN_SKIP_SILENCE_THRESHOLD_DB..=MAX_SKIP_SILENCE_THRESHOLD_DB,
+                                    skip_silence_threshold_db,
+                                    Message::SkipSilenceThresholdDb,
+                                )
+                                .width(Length::Fixed(180.0)),
+                            )
+                            .push(widget::text(fl!(
+                                "decibels",
+                                value = format_decimal(skip_silence_threshold_db as f64, 0)
+                            ))),
+                    )
+            });
+        }
+
+        playback_section = playback_section.add({
+            settings::item::builder(fl!("fade-in")).control(
+                row()
+                    .align_y(Alignment::Center)
+                    .spacing(space_xxs)
+                    .push(
+                        widget::slider(0..=MAX_FADE_MS, fade_in_ms, Message::FadeInMs)
+                            .step(50)
+                            .width(Length::Fixed(180.0)),
+                    )
+                    .push(widget::text(format!("{}ms", fade_in_ms))),
+            )
+        });
+
+        playback_section = playback_section.add({
+            settings::item::builder(fl!("fade-out"))
+                .description(fl!("fade-out-description"))
+                .control(
+                    row()
+                        .align_y(Alignment::Center)
+                        .spacing(space_xxs)
+                        .push(
+                            widget::slider(0..=MAX_FADE_MS, fade_out_ms, Message::FadeOutMs)
+                                .step(50)
+                                .width(Length::Fixed(180.0)),
+                        )
+                        .push(widget::text(format!("{}ms", fade_out_ms))),
+                )
+        });
+
+        playback_section = playback_section.add({
+            settings::item::builder(fl!("fade-curve"))
+                .description(fl!("fade-curve-description"))
+                .control(widget::dropdown(
+                    &self.fade_curve_labels,
+                    Some(self.config.fade_curve.selected_index()),
+                    |index| {
+                        Message::FadeCurve(FadeCurve::ALL.get(index).copied().unwrap_or_default())
+                    },
+                ))
+        });
+
+        playback_section = playback_section.add({
+            settings::item::builder(fl!("previous-restarts-track")).control(
+                toggler(self.config.previous_restarts_track)
+                    .on_toggle(Message::TogglePreviousRestartsTrack),
+            )
+        });
+
+        if self.config.previous_restarts_track {
+            playback_section = playback_section.add({
+                settings::item::builder(fl!("previous-restart-threshold")).control(
+                    row()
+                        .align_y(Alignment::Center)
+                        .spacing(space_xxs)
+                        .push(
+                            widget::slider(
+                                1..=MAX_PREVIOUS_RESTART_THRESHOLD_SECS,
+                                previous_restart_threshold_secs,
+                                Message::PreviousRestartThresholdSecs,
+                            )
+                            .width(Length::Fixed(180.0)),
+                        )
+                        .push(widget::text(fl!(
+                            "seconds",
+                            value = previous_restart_threshold_secs
+                        ))),
+                )
             });
+        }
 
-        let mut playback_section = settings::section().title(fl!("playback")).add({
-            settings::item::builder(fl!("transition-mode")).control(widget::dropdown(
-                &self.playback_transition_labels,
-                Some(playback_transition_selected),
-                move |index| {
-                    Message::PlaybackTransitionMode(match index {
-                        1 => PlaybackTransitionMode::Crossfade,
-                        _ => PlaybackTransitionMode::Gapless,
-                    })
-                },
-            ))
+        playback_section = playback_section.add({
+            settings::item::builder(fl!("bpm-radio-tolerance")).control(
+                row()
+                    .align_y(Alignment::Center)
+                    .spacing(space_xxs)
+                    .push(
+                        widget::slider(
+                            1..=MAX_BPM_RADIO_TOLERANCE,
+                            bpm_radio_tolerance,
+                            Message::BpmRadioTolerance,
+                        )
+                        .width(Length::Fixed(180.0)),
+                    )
+                    .push(widget::text(fl!("bpm-range", bpm = bpm_radio_tolerance))),
+            )
+        });
+
+        playback_section = playback_section.add({
+            settings::item::builder(fl!("bpm-radio-same-genre")).control(
+                toggler(self.config.bpm_radio_same_genre)
+                    .on_toggle(Message::ToggleBpmRadioSameGenre),
+            )
         });
 
         if self.config.playback_transition_mode == PlaybackTransitionMode::Crossfade {
@@ -2419,15 +5324,58 @@ impl AppModel {
                         .push(widget::text(format!("{}s", crossfade_duration_secs))),
                 )
             });
+
+            playback_section = playback_section.add({
+                settings::item::builder(fl!("gapless-within-albums"))
+                    .description(fl!("gapless-within-albums-description"))
+                    .control(
+                        toggler(self.config.gapless_within_albums)
+                            .on_toggle(Message::ToggleGaplessWithinAlbums),
+                    )
+            });
         }
 
-        let grid_view_section = settings::section().title(fl!("grid-view")).add({
-            settings::item::builder(fl!("artwork-size")).control(widget::dropdown(
-                &self.artwork_size_labels,
-                Some(grid_artwork_size_selected),
-                move |index| Message::GridViewArtworkSize(ArtworkSize::from_index(index)),
-            ))
-        });
+        let sharing_section = settings::section()
+            .title(fl!("sharing"))
+            .add({
+                settings::item::builder(fl!("share-template"))
+                    .description(fl!("share-template-description"))
+                    .control(
+                        widget::text_input(DEFAULT_SHARE_TEMPLATE, &self.config.share_template)
+                            .on_input(|template| {
+                                Message::ShareTemplate(ShareFormat::Plain, template)
+                            })
+                            .width(Length::Fixed(240.0)),
+                    )
+            })
+            .add({
+                settings::item::builder(fl!("share-markdown-template")).control(
+                    widget::text_input(
+                        DEFAULT_SHARE_MARKDOWN_TEMPLATE,
+                        &self.config.share_markdown_template,
+                    )
+                    .on_input(|template| Message::ShareTemplate(ShareFormat::Markdown, template))
+                    .width(Length::Fixed(240.0)),
+                )
+            });
+
+        let grid_view_section = settings::section()
+            .title(fl!("grid-view"))
+            .add({
+                settings::item::builder(fl!("artwork-size")).control(widget::dropdown(
+                    &self.artwork_size_labels,
+                    Some(grid_artwork_size_selected),
+                    move |index| Message::GridViewArtworkSize(ArtworkSize::from_index(index)),
+                ))
+            })
+            .add({
+                settings::item::builder(fl!("merge-featured-artists"))
+                    .description(fl!("merge-featured-artists-description"))
+                    .control(
+                        toggler(self.config.merge_featured_artists)
+                            .on_toggle(Message::ToggleMergeFeaturedArtists),
+                    )
+            });
 
         let mut list_view_section = settings::section()
             .title(fl!("list-view"))
@@ -2436,6 +5384,13 @@ impl AppModel {
                     toggler(self.config.list_text_wrap).on_toggle(Message::ToggleListTextWrap),
                 )
             })
+            .add({
+                settings::item::builder(fl!("path-fallbacks"))
+                    .description(fl!("path-fallbacks-description"))
+                    .control(
+                        toggler(self.config.path_fallbacks).on_toggle(Message::TogglePathFallbacks),
+                    )
+            })
             .add({
                 settings::item::builder(fl!("align-rows-top")).control(
                     toggler(self.config.list_row_align_top)
@@ -2465,6 +5420,41 @@ impl AppModel {
                     toggler(self.config.regenerate_thumbnails_on_update)
                         .on_toggle(Message::ToggleRegenerateThumbnailsOnUpdate),
                 )
+            })
+            .add({
+                settings::item::builder(fl!("collapse-tag-whitespace")).control(
+                    toggler(self.config.collapse_tag_whitespace)
+                        .on_toggle(Message::ToggleCollapseTagWhitespace),
+                )
+            })
+            .add(scan_excludes_column)
+            .add({
+                settings::item::builder(fl!("analyze-loudness-on-update"))
+                    .description(fl!("analyze-loudness-description"))
+                    .control(
+                        toggler(self.config.analyze_loudness_on_update)
+                            .on_toggle(Message::ToggleAnalyzeLoudnessOnUpdate),
+                    )
+            })
+            .add({
+                settings::item::builder(fl!("min-track-duration")).control(
+                    row()
+                        .align_y(Alignment::Center)
+                        .spacing(space_xxs)
+                        .push(
+                            widget::slider(
+                                0..=MAX_MIN_DURATION_SECS,
+                                min_duration_secs,
+                                Message::MinDurationSecs,
+                            )
+                            .width(Length::Fixed(180.0)),
+                        )
+                        .push(widget::text(if min_duration_secs == 0 {
+                            fl!("off")
+                        } else {
+                            fl!("seconds", value = min_duration_secs)
+                        })),
+                )
             });
 
         settings::view_column(vec![
@@ -2483,6 +5473,17 @@ impl AppModel {
                         },
                     ))
                 })
+                .add({
+                    settings::item::builder(fl!("tint-from-artwork")).control(
+                        toggler(self.config.tint_from_artwork)
+                            .on_toggle(Message::ToggleTintFromArtwork),
+                    )
+                })
+                .add({
+                    settings::item::builder(fl!("show-jump-strip")).control(
+                        toggler(self.config.show_jump_strip).on_toggle(Message::ToggleJumpStrip),
+                    )
+                })
                 .into(),
             sorting_section.into(),
             settings::section()
@@ -2500,8 +5501,38 @@ impl AppModel {
                         },
                     ))
                 })
+                .add({
+                    let (ids, names): (Vec<PlaylistId>, Vec<String>) = self
+                        .playlist_service
+                        .user_playlists()
+                        .map(|playlist| (playlist.id(), playlist.name().to_string()))
+                        .unzip();
+                    let selected = self
+                        .config
+                        .quick_add_playlist
+                        .and_then(|id| ids.iter().position(|playlist_id| *playlist_id == id));
+
+                    settings::item::builder(fl!("quick-add-playlist"))
+                        .description(fl!("quick-add-playlist-description"))
+                        .control(widget::dropdown(names, selected, move |index| {
+                            Message::QuickAddPlaylist(ids.get(index).copied())
+                        }))
+                })
+                .add({
+                    settings::item::builder(fl!("confirm-remove-from-playlist")).control(
+                        toggler(self.config.confirm_remove_from_playlist)
+                            .on_toggle(Message::ToggleConfirmRemoveFromPlaylist),
+                    )
+                })
+                .add({
+                    settings::item::builder(fl!("remember-scroll-position")).control(
+                        toggler(self.config.remember_scroll_position)
+                            .on_toggle(Message::ToggleRememberScrollPosition),
+                    )
+                })
                 .into(),
             playback_section.into(),
+            sharing_section.into(),
             grid_view_section.into(),
             list_view_section.into(),
             library_section.into(),
@@ -2514,67 +5545,229 @@ impl AppModel {
             .set_crossfade_duration_secs(self.config.crossfade_duration_secs);
     }
 
-    /// Track info panel
+    fn sync_balance_from_config(&mut self) {
+        let balance = self
+            .config
+            .balance
+            .clamp(-MAX_BALANCE_PERCENT, MAX_BALANCE_PERCENT);
+        self.playback_service
+            .set_balance(balance as f32 / MAX_BALANCE_PERCENT as f32);
+    }
+
+    /// Empty state for a playlist with no tracks or a search that matches nothing
+    /// Track count and total length of a playlist, shown above its tracks
+    fn playlist_summary(&self, playlist: &Playlist) -> Element<'_, Message> {
+        let cosmic_theme::Spacing { space_xxs, .. } = theme::active().cosmic().spacing;
+
+        widget::text(fl!(
+            "playlist-summary",
+            count = playlist.len(),
+            tracks = format_count(playlist.len()),
+            duration = format_duration(playlist.total_duration() as f32)
+        ))
+        .apply(widget::container)
+        .padding([space_xxs, GRID_VIEW_PADDING as u16])
+        .width(Length::Fill)
+        .into()
+    }
+
+    fn empty_view_content(&self) -> widget::Column<'_, Message> {
+        let is_searching = self
+            .search_term
+            .as_ref()
+            .is_some_and(|term| !term.trim().is_empty());
+
+        if !is_searching {
+            return empty_library::notice(fl!("empty-playlist"), None);
+        }
+
+        let message = match (self.config.view_mode, self.config.grid_group_by) {
+            (ViewMode::Grid, GridGroupBy::Album) => fl!("no-albums-found"),
+            (ViewMode::Grid, GridGroupBy::Artist | GridGroupBy::AlbumArtist) => {
+                fl!("no-artists-found")
+            }
+            _ => fl!("no-tracks-found"),
+        };
+
+        empty_library::notice(message, Some((fl!("clear-search"), Message::SearchClear)))
+    }
+
+    /// Track info panel for the selected tracks, or the library track it was opened for
     fn track_info_panel(&self) -> Element<'_, Message> {
         let cosmic_theme::Spacing { space_xs, .. } = theme::active().cosmic().spacing;
 
-        let active_playlist = self.get_active_playlist();
-
-        let tracks: Vec<&Track> = match active_playlist {
-            Some(playlist) => playlist.selected(),
-            None => vec![],
+        let tracks: Vec<(&PathBuf, &MediaMetaData)> = match self
+            .track_info_id
+            .as_ref()
+            .and_then(|id| self.library.from_id(id))
+        {
+            Some(track) => vec![track],
+            None => self
+                .get_active_playlist()
+                .map(|playlist| {
+                    playlist
+                        .selected()
+                        .into_iter()
+                        .map(|track| (&track.path, &track.metadata))
+                        .collect()
+                })
+                .unwrap_or_default(),
         };
         let take = TRACK_INFO_LIST_TOTAL;
 
         let mut column = widget::column().spacing(space_xs);
 
-        for (i, t) in tracks.iter().enumerate().take(take) {
-            let duration = t.metadata.duration.clone().unwrap_or(0.0);
+        for (i, (path, metadata)) in tracks.iter().enumerate().take(take) {
+            let duration = metadata.duration.clone().unwrap_or(0.0);
             let display_duration = format_duration(duration);
+            // Play statistics and format details live on the library entry rather than
+            // playlist copies
+            let play_stats = self.library.media.get(*path);
+            let details = play_stats.unwrap_or(*metadata);
 
             let container = widget::container(
                 widget::column()
                     .push(track_info_row(
                         fl!("title"),
-                        t.metadata.title.clone().unwrap_or_default(),
+                        metadata.title.clone().unwrap_or_default(),
                     ))
                     .push(track_info_row(
                         fl!("album"),
-                        t.metadata.album.clone().unwrap_or_default(),
+                        metadata.album.clone().unwrap_or_default(),
                     ))
                     .push(track_info_row(
                         fl!("artist"),
-                        t.metadata.artist.clone().unwrap_or_default(),
+                        metadata.artist.clone().unwrap_or_default(),
                     ))
                     .push(track_info_row(
                         fl!("album-artist"),
-                        t.metadata.album_artist.clone().unwrap_or_default(),
+                        metadata.album_artist.clone().unwrap_or_default(),
+                    ))
+                    .push(track_info_row(fl!("genre"), metadata.genres.join(", ")))
+                    .push(track_info_row(
+                        fl!("grouping"),
+                        metadata.grouping.clone().unwrap_or_default(),
                     ))
                     .push(track_info_row(
-                        fl!("genre"),
-                        t.metadata.genre.clone().unwrap_or_default(),
+                        fl!("bpm"),
+                        optional_display(metadata.bpm.map(|bpm| bpm.round() as u32)),
                     ))
                     .push(track_info_row(
                         fl!("album-disc-number"),
-                        optional_display(t.metadata.album_disc_number),
+                        optional_display(metadata.album_disc_number),
                     ))
                     .push(track_info_row(
                         fl!("album-disc-count"),
-                        optional_display(t.metadata.album_disc_count),
+                        optional_display(metadata.album_disc_count),
                     ))
                     .push(track_info_row(
                         fl!("track-number"),
-                        optional_display(t.metadata.track_number),
+                        optional_display(metadata.track_number),
                     ))
                     .push(track_info_row(
                         fl!("track-count"),
-                        optional_display(t.metadata.track_count),
+                        optional_display(metadata.track_count),
                     ))
                     .push(track_info_row(fl!("duration"), display_duration))
+                    .push(track_info_row(
+                        fl!("codec"),
+                        details.codec.clone().unwrap_or_default(),
+                    ))
+                    .push(track_info_row(
+                        fl!("container"),
+                        details.container.clone().unwrap_or_default(),
+                    ))
+                    .push(track_info_row(
+                        fl!("bitrate"),
+                        details.bitrate.map(format_bitrate).unwrap_or_default(),
+                    ))
+                    .push(track_info_row(
+                        fl!("sample-rate"),
+                        details
+                            .sample_rate
+                            .map(format_sample_rate)
+                            .unwrap_or_default(),
+                    ))
+                    .push(track_info_row(
+                        fl!("channels"),
+                        optional_display(details.channels),
+                    ))
+                    .push(track_info_row(
+                        fl!("file-size"),
+                        details
+                            .file_size
+                            .map(|size| {
+                                // Cue tracks share their file, its size isn't theirs alone
+                                if details.is_cue_track() {
+                                    fl!("whole-file-size", size = format_file_size(size))
+                                } else {
+                                    format_file_size(size)
+                                }
+                            })
+                            .unwrap_or_default(),
+                    ))
+                    .push(track_info_row(
+                        fl!("replay-gain"),
+                        details
+                            .replay_gain_track_db
+                            .map(|gain_db| {
+                                fl!("applied-gain", gain = format_signed_decimal(gain_db, 2))
+                            })
+                            .unwrap_or_default(),
+                    ))
+                    .push(track_info_row(
+                        fl!("play-count"),
+                        play_stats
+                            .map(|m| format_count(m.play_count as usize))
+                            .unwrap_or_default(),
+                    ))
+                    .push(track_info_row(
+                        fl!("last-played"),
+                        play_stats
+                            .and_then(|m| m.last_played.as_deref())
+                            .map(format_last_played)
+                            .unwrap_or_default(),
+                    ))
+                    .push_maybe(play_stats.and_then(|m| {
+                        let gain_db = m.manual_gain_db.unwrap_or(0.0);
+                        let id = m.id.clone()?;
+
+                        widget::row()
+                            .align_y(Alignment::Center)
+                            .spacing(space_xs)
+                            .push(
+                                widget::text(fl!("manual-gain"))
+                                    .width(Length::FillPortion(1))
+                                    .align_x(Alignment::End)
+                                    .font(Font {
+                                        weight: Weight::Bold,
+                                        ..Font::default()
+                                    }),
+                            )
+                            .push(
+                                widget::row()
+                                    .align_y(Alignment::Center)
+                                    .spacing(space_xs)
+                                    .width(Length::FillPortion(1))
+                                    .push(
+                                        widget::slider(
+                                            MIN_MANUAL_GAIN_DB..=MAX_MANUAL_GAIN_DB,
+                                            gain_db,
+                                            move |gain_db| Message::ManualGain(id.clone(), gain_db),
+                                        )
+                                        .step(MANUAL_GAIN_STEP_DB)
+                                        .on_release(Message::ManualGainReleased),
+                                    )
+                                    .push(widget::text(fl!(
+                                        "applied-gain",
+                                        gain = format_signed_decimal(gain_db as f64, 1)
+                                    ))),
+                            )
+                    }))
                     .push(
                         widget::row()
                             .width(Length::Fill)
-                            .push(widget::text(t.path.to_string_lossy())),
+                            .push(widget::text(path.to_string_lossy())),
                     ),
             );
 
@@ -2592,18 +5785,320 @@ impl AppModel {
         column.into()
     }
 
+    /// Chapter list for the now playing track, the current chapter is highlighted
+    fn chapters_panel(&self) -> Element<'_, Message> {
+        let cosmic_theme::Spacing { space_xxs, .. } = theme::active().cosmic().spacing;
+
+        let Some(now_playing) = self.playback_service.now_playing() else {
+            return widget::column().into();
+        };
+
+        let progress_ms = (self.playback_service.progress() * 1000.0) as u64;
+        let current = now_playing
+            .chapters
+            .iter()
+            .rposition(|(start, _)| *start <= progress_ms);
+
+        let mut column = widget::column().spacing(space_xxs);
+
+        for (index, (start, title)) in now_playing.chapters.iter().enumerate() {
+            let title = if title.is_empty() {
+                fl!("chapter-number", number = (index + 1))
+            } else {
+                title.clone()
+            };
+
+            let label = widget::row()
+                .spacing(space_xxs)
+                .push(widget::text(format_duration(*start as f32 / 1000.0)))
+                .push(widget::text(title).width(Length::Fill));
+
+            let button = if current == Some(index) {
+                widget::button::custom(label).class(theme::Button::Suggested)
+            } else {
+                widget::button::custom(label).class(theme::Button::Text)
+            };
+
+            column = column.push(
+                button
+                    .width(Length::Fill)
+                    .on_press(Message::SeekToChapter(*start)),
+            );
+        }
+
+        column.into()
+    }
+
+    /// Debug panel with pipeline state and recent bus messages
+    fn debug_panel(&self) -> Element<'_, Message> {
+        let cosmic_theme::Spacing { space_xxs, .. } = theme::active().cosmic().spacing;
+        let info = self.playback_service.debug_info();
+
+        let position = match (info.position_secs, info.duration_secs) {
+            (Some(position), Some(duration)) => {
+                format!(
+                    "{} / {}",
+                    format_duration(position),
+                    format_duration(duration)
+                )
+            }
+            (Some(position), None) => format_duration(position),
+            _ => String::new(),
+        };
+
+        let mut column = widget::column()
+            .spacing(space_xxs)
+            .push(track_info_row(fl!("pipeline-state"), info.pipeline_state))
+            .push(track_info_row(fl!("uri"), info.uri.unwrap_or_default()))
+            .push(track_info_row(
+                fl!("last-bus-message"),
+                info.last_bus_message.unwrap_or_default(),
+            ))
+            .push(track_info_row(fl!("position"), position))
+            .push(track_info_row(
+                fl!("image-cache-size"),
+                self.image_store.len().to_string(),
+            ))
+            .push(widget::divider::horizontal::light())
+            .push(widget::text::heading(fl!("bus-messages")));
+
+        // Newest first so the latest activity stays in view
+        for entry in self.playback_service.debug_log().rev() {
+            column = column.push(widget::text::caption(entry.clone()));
+        }
+
+        column.into()
+    }
+
+    fn logs_panel(&self) -> Element<'_, Message> {
+        let cosmic_theme::Spacing { space_xxs, .. } = theme::active().cosmic().spacing;
+
+        let mut column = widget::column()
+            .spacing(space_xxs)
+            .push(
+                settings::item::builder(fl!("log-level")).control(widget::dropdown(
+                    &self.log_level_labels,
+                    Some(self.config.log_level.selected_index()),
+                    |index| {
+                        Message::LogLevel(LogLevel::ALL.get(index).copied().unwrap_or_default())
+                    },
+                )),
+            )
+            .push(widget::divider::horizontal::light());
+
+        // Newest first so the latest activity stays in view
+        for line in logger::recent().into_iter().rev() {
+            column = column.push(widget::text::caption(line));
+        }
+
+        column.into()
+    }
+
+    /// Create a playlist named after `folder` with the library tracks of `files`, in file
+    /// order and cue tracks in track order. Files that couldn't be read are left out.
+    fn create_folder_playlist(
+        &mut self,
+        folder: &Path,
+        files: &[PathBuf],
+    ) -> Task<cosmic::Action<Message>> {
+        let mut by_file: HashMap<&Path, Vec<(&PathBuf, &MediaMetaData)>> = HashMap::new();
+        for (path, metadata) in &self.library.media {
+            by_file
+                .entry(metadata.media_file(path))
+                .or_default()
+                .push((path, metadata));
+        }
+
+        let mut tracks = Vec::new();
+        for file in files {
+            let Some(entries) = by_file.get_mut(file.as_path()) else {
+                continue;
+            };
+            entries.sort_by_key(|(_, metadata)| metadata.track_number);
+
+            for (path, metadata) in entries.iter() {
+                let mut track = Track::new();
+                track.path = (*path).clone();
+                track.metadata = (*metadata).clone();
+                tracks.push(track);
+            }
+        }
+
+        if tracks.is_empty() {
+            return self.show_toast(fl!(
+                "no-audio-files-in-folder",
+                path = folder.display().to_string()
+            ));
+        }
+
+        let base_name = folder
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_else(|| fl!("untitled-playlist"));
+        let name = self.playlist_service.unused_name(base_name);
+
+        let id = match self.playlist_service.create(name) {
+            Ok(id) => id,
+            Err(err) => {
+                log::error!("Error creating playlist: {}", err);
+                return Task::none();
+            }
+        };
+
+        if let Err(err) = self.playlist_service.add_tracks(id, tracks) {
+            log::error!("Error adding tracks: {}", err);
+        }
+
+        self.view_playlist = Some(id);
+
+        // Rebuild nav preserving order
+        let items = self.build_ordered_nav_items();
+        self.rebuild_nav_from_order(items, id);
+        self.invalidate_all_caches();
+        self.update_title()
+    }
+
+    /// Re-read metadata for just these tracks, much faster than a full update
+    fn rescan_tracks(
+        &mut self,
+        tracks: Vec<(PathBuf, MediaMetaData)>,
+    ) -> Task<cosmic::Action<Message>> {
+        self.library_undo = Some(self.library.clone());
+        self.is_updating = true;
+        self.update_activity = UpdateActivity::Library;
+        self.update_progress = 0.0;
+        self.update_total = 0.0;
+        self.update_percent = 0.0;
+
+        let (tx, rx) = ProgressSender::channel();
+
+        let cancel_token = CancellationToken::new();
+        self.library_update_cancel = Some(cancel_token.clone());
+
+        LibraryService::rescan_tracks(
+            tracks,
+            self.app_xdg_dirs.clone(),
+            tx,
+            cancel_token,
+            self.config.collapse_tag_whitespace,
+        );
+
+        cosmic::Task::stream(ReceiverStream::new(rx))
+            .map(|progress| cosmic::Action::App(Message::LibraryProgress(progress)))
+    }
+
+    fn scan_report_panel(&self) -> Element<'_, Message> {
+        let cosmic_theme::Spacing { space_xxs, .. } = theme::active().cosmic().spacing;
+
+        if self.scan_errors.is_empty() {
+            return widget::text(fl!("no-scan-errors")).into();
+        }
+
+        let mut column = widget::column()
+            .spacing(space_xxs)
+            .push(widget::text::body(fl!(
+                "scan-errors-summary",
+                count = self.scan_errors.len()
+            )))
+            .push(widget::divider::horizontal::light());
+
+        for error in &self.scan_errors {
+            let reason = match &error.reason {
+                ScanErrorReason::MissingPlugins(plugins) => {
+                    fl!("scan-error-missing-plugins", plugins = plugins.join(", "))
+                }
+                ScanErrorReason::Timeout => fl!("scan-error-timeout"),
+                ScanErrorReason::Unreadable(message) => {
+                    fl!("scan-error-unreadable", error = message.clone())
+                }
+            };
+
+            let actions = widget::row()
+                .spacing(space_xxs)
+                .push(
+                    widget::button::text(fl!("show-in-folder"))
+                        .on_press(Message::OpenContainingFolder(error.path.clone())),
+                )
+                .push(widget::button::text(fl!("retry")).on_press_maybe(
+                    (!self.is_updating).then(|| Message::RetryScanError(error.path.clone())),
+                ));
+
+            column = column
+                .push(widget::text(error.path.to_string_lossy().to_string()))
+                .push(widget::text::caption(reason))
+                .push(actions)
+                .push(widget::divider::horizontal::light());
+        }
+
+        column.into()
+    }
+
+    /// Save the library on a blocking thread, errors are only logged
+    fn save_library(&self) -> Task<cosmic::Action<Message>> {
+        let save = self
+            .library_service
+            .save_in_background(self.library.clone());
+
+        cosmic::task::future(async move {
+            if let Err(e) = save.await {
+                log::error!("Error saving library: {}", e);
+            }
+            Message::Noop
+        })
+    }
+
+    fn save_scan_report(&self) {
+        if let Err(err) = scan_report::save(&self.app_xdg_dirs, &self.scan_errors) {
+            log::error!("Error saving scan report: {}", err);
+        }
+    }
+
+    fn show_toast(&mut self, message: String) -> Task<cosmic::Action<Message>> {
+        self.toasts
+            .push(widget::toaster::Toast::new(message))
+            .map(cosmic::Action::App)
+    }
+
     /// Updates the cosmic config, in particular the theme
     fn update_config(&mut self) -> Task<cosmic::Action<Message>> {
         cosmic::command::set_theme(self.config.app_theme.theme())
     }
 
-    /// Load library and playlists
+    /// Load the library from disk off the UI thread. Scanning stays disabled until
+    /// `Message::LibraryLoaded` has been handled.
     pub fn load_data(&mut self) -> Task<cosmic::Action<Message>> {
-        // Load library from disk
-        self.library = self.library_service.load().unwrap_or_else(|e| {
-            eprintln!("Error loading library: {}", e);
-            Library::new()
-        });
+        let library_service = self.library_service.clone();
+
+        cosmic::task::future(async move {
+            let library = tokio::task::spawn_blocking(move || library_service.load()).await;
+
+            let library = match library {
+                Ok(Ok(library)) => library,
+                Ok(Err(LibraryError::Json(e))) => {
+                    log::error!(
+                        "Library file is corrupt, starting with an empty library: {}",
+                        e
+                    );
+                    Library::new()
+                }
+                Ok(Err(e)) => {
+                    log::error!("Error loading library: {}", e);
+                    Library::new()
+                }
+                Err(e) => {
+                    log::error!("Error loading library: {}", e);
+                    Library::new()
+                }
+            };
+
+            Message::LibraryLoaded(library)
+        })
+    }
+
+    /// Load playlists against the freshly loaded library and build the nav
+    fn finish_load_data(&mut self, library: Library) -> Task<cosmic::Action<Message>> {
+        self.library = library;
+        self.sync_manual_gains();
         let library_media = self.library.media.clone();
 
         // Convert library to tracks
@@ -2619,7 +6114,7 @@ impl AppModel {
 
         // Load all playlists through the service
         if let Err(e) = self.playlist_service.load_all(library_tracks) {
-            eprintln!("Error loading playlists: {}", e);
+            log::error!("Error loading playlists: {}", e);
             self.initial_load_complete = false;
             return Task::none();
         }
@@ -2632,6 +6127,7 @@ impl AppModel {
                     self.state.sort_by.clone(),
                     self.state.sort_direction.clone(),
                     self.config.title_sort,
+                    self.config.sort_ignore_articles,
                     self.config.sort_case_sensitive,
                 );
             }
@@ -2685,7 +6181,7 @@ impl AppModel {
             None => match self.playlist_service.get_library() {
                 Ok(lib) => lib.id(),
                 Err(e) => {
-                    eprintln!("Failed to get library playlist: {}", e);
+                    log::error!("Failed to get library playlist: {}", e);
                     self.initial_load_complete = false; // Stay in loading state
                     return Task::none();
                 }
@@ -2696,7 +6192,11 @@ impl AppModel {
         self.rebuild_nav_from_order(items, active_id);
 
         self.initial_load_complete = true;
-        Task::none()
+
+        match self.pending_open.take() {
+            Some(request) => Task::done(cosmic::Action::App(Message::OpenFile(request))),
+            None => Task::none(),
+        }
     }
 
     fn update_mpris(&self) {
@@ -2822,7 +6322,86 @@ impl AppModel {
                     .await;
                 let _ = iface_ref.get_mut().await.volume_changed(&signal_ctx).await;
             }
-        });
+        });
+    }
+
+    /// A track for `path`, using the library metadata when the file is in the library
+    fn track_for_path(&self, path: &Path) -> Track {
+        let mut track = Track::new();
+        track.path = path.to_path_buf();
+
+        match self.library.media.get(path) {
+            Some(metadata) => track.metadata = metadata.clone(),
+            None => {
+                track.metadata.id = Some(sha256::digest(path.to_string_lossy().to_string()));
+                track.metadata.title = path
+                    .file_stem()
+                    .map(|stem| stem.to_string_lossy().to_string());
+            }
+        }
+
+        track
+    }
+
+    /// Previous restarts the current track when enabled and playback is past the threshold.
+    /// Pressing near the start of the track, or twice in quick succession, skips back instead.
+    fn previous_or_restart(&mut self) {
+        if self.config.previous_restarts_track {
+            let now = Instant::now();
+            let pressed_twice = self.previous_pressed_at.is_some_and(|pressed_at| {
+                now.duration_since(pressed_at) < Duration::from_millis(PREVIOUS_DOUBLE_PRESS_MS)
+            });
+            self.previous_pressed_at = Some(now);
+
+            let threshold = self.config.previous_restart_threshold_secs as f32;
+            if !pressed_twice
+                && self.playback_service.status() != PlaybackStatus::Stopped
+                && self.playback_service.progress() >= threshold
+            {
+                self.playback_service.seek(0.0);
+                return;
+            }
+        }
+
+        self.playback_service.prev(self.state.repeat_mode.clone());
+    }
+
+    /// Publish playback progress to the dock or taskbar icon, at most once per interval
+    /// unless the overlay is appearing, disappearing or `force` is set
+    fn update_launcher_progress(&mut self, force: bool) {
+        let Some(conn) = &self.mpris_connection else {
+            return;
+        };
+
+        let duration = self
+            .playback_service
+            .now_playing()
+            .and_then(|metadata| metadata.duration)
+            .filter(|duration| *duration > 0.0);
+
+        let progress = match duration {
+            Some(duration)
+                if self.config.show_launcher_progress
+                    && self.playback_service.status() != PlaybackStatus::Stopped =>
+            {
+                Some((self.playback_service.progress() / duration) as f64)
+            }
+            _ => None,
+        };
+
+        let visibility_changed = progress.is_some() != self.launcher_progress.is_some();
+        let interval_elapsed = self.launcher_updated_at.elapsed()
+            >= Duration::from_millis(LAUNCHER_UPDATE_INTERVAL_MS);
+
+        if progress == self.launcher_progress
+            || !(force || visibility_changed || (progress.is_some() && interval_elapsed))
+        {
+            return;
+        }
+
+        launcher::update_progress(conn, APP_ID, progress);
+        self.launcher_progress = progress;
+        self.launcher_updated_at = Instant::now();
     }
 
     fn start_session_maybe(&mut self) {
@@ -2856,6 +6435,20 @@ impl AppModel {
             .data(Page::Playlist(library_id))
             .icon(widget::icon::from_name("folder-music-symbolic"));
 
+        if let Some(favorites_id) = self
+            .playlist_service
+            .all()
+            .iter()
+            .find(|p| p.is_favorites())
+            .map(|p| p.id())
+        {
+            self.nav
+                .insert()
+                .text(fl!("favorites"))
+                .data(Page::Playlist(favorites_id))
+                .icon(widget::icon::from_name("starred-symbolic"));
+        }
+
         // Add the playlists
         for (i, item) in items.iter().enumerate() {
             if self.playlist_service.get(item.id).is_err() {
@@ -2886,6 +6479,34 @@ impl AppModel {
         self.nav_order();
     }
 
+    /// Highlight the user playlist tracks are dragged over by swapping its icon, the library
+    /// and favorites keep theirs
+    fn set_nav_drop_target(&mut self, target: Option<nav_bar::Id>) {
+        if let Some(previous) = self.nav_drop_target.take() {
+            self.nav.icon_set(
+                previous,
+                widget::icon::from_name("playlist-symbolic").icon(),
+            );
+        }
+
+        let Some(target) = target else {
+            return;
+        };
+        let is_user_playlist = match self.nav.data::<Page>(target) {
+            Some(Page::Playlist(id)) => self
+                .playlist_service
+                .get(*id)
+                .is_ok_and(|playlist| playlist.is_user()),
+            _ => false,
+        };
+
+        if is_user_playlist {
+            self.nav
+                .icon_set(target, widget::icon::from_name("list-add-symbolic").icon());
+            self.nav_drop_target = Some(target);
+        }
+    }
+
     /// Swap positions of nav items
     fn move_active_nav(&mut self, direction: i32) {
         let active = self.nav.active();
@@ -2899,7 +6520,7 @@ impl AppModel {
             Err(_) => return,
         };
 
-        if active_playlist.is_library() {
+        if !active_playlist.is_user() {
             return;
         }
 
@@ -2912,7 +6533,7 @@ impl AppModel {
                         .playlist_service
                         .get(*pid)
                         .ok()
-                        .filter(|pl| !pl.is_library())
+                        .filter(|pl| pl.is_user())
                         .map(|pl| NavPlaylistItem {
                             id: *pid,
                             name: pl.name().to_string(),
@@ -2943,7 +6564,7 @@ impl AppModel {
                         .playlist_service
                         .get(*pid)
                         .ok()
-                        .filter(|p| !p.is_library())
+                        .filter(|p| p.is_user())
                         .map(|_| *pid),
                 })
             })
@@ -3017,6 +6638,7 @@ impl AppModel {
             wrapping,
             row_align,
             sort_direction_icon,
+            path_fallbacks: self.config.path_fallbacks,
         })
     }
 
@@ -3084,6 +6706,17 @@ impl AppModel {
             Vec::new()
         };
 
+        // Covers of the rows just past the rendered ones, on the side being scrolled towards
+        let prefetch_range = if self.grid_scrolling_up && start_item > 0 {
+            start_item.saturating_sub(GRID_PREFETCH_ROWS * layout.column_count)..start_item
+        } else {
+            end_item..(end_item + GRID_PREFETCH_ROWS * layout.column_count).min(total_card_count)
+        };
+        let prefetch_artwork = grid_card_bases[prefetch_range]
+            .iter()
+            .filter_map(|card| card.artwork_filename.clone())
+            .collect();
+
         let top_spacer_height = grid_start as f32 * layout.row_stride;
         let visible_rows = Self::grid_total_rows(visible_cards.len(), layout.column_count);
         let rendered_height = visible_rows as f32 * layout.row_stride;
@@ -3092,6 +6725,7 @@ impl AppModel {
 
         Some(GridViewModel {
             visible_cards,
+            prefetch_artwork,
             selected_track_ids,
             column_count: layout.column_count,
             card_width: layout.card_width,
@@ -3105,6 +6739,42 @@ impl AppModel {
         })
     }
 
+    /// Load the on-screen grid covers ahead of anything queued while scrolling past, then
+    /// queue the ones about to scroll in behind them so they're ready when they appear.
+    /// Ones that are scrolled past unseen are dropped by the cache's idle eviction.
+    fn warm_grid_artwork(&self) {
+        if self.config.view_mode != ViewMode::Grid {
+            return;
+        }
+        let Some(view_model) = self
+            .grid_viewport_size
+            .and_then(|size| self.calculate_grid_view(size))
+        else {
+            return;
+        };
+
+        let artwork_size = &self.config.grid_artwork_size;
+        let decode_size = Some(ImageStore::decode_size(view_model.artwork_size));
+        self.image_store.warm(
+            view_model.visible_cards.iter().filter_map(|card| {
+                card.artwork_filename
+                    .as_ref()
+                    .map(|filename| artwork_size.cache_filename(filename))
+            }),
+            decode_size,
+        );
+
+        for filename in &view_model.prefetch_artwork {
+            let preferred = artwork_size.cache_filename(filename);
+            if self.image_store.exists(&preferred) {
+                self.image_store.request_sized(preferred, decode_size);
+            } else {
+                self.image_store
+                    .request_sized(filename.clone(), decode_size);
+            }
+        }
+    }
+
     pub fn is_track_playing(&self, track: &Track, is_playing_playlist: bool) -> bool {
         is_playing_playlist
             && self
@@ -3167,11 +6837,31 @@ impl AppModel {
         }
     }
 
+    /// Scroll the current view to the stored list or grid start row
+    fn scroll_to_view_start(&self) -> Task<cosmic::Action<Message>> {
+        let scroll_offset = match self.config.view_mode {
+            ViewMode::List => self
+                .calculate_list_view()
+                .map(|view_model| view_model.scroll_offset),
+            ViewMode::Grid => Some(self.grid_start as f32 * self.grid_row_stride()),
+        }
+        .unwrap_or(0.0);
+
+        scrollable::scroll_to(
+            self.list_scroll_id.clone(),
+            AbsoluteOffset {
+                x: Some(0.0),
+                y: Some(scroll_offset),
+            },
+        )
+    }
+
     fn sort_all_playlists(&mut self) {
         let playlist_ids: Vec<u32> = self.playlist_service.all().iter().map(|p| p.id()).collect();
         let sort_by = self.state.sort_by.clone();
         let sort_direction = self.state.sort_direction.clone();
         let title_sort = self.config.title_sort;
+        let sort_ignore_articles = self.config.sort_ignore_articles;
         let sort_case_sensitive = self.config.sort_case_sensitive;
 
         for id in playlist_ids {
@@ -3180,12 +6870,359 @@ impl AppModel {
                     sort_by.clone(),
                     sort_direction.clone(),
                     title_sort,
+                    sort_ignore_articles,
                     sort_case_sensitive,
                 );
             }
         }
 
+        // Saved positions point at different tracks after a re-sort
+        self.scroll_positions.clear();
+        self.invalidate_all_caches();
+    }
+
+    /// Start the album that follows the one containing `finished_id`. Prefers the next
+    /// album by the same album artist, then the next album in the library.
+    /// Ask before quitting or sleeping once the queue has run out, the action runs when the
+    /// countdown ends unless it's cancelled
+    fn start_queue_end_countdown(&mut self) {
+        let action = self.config.queue_end_action;
+        if action == QueueEndAction::Stop || self.queue_end_countdown.is_some() {
+            return;
+        }
+
+        let deadline = Instant::now() + Duration::from_secs(QUEUE_END_COUNTDOWN_SECS);
+        self.queue_end_countdown = Some((action, deadline));
+        self.dialog_pages.push_back(DialogPage::QueueEnded(action));
+    }
+
+    fn cancel_queue_end_countdown(&mut self) {
+        self.queue_end_countdown = None;
+        self.dialog_pages
+            .retain(|page| !matches!(page, DialogPage::QueueEnded(_)));
+    }
+
+    fn run_queue_end_action(&self, action: QueueEndAction) -> Task<cosmic::Action<Message>> {
+        match action {
+            QueueEndAction::Stop => Task::none(),
+            QueueEndAction::Quit => Task::done(cosmic::Action::App(Message::Quit)),
+            QueueEndAction::Sleep => cosmic::task::future(async move {
+                match power::suspend().await {
+                    Ok(()) => Message::Noop,
+                    Err(err) => Message::SuspendFailed(err),
+                }
+            }),
+        }
+    }
+
+    fn continue_to_next_album(&mut self, finished_id: Option<&str>) {
+        let Some((_, finished)) = finished_id.and_then(|id| self.library.from_id(id)) else {
+            return;
+        };
+        let Some(current_key) = Self::album_key(finished) else {
+            return;
+        };
+
+        let Ok(library) = self.playlist_service.get_library() else {
+            return;
+        };
+
+        let mut album_keys: Vec<(String, String)> = library
+            .tracks()
+            .iter()
+            .filter_map(|track| Self::album_key(&track.metadata))
+            .filter(|key| *key != current_key)
+            .collect();
+        album_keys.sort();
+        album_keys.dedup();
+
+        let Some(next_key) = album_keys
+            .iter()
+            .find(|(artist, album)| *artist == current_key.0 && *album > current_key.1)
+            .or_else(|| album_keys.iter().find(|key| **key > current_key))
+            // The last album only wraps around to the first when repeating everything
+            .or_else(|| {
+                album_keys
+                    .first()
+                    .filter(|_| self.state.repeat && self.state.repeat_mode == RepeatMode::All)
+            })
+            .cloned()
+        else {
+            return;
+        };
+
+        let Some(album) = self.album_playlist(&next_key) else {
+            return;
+        };
+
+        self.playback_service
+            .start_session(&album, 0, self.state.shuffle);
+        self.sync_playback_output_from_state();
+        self.playback_service.play();
+    }
+
+    /// Temporary playlist of one album's library tracks in disc and track order
+    fn album_playlist(&self, key: &(String, String)) -> Option<Playlist> {
+        let library = self.playlist_service.get_library().ok()?;
+
+        let mut album = Playlist::new(key.1.clone());
+        library
+            .tracks()
+            .iter()
+            .filter(|track| Self::album_key(&track.metadata).as_ref() == Some(key))
+            .for_each(|track| album.push(track.clone()));
+
+        if album.tracks().is_empty() {
+            return None;
+        }
+
+        album.sort(
+            SortBy::DiscNumber,
+            SortDirection::Ascending,
+            TitleSortMode::TrackNumber,
+            self.config.sort_ignore_articles,
+            self.config.sort_case_sensitive,
+        );
+
+        Some(album)
+    }
+
+    /// Play the album containing the library track `id`, from the first track or shuffled
+    fn play_album(&mut self, id: &str, shuffle: bool) {
+        let Some(key) = self
+            .library
+            .from_id(id)
+            .and_then(|(_, metadata)| Self::album_key(metadata))
+        else {
+            return;
+        };
+        let Some(album) = self.album_playlist(&key) else {
+            return;
+        };
+
+        let start_index = if shuffle {
+            use rand::Rng;
+            rand::rng().random_range(0..album.tracks().len())
+        } else {
+            0
+        };
+
+        self.playback_service
+            .start_session(&album, start_index, shuffle);
+        self.sync_playback_output_from_state();
+        self.playback_service.play();
+    }
+
+    /// Play every track the view shows, from the top or a random one when shuffling. While
+    /// searching only the matching tracks are queued.
+    fn play_current_view(&mut self, shuffle: bool) {
+        let Some(playlist_id) = self.view_playlist else {
+            return;
+        };
+        let Ok(playlist) = self.playlist_service.get(playlist_id) else {
+            return;
+        };
+
+        let normalized_search = self.search_term.as_ref().map(|term| term.to_lowercase());
+        let (filtered_track_indices, _, _) =
+            self.cached_view_base(playlist_id, playlist.tracks(), normalized_search.as_deref());
+        if filtered_track_indices.is_empty() {
+            return;
+        }
+
+        let start_index = if shuffle {
+            use rand::Rng;
+            rand::rng().random_range(0..filtered_track_indices.len())
+        } else {
+            0
+        };
+
+        if filtered_track_indices.len() == playlist.tracks().len() {
+            self.playback_service
+                .start_session(playlist, start_index, shuffle);
+        } else {
+            let mut queue = Playlist::new(playlist.name().to_string());
+            filtered_track_indices
+                .iter()
+                .filter_map(|index| playlist.tracks().get(*index))
+                .for_each(|track| queue.push(track.clone()));
+            self.playback_service
+                .start_session(&queue, start_index, shuffle);
+        }
+
+        self.sync_playback_output_from_state();
+        self.playback_service.play();
+    }
+
+    /// Start a fresh shuffled queue from the whole library, whether or not shuffle is on.
+    /// Libraries above `SHUFFLE_LIBRARY_MAX_TRACKS` are sampled so the queue builds quickly.
+    fn shuffle_library(&mut self) {
+        use rand::seq::{IndexedRandom, SliceRandom};
+
+        let Ok(library) = self.playlist_service.get_library() else {
+            return;
+        };
+        if library.tracks().is_empty() {
+            return;
+        }
+
+        let mut rng = rand::rng();
+        let mut tracks: Vec<Track> = library
+            .tracks()
+            .choose_multiple(&mut rng, SHUFFLE_LIBRARY_MAX_TRACKS)
+            .cloned()
+            .collect();
+        tracks.shuffle(&mut rng);
+
+        let mut queue = Playlist::new(fl!("shuffle-library"));
+        tracks.into_iter().for_each(|track| queue.push(track));
+
+        self.playback_service.start_session(&queue, 0, false);
+        self.sync_playback_output_from_state();
+        self.playback_service.play();
+    }
+
+    /// Queue shuffled library tracks within the BPM tolerance of the playing track, sharing a
+    /// genre with it when that's enabled. Tracks without a BPM are left out.
+    fn start_bpm_radio(&mut self) -> Task<cosmic::Action<Message>> {
+        use rand::seq::{IndexedRandom, SliceRandom};
+
+        let Some(now_playing) = self.playback_service.now_playing() else {
+            return Task::none();
+        };
+        let Some(bpm) = now_playing.bpm else {
+            return self.show_toast(fl!("bpm-radio-no-bpm"));
+        };
+        let current_id = now_playing.id.clone();
+        let genres: Vec<String> = if self.config.bpm_radio_same_genre {
+            now_playing
+                .genres
+                .iter()
+                .map(|genre| genre.to_lowercase())
+                .collect()
+        } else {
+            Vec::new()
+        };
+        let tolerance = self
+            .config
+            .bpm_radio_tolerance
+            .clamp(1, MAX_BPM_RADIO_TOLERANCE) as f64;
+
+        let candidates: Vec<Track> = self
+            .library
+            .media
+            .iter()
+            .filter(|(_, metadata)| metadata.id != current_id)
+            .filter(|(_, metadata)| {
+                metadata
+                    .bpm
+                    .is_some_and(|track_bpm| (track_bpm - bpm).abs() <= tolerance)
+            })
+            .filter(|(_, metadata)| {
+                genres.is_empty()
+                    || metadata
+                        .genres
+                        .iter()
+                        .any(|genre| genres.contains(&genre.to_lowercase()))
+            })
+            .map(|(path, metadata)| {
+                let mut track = Track::new();
+                track.path = path.clone();
+                track.metadata = metadata.clone();
+                track
+            })
+            .collect();
+
+        if candidates.is_empty() {
+            return self.show_toast(fl!("bpm-radio-no-matches"));
+        }
+
+        let mut rng = rand::rng();
+        let mut tracks: Vec<Track> = candidates
+            .choose_multiple(&mut rng, BPM_RADIO_MAX_TRACKS)
+            .cloned()
+            .collect();
+        tracks.shuffle(&mut rng);
+
+        let mut queue = Playlist::new(fl!("bpm-radio"));
+        tracks.into_iter().for_each(|track| queue.push(track));
+
+        self.playback_service.start_session(&queue, 0, false);
+        self.sync_playback_output_from_state();
+        self.playback_service.play();
+        Task::none()
+    }
+
+    /// Normalized (album artist, album) pair used to tell albums apart
+    fn album_key(metadata: &MediaMetaData) -> Option<(String, String)> {
+        let album = non_empty_text(metadata.album.as_deref())?;
+        Some((
+            metadata.display_album_artist().to_lowercase(),
+            album.to_lowercase(),
+        ))
+    }
+
+    /// Count a finished play, true when the track is in the library and it needs saving
+    fn record_play(&mut self, id: Option<&str>) -> bool {
+        let Some(metadata) = id.and_then(|id| self.library.from_id_mut(id)) else {
+            return false;
+        };
+        metadata.record_play();
+        true
+    }
+
+    /// Remove the selected tracks from the playlist being viewed and scroll back to the top
+    fn remove_selected_from_view_playlist(&mut self) -> Task<Message> {
+        let Some(playlist_id) = self.view_playlist else {
+            return Task::none();
+        };
+
+        if let Err(err) = self.playlist_service.remove_selected(playlist_id) {
+            log::error!("Error removing tracks: {}", err);
+        }
+
         self.invalidate_all_caches();
+
+        // Reset viewport scroll to top
+        self.list_start = 0;
+        self.grid_start = 0;
+        scrollable::scroll_to(
+            self.list_scroll_id.clone(),
+            AbsoluteOffset {
+                x: Some(0.0 as f32),
+                y: Some(0.0 as f32),
+            },
+        )
+    }
+
+    /// Reset play statistics for a single track, or the whole library when `id` is `None`
+    fn reset_play_stats(&mut self, id: Option<&str>) -> Task<cosmic::Action<Message>> {
+        match id {
+            Some(id) => match self.library.from_id_mut(id) {
+                Some(metadata) => metadata.reset_play_stats(),
+                None => return Task::none(),
+            },
+            None => self
+                .library
+                .media
+                .values_mut()
+                .for_each(|metadata| metadata.reset_play_stats()),
+        }
+
+        self.save_library()
+    }
+
+    fn set_update_progress(&mut self, current: f32, total: f32, percent: f32) {
+        self.update_progress = current;
+        self.update_total = total;
+        self.update_percent = percent;
+        self.update_progress_display = fl!(
+            "update-progress",
+            activity = self.update_activity.label(),
+            current = format_count(current as usize),
+            total = format_count(total as usize),
+            percent = format_percent(percent as f64, 2)
+        );
     }
 
     fn update_library_playlist(&mut self) {
@@ -3197,20 +7234,135 @@ impl AppModel {
                 track.metadata = metadata.clone();
                 lib_playlist.push(track);
             }
-            lib_playlist.sort(
+            lib_playlist.sort(
+                self.state.sort_by.clone(),
+                self.state.sort_direction.clone(),
+                self.config.title_sort,
+                self.config.sort_ignore_articles,
+                self.config.sort_case_sensitive,
+            );
+
+            let library = lib_playlist.clone();
+            self.playback_service.update_session_for_library(&library);
+            self.scroll_positions.remove(&library.id());
+        }
+
+        self.update_favorites_playlist();
+        self.update_library_path_track_counts();
+        self.sync_manual_gains();
+        self.invalidate_all_caches();
+    }
+
+    /// Check which library paths are on read-only or network mounts. Runs on a blocking
+    /// thread since resolving a path on an unresponsive network mount can hang.
+    fn check_library_path_mounts(&self) -> Task<cosmic::Action<Message>> {
+        let library_paths = self.config.library_paths.clone();
+
+        cosmic::task::future(async move {
+            let statuses =
+                tokio::task::spawn_blocking(move || mounts::mount_statuses(&library_paths))
+                    .await
+                    .unwrap_or_default();
+
+            Message::LibraryPathMounts(statuses)
+        })
+    }
+
+    /// Count the tracks under each library path. A track inside nested library paths counts
+    /// toward the most specific one.
+    fn update_library_path_track_counts(&mut self) {
+        let mut counts: HashMap<String, usize> = HashMap::new();
+
+        for path in self.library.media.keys() {
+            let owner = self
+                .config
+                .library_paths
+                .iter()
+                .filter(|library_path| path.starts_with(library_path.as_str()))
+                .max_by_key(|library_path| Path::new(library_path.as_str()).components().count());
+
+            if let Some(owner) = owner {
+                *counts.entry(owner.clone()).or_default() += 1;
+            }
+        }
+
+        self.library_path_track_counts = counts;
+    }
+
+    /// Rebuild the favorites view from the library
+    fn update_favorites_playlist(&mut self) {
+        let favorites: Vec<Track> = self
+            .library
+            .media
+            .iter()
+            .filter(|(_, metadata)| metadata.favorite)
+            .map(|(path, metadata)| {
+                let mut track = Track::new();
+                track.path = path.clone();
+                track.metadata = metadata.clone();
+                track
+            })
+            .collect();
+
+        if let Ok(playlist) = self.playlist_service.get_favorites_mut() {
+            playlist.clear();
+            for track in favorites {
+                playlist.push(track);
+            }
+            playlist.sort(
                 self.state.sort_by.clone(),
                 self.state.sort_direction.clone(),
                 self.config.title_sort,
+                self.config.sort_ignore_articles,
                 self.config.sort_case_sensitive,
             );
 
-            let library = lib_playlist.clone();
-            self.playback_service.update_session_for_library(&library);
+            let id = playlist.id();
+            self.scroll_positions.remove(&id);
         }
 
         self.invalidate_all_caches();
     }
 
+    /// Keep favorites, play statistics and gains changed while a scan was running, its
+    /// results start from the library as it was when the scan began
+    fn keep_user_data(&self, media: &mut HashMap<PathBuf, MediaMetaData>) {
+        for (path, metadata) in media.iter_mut() {
+            if let Some(current) = self.library.media.get(path) {
+                carry_over_user_data(current, metadata);
+            }
+        }
+    }
+
+    /// Mark or unmark library tracks as favorites and save the library
+    fn set_favorites(&mut self, ids: &[String], favorite: bool) -> Task<cosmic::Action<Message>> {
+        let mut changed = false;
+        for id in ids {
+            if let Some(metadata) = self.library.from_id_mut(id) {
+                changed |= metadata.favorite != favorite;
+                metadata.favorite = favorite;
+            }
+        }
+
+        if !changed {
+            return Task::none();
+        }
+
+        self.update_favorites_playlist();
+        self.save_library()
+    }
+
+    fn sync_manual_gains(&mut self) {
+        let gains = self
+            .library
+            .media
+            .values()
+            .filter_map(|metadata| Some((metadata.id.clone()?, metadata.manual_gain_db?)))
+            .collect();
+
+        self.playback_service.set_manual_gains(gains);
+    }
+
     fn artwork_filenames_in_use(&self) -> HashSet<String> {
         let mut artwork_filenames = HashSet::new();
 
@@ -3320,15 +7472,62 @@ impl AppModel {
         }
     }
 
-    fn normalized_volume(volume: i32) -> f64 {
-        (volume as f64 / 100.0).clamp(0.0, 1.0)
+    // A volume between 0.0 and 1.0, a malformed config value plays at a moderate level
+    fn clamp_volume(volume: f64) -> f64 {
+        if volume.is_nan() {
+            FALLBACK_VOLUME
+        } else {
+            volume.clamp(0.0, 1.0)
+        }
+    }
+
+    /// Applies a volume right away and saves it once it stops changing
+    fn change_volume(&mut self, volume: f64) -> Task<cosmic::Action<Message>> {
+        self.config.volume = Self::clamp_volume(volume);
+        self.playback_service.set_volume(self.config.volume);
+        self.volume_changed_at = Some(Instant::now());
+
+        cosmic::task::future(async {
+            tokio::time::sleep(Duration::from_millis(VOLUME_SAVE_DELAY_MS)).await;
+            Message::SaveVolume
+        })
+    }
+
+    /// Volume used to be kept in state as a percentage, it's carried over until one is saved in
+    /// the config
+    fn migrate_volume_from_state(&mut self) {
+        let (Some(config_handler), Some(state_handler)) =
+            (&self.config_handler, &self.state_handler)
+        else {
+            return;
+        };
+        if config_handler.get::<f64>("volume").is_ok() {
+            return;
+        }
+        let Ok(percent) = state_handler.get::<i32>("volume") else {
+            return;
+        };
+
+        self.config.volume = Self::clamp_volume(f64::from(percent) / 100.0);
+        self.save_volume();
+    }
+
+    // Written directly, `config.volume` already holds the level and the generated setter skips
+    // unchanged values
+    fn save_volume(&self) {
+        let Some(config_handler) = &self.config_handler else {
+            return;
+        };
+        if let Err(err) = config_handler.set("volume", self.config.volume) {
+            log::warn!("failed to save config \"volume\": {}", err);
+        }
     }
 
     fn effective_output_volume(&self) -> f64 {
         if self.state.muted {
             0.0
         } else {
-            Self::normalized_volume(self.state.volume)
+            Self::clamp_volume(self.config.volume)
         }
     }
 
@@ -3349,11 +7548,11 @@ impl AppModel {
             track.metadata.album.as_deref(),
             track.metadata.artist.as_deref(),
             track.metadata.album_artist.as_deref(),
-            track.metadata.genre.as_deref(),
             Some(path.as_ref()),
         ]
         .into_iter()
         .flatten()
+        .chain(track.metadata.genres.iter().map(String::as_str))
         .any(|value| value.to_lowercase().contains(search))
     }
 
@@ -3505,6 +7704,7 @@ impl AppModel {
             playlist_id,
             normalized_search: normalized_search.map(str::to_owned),
             group_by,
+            merge_featured_artists: self.config.merge_featured_artists,
         };
 
         {
@@ -3579,7 +7779,7 @@ impl AppModel {
                 continue;
             };
 
-            let key = Self::grid_group_key(track, group_by);
+            let key = Self::grid_group_key(track, group_by, self.config.merge_featured_artists);
             let group_index = if let Some(&group_index) = group_positions.get(&key) {
                 group_index
             } else {
@@ -3675,156 +7875,569 @@ impl AppModel {
             })
             .collect();
 
-        if playable_indices.is_empty() {
-            return None;
+        if playable_indices.is_empty() {
+            return None;
+        }
+
+        if self.state.shuffle {
+            use rand::Rng;
+            let random_index = rand::rng().random_range(0..playable_indices.len());
+            playable_indices.get(random_index).copied()
+        } else {
+            playable_indices.first().copied()
+        }
+    }
+
+    fn grid_track_subtitle(track: &Track, is_in_library: bool) -> String {
+        if !is_in_library {
+            return fl!("not-in-library");
+        }
+
+        let artist = non_empty_text(track.metadata.artist.as_deref())
+            .or_else(|| non_empty_text(track.metadata.album_artist.as_deref()));
+        let album = non_empty_text(track.metadata.album.as_deref());
+
+        join_non_empty(
+            &[
+                artist.as_deref().unwrap_or_default(),
+                album.as_deref().unwrap_or_default(),
+            ],
+            " • ",
+        )
+    }
+
+    fn grid_album_identity(track: &Track) -> String {
+        let album = fallback_text(track.metadata.album.as_deref(), "Unknown Album");
+        let album_artist = track.metadata.display_album_artist();
+        format!("{}::{}", album.to_lowercase(), album_artist.to_lowercase())
+    }
+
+    fn grid_group_key(track: &Track, group_by: GridGroupBy, merge_featured: bool) -> GridGroupKey {
+        // The tracks keep their full credit, only the card they're grouped under changes
+        let artist_key = |artist: String| {
+            if merge_featured {
+                primary_artist(&artist).to_string()
+            } else {
+                artist
+            }
+        };
+
+        match group_by {
+            GridGroupBy::Track => unreachable!("track grouping does not use a grouped key"),
+            GridGroupBy::Album => GridGroupKey::Album {
+                album: fallback_text(track.metadata.album.as_deref(), "Unknown Album"),
+                album_artist: track.metadata.display_album_artist().to_string(),
+            },
+            GridGroupBy::Artist => GridGroupKey::Artist(artist_key(fallback_text(
+                track
+                    .metadata
+                    .artist
+                    .as_deref()
+                    .or(track.metadata.album_artist.as_deref()),
+                "Unknown Artist",
+            ))),
+            GridGroupBy::AlbumArtist => GridGroupKey::AlbumArtist(artist_key(
+                track.metadata.display_album_artist().to_string(),
+            )),
+        }
+    }
+
+    fn grid_track_count_text(track_count: usize) -> String {
+        if track_count == 1 {
+            "1 track".to_string()
+        } else {
+            format!("{track_count} tracks")
+        }
+    }
+
+    fn grid_album_count_text(album_count: usize) -> String {
+        if album_count == 1 {
+            "1 album".to_string()
+        } else {
+            format!("{album_count} albums")
+        }
+    }
+
+    fn grid_layout_metrics(&self, width: f32) -> GridLayoutMetrics {
+        let artwork_size = clamp(
+            self.grid_size_multiplier * GRID_ARTWORK_SCALE,
+            GRID_MIN_ARTWORK_SIZE,
+            GRID_MAX_ARTWORK_SIZE,
+        );
+        let card_width = artwork_size + (GRID_CARD_PADDING * 2.0);
+        let card_height = artwork_size
+            + (GRID_CARD_PADDING * 2.0)
+            + GRID_TITLE_HEIGHT
+            + GRID_SUBTITLE_HEIGHT
+            + GRID_INFO_HEIGHT
+            + (GRID_CARD_CONTENT_SPACING * 3.0);
+        let available_width = (width - (GRID_VIEW_PADDING * 2.0)).max(card_width);
+        let column_count = (((available_width + GRID_ITEM_SPACING)
+            / (card_width + GRID_ITEM_SPACING))
+            .floor() as usize)
+            .max(1);
+
+        GridLayoutMetrics {
+            artwork_size,
+            card_width,
+            card_height,
+            card_padding: GRID_CARD_PADDING,
+            column_count,
+            item_spacing: GRID_ITEM_SPACING,
+            row_stride: card_height + GRID_ITEM_SPACING,
+            view_padding: GRID_VIEW_PADDING,
+        }
+    }
+
+    fn grid_row_stride(&self) -> f32 {
+        self.grid_layout_metrics(self.state.window_width).row_stride
+    }
+
+    fn grid_visible_row_count(&self, height: f32) -> usize {
+        Self::grid_visible_row_count_for_stride(height, self.grid_row_stride())
+    }
+
+    fn grid_visible_row_count_for_stride(height: f32, row_stride: f32) -> usize {
+        if row_stride == 0.0 {
+            0
+        } else {
+            (height / row_stride).ceil() as usize
+        }
+    }
+
+    fn grid_total_rows(track_count: usize, column_count: usize) -> usize {
+        if track_count == 0 {
+            0
+        } else {
+            (track_count - 1) / column_count.max(1) + 1
+        }
+    }
+
+    /// Text the current sort orders entries by, `None` for sorts that aren't alphabetical
+    fn jump_text<'a>(track: &'a Track, sort_by: &SortBy, ignore_articles: bool) -> Option<&'a str> {
+        match sort_by {
+            SortBy::Artist => track.metadata.artist_sort_key(ignore_articles),
+            SortBy::Album => track.metadata.album_sort_key(ignore_articles),
+            SortBy::AlbumArtist => Some(track.metadata.album_artist_sort_key(ignore_articles)),
+            SortBy::Genre => track.metadata.genre(),
+            SortBy::Title => track.metadata.title_sort_key(ignore_articles),
+            _ => None,
+        }
+    }
+
+    /// Whether the jump strip has anything to jump through. Grid cards are always jumped to
+    /// by their title, list rows only when sorted by a text column.
+    pub fn can_jump(&self) -> bool {
+        match self.config.view_mode {
+            ViewMode::List => matches!(
+                self.state.sort_by,
+                SortBy::Artist
+                    | SortBy::Album
+                    | SortBy::AlbumArtist
+                    | SortBy::Genre
+                    | SortBy::Title
+            ),
+            ViewMode::Grid => true,
+        }
+    }
+
+    /// Scroll to the first list row or grid card starting with `letter`, '#' finds the first
+    /// one that doesn't start with a letter
+    fn jump_to_letter(&mut self, letter: char) -> Task<cosmic::Action<Message>> {
+        let Some(playlist_id) = self.view_playlist else {
+            return Task::none();
+        };
+
+        let target = {
+            let Ok(playlist) = self.playlist_service.get(playlist_id) else {
+                return Task::none();
+            };
+            let tracks = playlist.tracks();
+            let normalized_search = self.search_term.as_ref().map(|term| term.to_lowercase());
+            let (filtered_track_indices, _, _) =
+                self.cached_view_base(playlist_id, tracks, normalized_search.as_deref());
+
+            match self.config.view_mode {
+                ViewMode::List => filtered_track_indices.iter().position(|&index| {
+                    tracks
+                        .get(index)
+                        .and_then(|track| {
+                            Self::jump_text(
+                                track,
+                                &self.state.sort_by,
+                                self.config.sort_ignore_articles,
+                            )
+                        })
+                        .is_some_and(|text| jump_letter(text) == letter)
+                }),
+                ViewMode::Grid => self
+                    .cached_grid_card_bases(
+                        playlist_id,
+                        tracks,
+                        filtered_track_indices,
+                        normalized_search.as_deref(),
+                        self.config.grid_group_by,
+                    )
+                    .iter()
+                    .position(|card| {
+                        let title = if self.config.sort_ignore_articles {
+                            strip_leading_article(&card.title)
+                        } else {
+                            &card.title
+                        };
+                        jump_letter(title) == letter
+                    }),
+            }
+        };
+
+        let Some(target) = target else {
+            return Task::none();
+        };
+
+        let y = match self.config.view_mode {
+            ViewMode::List => {
+                let row_stride = calculate_row_stride(
+                    self.list_size_multiplier,
+                    BASE_ROW_HEIGHT,
+                    DIVIDER_HEIGHT,
+                );
+                let max_start =
+                    Self::max_list_start(self.visible_track_count(), self.list_visible_row_count);
+                self.list_start = target.min(max_start);
+                self.list_start as f32 * row_stride
+            }
+            // The grid sits beside the nav bar unless it's hidden or overlaid, the scroll
+            // handler sets grid_start once the view has moved
+            ViewMode::Grid => {
+                let width = if self.core.nav_bar_active() && !self.core.is_condensed() {
+                    self.state.window_width - NAV_BAR_WIDTH
+                } else {
+                    self.state.window_width
+                };
+                let layout = self.grid_layout_metrics(width);
+                (target / layout.column_count) as f32 * layout.row_stride
+            }
+        };
+
+        scrollable::scroll_to(
+            self.list_scroll_id.clone(),
+            AbsoluteOffset {
+                x: Some(0.0),
+                y: Some(y),
+            },
+        )
+    }
+
+    fn max_list_start(track_count: usize, visible_row_count: usize) -> usize {
+        track_count.saturating_sub(visible_row_count.max(1))
+    }
+
+    /// Parse a 1-based track number typed into the go to track dialog
+    fn parse_track_number(number: &str, track_count: usize) -> Option<usize> {
+        number
+            .trim()
+            .parse::<usize>()
+            .ok()
+            .filter(|number| (1..=track_count).contains(number))
+    }
+
+    /// Start the library at the track playing when the app last quit, from where it was.
+    /// Returns false when there's no such track in the library.
+    fn resume_last_track(&mut self) -> bool {
+        let Some((path, position)) = self.state.last_track.clone() else {
+            return false;
+        };
+        let Ok(library) = self.playlist_service.get_library() else {
+            return false;
+        };
+        let Some(index) = library.tracks().iter().position(|track| track.path == path) else {
+            return false;
+        };
+
+        self.playback_service
+            .start_session(library, index, self.state.shuffle);
+        self.sync_playback_output_from_state();
+        self.playback_service.play();
+        self.playback_service.seek_when_ready(position);
+        true
+    }
+
+    /// Switch to a playlist's page as if it was picked in the nav bar
+    fn show_playlist(&mut self, playlist_id: PlaylistId) -> Task<cosmic::Action<Message>> {
+        let nav_id = self.nav.iter().find(|nav_id| {
+            matches!(self.nav.data::<Page>(*nav_id), Some(Page::Playlist(id)) if *id == playlist_id)
+        });
+
+        match nav_id {
+            Some(nav_id) => self.on_nav_select(nav_id),
+            None => Task::none(),
+        }
+    }
+
+    /// Select the Nth track of the current view, optionally starting playback from it
+    fn go_to_track_number(&mut self, number: usize, play: bool) -> Task<cosmic::Action<Message>> {
+        let Some(playlist_id) = self.view_playlist else {
+            return Task::none();
+        };
+
+        let (row, index) = {
+            let Ok(playlist) = self.playlist_service.get(playlist_id) else {
+                return Task::none();
+            };
+
+            let normalized_search = self.search_term.as_ref().map(|term| term.to_lowercase());
+            let (filtered_track_indices, _, _) =
+                self.cached_view_base(playlist_id, playlist.tracks(), normalized_search.as_deref());
+
+            if filtered_track_indices.is_empty() {
+                return Task::none();
+            }
+
+            let track_count = filtered_track_indices.len();
+            if !(1..=track_count).contains(&number) {
+                log::info!(
+                    "track number {} is out of range 1..={}, clamping",
+                    number,
+                    track_count
+                );
+            }
+
+            let row = number.clamp(1, track_count) - 1;
+            (row, filtered_track_indices[row])
+        };
+
+        let _ = self.playlist_service.clear_selection(playlist_id);
+        let _ = self.playlist_service.select_track(playlist_id, index);
+        self.list_last_selected_id = Some(index);
+        self.invalidate_view_cache();
+
+        if play {
+            let mut started = false;
+            if let Ok(playlist) = self.playlist_service.get(playlist_id) {
+                let is_in_library = playlist
+                    .tracks()
+                    .get(index)
+                    .is_some_and(|track| self.library.media.contains_key(&track.path));
+
+                if is_in_library {
+                    self.playback_service
+                        .start_session(playlist, index, self.state.shuffle);
+                    started = true;
+                }
+            }
+            if started {
+                self.sync_playback_output_from_state();
+                self.playback_service.play();
+            }
+        }
+
+        if self.config.view_mode != ViewMode::List {
+            return Task::none();
         }
 
-        if self.state.shuffle {
-            use rand::Rng;
-            let random_index = rand::rng().random_range(0..playable_indices.len());
-            playable_indices.get(random_index).copied()
-        } else {
-            playable_indices.first().copied()
+        self.scroll_list_to_row(row)
+    }
+
+    /// Scroll the list view so the given row of the filtered view is near the middle
+    /// Peaks for the seek preview of the playing track, when enabled and already computed
+    pub fn current_waveform(&self) -> Option<&[f32]> {
+        if !self.config.show_waveform_preview {
+            return None;
         }
+
+        let id = self.playback_service.now_playing()?.id.as_ref()?;
+        self.waveforms.get(id).map(Vec::as_slice)
     }
 
-    fn grid_track_subtitle(track: &Track, is_in_library: bool) -> String {
-        if !is_in_library {
-            return fl!("not-in-library");
+    /// Load or compute the seek preview peaks for the playing track the first time it plays.
+    /// Computing decodes the whole file, so it only runs when the preview is enabled.
+    fn request_waveform(&mut self) -> Task<cosmic::Action<Message>> {
+        if !self.config.show_waveform_preview {
+            return Task::none();
         }
 
-        let artist = non_empty_text(track.metadata.artist.as_deref())
-            .or_else(|| non_empty_text(track.metadata.album_artist.as_deref()));
-        let album = non_empty_text(track.metadata.album.as_deref());
+        let Some(metadata) = self.playback_service.now_playing() else {
+            return Task::none();
+        };
+        let Some(id) = metadata.id.clone() else {
+            return Task::none();
+        };
+        // Peaks are computed for whole files, which doesn't fit a cue track's range
+        if metadata.is_cue_track() {
+            return Task::none();
+        }
+        let duration = metadata.duration.unwrap_or(0.0);
+        let Some(path) = self
+            .playback_service
+            .session()
+            .and_then(|session| session.order.get(session.index))
+            .map(|track| track.path.clone())
+            // Pipes can only be read once, by the player
+            .filter(|path| path.is_file())
+        else {
+            return Task::none();
+        };
 
-        join_non_empty(
-            &[
-                artist.as_deref().unwrap_or_default(),
-                album.as_deref().unwrap_or_default(),
-            ],
-            " • ",
-        )
-    }
+        if self.waveforms.contains_key(&id) || !self.waveform_pending.insert(id.clone()) {
+            return Task::none();
+        }
 
-    fn grid_album_identity(track: &Track) -> String {
-        let album = fallback_text(track.metadata.album.as_deref(), "Unknown Album");
-        let album_artist = fallback_text(
-            track
-                .metadata
-                .album_artist
-                .as_deref()
-                .or(track.metadata.artist.as_deref()),
-            "Unknown Album Artist",
-        );
-        format!("{}::{}", album.to_lowercase(), album_artist.to_lowercase())
+        let xdg_dirs = self.app_xdg_dirs.clone();
+
+        cosmic::task::future(async move {
+            let track_id = id.clone();
+            let peaks = tokio::task::spawn_blocking(move || {
+                if let Some(peaks) = waveform::load(&xdg_dirs, &track_id) {
+                    return Some(peaks);
+                }
+
+                match waveform::compute_peaks(&path, duration) {
+                    Ok(peaks) => {
+                        if let Err(e) = waveform::save(&xdg_dirs, &track_id, &peaks) {
+                            log::error!("Error saving waveform: {}", e);
+                        }
+                        Some(peaks)
+                    }
+                    Err(e) => {
+                        log::warn!("Failed to compute waveform for {:?}: {}", path, e);
+                        None
+                    }
+                }
+            })
+            .await
+            .ok()
+            .flatten();
+
+            Message::WaveformReady(id, peaks)
+        })
     }
 
-    fn grid_group_key(track: &Track, group_by: GridGroupBy) -> GridGroupKey {
-        match group_by {
-            GridGroupBy::Track => unreachable!("track grouping does not use a grouped key"),
-            GridGroupBy::Album => GridGroupKey::Album {
-                album: fallback_text(track.metadata.album.as_deref(), "Unknown Album"),
-                album_artist: fallback_text(
-                    track
-                        .metadata
-                        .album_artist
-                        .as_deref()
-                        .or(track.metadata.artist.as_deref()),
-                    "Unknown Album Artist",
-                ),
-            },
-            GridGroupBy::Artist => GridGroupKey::Artist(fallback_text(
-                track
-                    .metadata
-                    .artist
-                    .as_deref()
-                    .or(track.metadata.album_artist.as_deref()),
-                "Unknown Artist",
-            )),
-            GridGroupBy::AlbumArtist => GridGroupKey::AlbumArtist(fallback_text(
-                track
-                    .metadata
-                    .album_artist
-                    .as_deref()
-                    .or(track.metadata.artist.as_deref()),
-                "Unknown Album Artist",
-            )),
+    /// Skip the silent lead-in and tail of the playing track, finding them the first time it
+    /// plays this session. Called every tick like `request_waveform`.
+    fn skip_silence(&mut self) -> Task<cosmic::Action<Message>> {
+        if !self.config.skip_silence {
+            return Task::none();
         }
-    }
 
-    fn grid_track_count_text(track_count: usize) -> String {
-        if track_count == 1 {
-            "1 track".to_string()
-        } else {
-            format!("{track_count} tracks")
+        let Some(metadata) = self.playback_service.now_playing() else {
+            return Task::none();
+        };
+        let Some(id) = metadata.id.clone() else {
+            return Task::none();
+        };
+        // Only whole files are analyzed, a cue track's silence belongs to its neighbors
+        if metadata.is_cue_track() {
+            return Task::none();
         }
-    }
 
-    fn grid_album_count_text(album_count: usize) -> String {
-        if album_count == 1 {
-            "1 album".to_string()
-        } else {
-            format!("{album_count} albums")
+        if let Some(bounds) = self.silence_bounds.get(&id) {
+            if let Some(bounds) = *bounds {
+                self.playback_service.skip_silence(&id, bounds);
+            }
+            return Task::none();
         }
-    }
 
-    fn grid_layout_metrics(&self, width: f32) -> GridLayoutMetrics {
-        let artwork_size = clamp(
-            self.grid_size_multiplier * GRID_ARTWORK_SCALE,
-            GRID_MIN_ARTWORK_SIZE,
-            GRID_MAX_ARTWORK_SIZE,
-        );
-        let card_width = artwork_size + (GRID_CARD_PADDING * 2.0);
-        let card_height = artwork_size
-            + (GRID_CARD_PADDING * 2.0)
-            + GRID_TITLE_HEIGHT
-            + GRID_SUBTITLE_HEIGHT
-            + GRID_INFO_HEIGHT
-            + (GRID_CARD_CONTENT_SPACING * 3.0);
-        let available_width = (width - (GRID_VIEW_PADDING * 2.0)).max(card_width);
-        let column_count = (((available_width + GRID_ITEM_SPACING)
-            / (card_width + GRID_ITEM_SPACING))
-            .floor() as usize)
-            .max(1);
+        let Some(path) = self
+            .playback_service
+            .session()
+            .and_then(|session| session.order.get(session.index))
+            .map(|track| track.path.clone())
+            // Pipes can only be read once, by the player
+            .filter(|path| path.is_file())
+        else {
+            return Task::none();
+        };
 
-        GridLayoutMetrics {
-            artwork_size,
-            card_width,
-            card_height,
-            card_padding: GRID_CARD_PADDING,
-            column_count,
-            item_spacing: GRID_ITEM_SPACING,
-            row_stride: card_height + GRID_ITEM_SPACING,
-            view_padding: GRID_VIEW_PADDING,
+        if !self.silence_pending.insert(id.clone()) {
+            return Task::none();
         }
-    }
 
-    fn grid_row_stride(&self) -> f32 {
-        self.grid_layout_metrics(self.state.window_width).row_stride
-    }
+        let threshold_db = self.config.skip_silence_threshold_db as f64;
 
-    fn grid_visible_row_count(&self, height: f32) -> usize {
-        Self::grid_visible_row_count_for_stride(height, self.grid_row_stride())
+        cosmic::task::future(async move {
+            let bounds =
+                tokio::task::spawn_blocking(move || match silence::detect(&path, threshold_db) {
+                    Ok(bounds) => bounds,
+                    Err(e) => {
+                        log::warn!("Failed to detect silence in {:?}: {}", path, e);
+                        None
+                    }
+                })
+                .await
+                .ok()
+                .flatten();
+
+            Message::SilenceBoundsReady(id, bounds)
+        })
     }
 
-    fn grid_visible_row_count_for_stride(height: f32, row_stride: f32) -> usize {
-        if row_stride == 0.0 {
-            0
-        } else {
-            (height / row_stride).ceil() as usize
+    /// Scroll the list to the playing track after it changes, when following is enabled and
+    /// the track is in the current view but off screen. Called every tick so manual skips,
+    /// auto-advance and MPRIS commands are all caught.
+    fn follow_current_track(&mut self) -> Task<cosmic::Action<Message>> {
+        let playing = self.playback_service.session().and_then(|session| {
+            let track = session.order.get(session.index)?;
+            Some((session.playlist_id, track.entry_id, track.path.clone()))
+        });
+        let playing_key = playing
+            .as_ref()
+            .map(|(_, entry_id, path)| (*entry_id, path.clone()));
+
+        if playing_key == self.followed_track {
+            return Task::none();
         }
-    }
+        self.followed_track = playing_key;
 
-    fn grid_total_rows(track_count: usize, column_count: usize) -> usize {
-        if track_count == 0 {
-            0
-        } else {
-            (track_count - 1) / column_count.max(1) + 1
+        let Some((playlist_id, entry_id, path)) = playing else {
+            return Task::none();
+        };
+        if !self.config.follow_current_track
+            || self.config.view_mode != ViewMode::List
+            || self.view_playlist != Some(playlist_id)
+        {
+            return Task::none();
+        }
+
+        let Ok(playlist) = self.playlist_service.get(playlist_id) else {
+            return Task::none();
+        };
+        let normalized_search = self.search_term.as_ref().map(|term| term.to_lowercase());
+        let (filtered_track_indices, _, _) =
+            self.cached_view_base(playlist_id, playlist.tracks(), normalized_search.as_deref());
+
+        let Some(row) = filtered_track_indices.iter().position(|index| {
+            playlist
+                .tracks()
+                .get(*index)
+                .is_some_and(|track| track.entry_id == entry_id && track.path == path)
+        }) else {
+            return Task::none();
+        };
+
+        let visible_rows = self.list_start..self.list_start + self.list_visible_row_count;
+        if visible_rows.contains(&row) {
+            return Task::none();
         }
+
+        self.scroll_list_to_row(row)
     }
 
-    fn max_list_start(track_count: usize, visible_row_count: usize) -> usize {
-        track_count.saturating_sub(visible_row_count.max(1))
+    fn scroll_list_to_row(&mut self, row: usize) -> Task<cosmic::Action<Message>> {
+        let row_stride =
+            calculate_row_stride(self.list_size_multiplier, BASE_ROW_HEIGHT, DIVIDER_HEIGHT);
+        let max_start =
+            Self::max_list_start(self.visible_track_count(), self.list_visible_row_count);
+        self.list_start = row
+            .saturating_sub(self.list_visible_row_count / 2)
+            .min(max_start);
+
+        scrollable::scroll_to(
+            self.list_scroll_id.clone(),
+            AbsoluteOffset {
+                x: Some(0.0),
+                y: Some(self.list_start as f32 * row_stride),
+            },
+        )
     }
 }
 
@@ -3840,6 +8453,167 @@ pub struct Flags {
     pub config_handler: Option<cosmic_config::Config>,
     pub state_handler: Option<cosmic_config::Config>,
     pub state: State,
+    pub open: Option<OpenRequest>,
+}
+
+/// An audio file to play straight away, optionally starting part way in
+#[derive(Clone, Debug)]
+pub struct OpenRequest {
+    pub path: PathBuf,
+    pub start_secs: Option<f32>,
+    /// Read from a named pipe or stdin, which plays once from the start and can't seek
+    pub stream: bool,
+}
+
+impl OpenRequest {
+    /// Parse `[--at SECONDS] FILE` or `--stream SOURCE` from the command line. File URIs are
+    /// accepted so desktop entries can pass `%U`. Anything that isn't an existing audio file
+    /// is ignored.
+    ///
+    /// A stream source is a named pipe as a path or `file://` URI, `-` or `fd://0` for stdin,
+    /// or `fd://N` for another inherited file descriptor.
+    pub fn from_args(args: impl Iterator<Item = String>) -> Option<Self> {
+        let mut path = None;
+        let mut start_secs = None;
+        let mut stream = false;
+        let mut args = args;
+
+        while let Some(arg) = args.next() {
+            if arg == "--at" {
+                start_secs = args.next().and_then(|value| value.parse::<f32>().ok());
+            } else if let Some(value) = arg.strip_prefix("--at=") {
+                start_secs = value.parse::<f32>().ok();
+            } else if arg == "--stream" {
+                stream = true;
+            } else if path.is_none() && stream && arg == "-" {
+                path = Some(PathBuf::from(STDIN_PATH));
+            } else if let Some(fd_path) = Self::fd_path(&arg).filter(|_| path.is_none()) {
+                path = Some(fd_path);
+                stream = true;
+            } else if path.is_none() && !arg.starts_with("--") {
+                path = Some(match Self::from_uri(&arg) {
+                    Some(request) => {
+                        start_secs = start_secs.or(request.start_secs);
+                        stream |= request.stream;
+                        request.path
+                    }
+                    None => PathBuf::from(arg),
+                });
+            }
+        }
+
+        Self::for_path(path?, start_secs, stream)
+    }
+
+    /// A file forwarded by another instance, as a path or `file://` URI. Streams are refused,
+    /// a pipe or descriptor of the sending process is only read by that process.
+    #[cfg(feature = "single-instance")]
+    pub fn from_remote(arg: &str) -> Option<Self> {
+        let request = Self::from_uri(arg);
+        let (path, start_secs) = match &request {
+            Some(request) => (request.path.clone(), request.start_secs),
+            None => (PathBuf::from(arg), None),
+        };
+
+        let stream = request.is_some_and(|request| request.stream)
+            || Self::fd_path(arg).is_some()
+            || path.starts_with("/dev/fd")
+            || path == Path::new(STDIN_PATH);
+        if stream {
+            log::warn!("Ignoring {}: streams aren't forwarded", arg);
+            return None;
+        }
+
+        Self::for_path(path, start_secs, false)
+    }
+
+    /// The `/dev/fd` entry of an `fd://N` URI, a descriptor this process inherited
+    fn fd_path(uri: &str) -> Option<PathBuf> {
+        let url = url::Url::parse(uri).ok()?;
+        if url.scheme() != "fd" {
+            return None;
+        }
+
+        let fd = url.host_str()?.parse::<u32>().ok()?;
+        Some(PathBuf::from(format!("/dev/fd/{fd}")))
+    }
+
+    /// Parse a `file://` URI, a `#t=SECONDS` media fragment sets the start position and
+    /// `#stream` marks a pipe
+    pub fn from_uri(uri: &str) -> Option<Self> {
+        let url = url::Url::parse(uri).ok()?;
+
+        if url.scheme() != "file" {
+            return None;
+        }
+        let start_secs = url
+            .fragment()
+            .and_then(|fragment| fragment.strip_prefix("t="))
+            .and_then(|secs| secs.parse::<f32>().ok());
+
+        Some(Self {
+            path: url.to_file_path().ok()?,
+            start_secs,
+            stream: url.fragment() == Some("stream"),
+        })
+    }
+
+    fn for_path(path: PathBuf, start_secs: Option<f32>, stream: bool) -> Option<Self> {
+        if stream {
+            // A terminal on stdin has nothing to play
+            let readable = fs::metadata(&path)
+                .is_ok_and(|metadata| metadata.file_type().is_fifo() || metadata.is_file());
+            if !readable {
+                log::warn!("Ignoring {}: not a pipe or file", path.display());
+                return None;
+            }
+
+            return Some(Self {
+                path,
+                start_secs: None,
+                stream,
+            });
+        }
+
+        let is_audio = path
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .is_some_and(|extension| {
+                VALID_AUDIO_EXTENSIONS.contains(&extension.to_lowercase().as_str())
+            });
+
+        if !path.is_file() || !is_audio {
+            log::warn!("Ignoring {}: not an audio file", path.display());
+            return None;
+        }
+
+        Some(Self {
+            path: path.canonicalize().unwrap_or(path),
+            start_secs: start_secs.filter(|secs| secs.is_finite()),
+            stream: false,
+        })
+    }
+}
+
+// Sent to an already running instance as a file URI, streams are never sent
+impl std::fmt::Display for OpenRequest {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let Ok(mut url) = url::Url::from_file_path(&self.path) else {
+            return write!(f, "{}", self.path.display());
+        };
+        url.set_fragment(self.start_secs.map(|secs| format!("t={secs}")).as_deref());
+        write!(f, "{url}")
+    }
+}
+
+#[cfg(feature = "single-instance")]
+impl cosmic::app::CosmicFlags for Flags {
+    type SubCommand = OpenRequest;
+    type Args = Vec<String>;
+
+    fn action(&self) -> Option<&OpenRequest> {
+        self.open.as_ref()
+    }
 }
 
 /// The page to display in the application.
@@ -3855,6 +8629,10 @@ pub enum ContextPage {
     About,
     Settings,
     TrackInfo,
+    Chapters,
+    Debug,
+    Logs,
+    ScanReport,
 }
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
@@ -3862,21 +8640,43 @@ pub enum MenuAction {
     About,
     AddSelectedToPlaylist(PlaylistId),
     AddNowPlayingToPlaylist(PlaylistId),
+    AnalyzeLoudness,
+    CommandPalette,
     RemoveSelectedFromPlaylist,
+    RescanSelected,
+    DebugPanel,
     DeletePlaylist,
+    DuplicatePlaylist(PlaylistId),
+    ExportSelected,
+    GoToNowPlaying,
+    GoToTrack,
+    ImportFolderAsPlaylist,
+    Logs,
     MoveNavDown,
     MoveNavUp,
     NewPlaylist,
+    PreviewLibraryUpdate,
+    QuickAddCurrent,
+    CopyNowPlaying(ShareFormat),
     Quit,
     RenamePlaylist,
+    ResetPlayStats,
+    RevealNowPlayingInLibrary,
+    SaveQueue,
+    ScanReport,
     SelectAll,
     SetViewMode(ViewMode),
+    ShuffleLibrary,
+    StartBpmRadio,
     Settings,
+    ToggleFavoriteNowPlaying,
+    ToggleFooter,
     ToggleMute,
     ToggleRepeat,
     ToggleRepeatMode,
     ToggleShuffle,
     TrackInfoPanel,
+    UndoLastScan,
     UpdateLibrary,
     VolumeDown,
     VolumeUp,
@@ -3892,21 +8692,43 @@ impl menu::action::MenuAction for MenuAction {
             MenuAction::About => Message::ToggleContextPage(ContextPage::About),
             MenuAction::AddSelectedToPlaylist(id) => Message::AddSelectedToPlaylist(*id),
             MenuAction::AddNowPlayingToPlaylist(id) => Message::AddNowPlayingToPlaylist(*id),
+            MenuAction::AnalyzeLoudness => Message::AnalyzeLoudness,
+            MenuAction::CommandPalette => Message::CommandPalette,
             MenuAction::RemoveSelectedFromPlaylist => Message::RemoveSelectedFromPlaylist,
+            MenuAction::RescanSelected => Message::RescanSelected,
+            MenuAction::DebugPanel => Message::ToggleContextPage(ContextPage::Debug),
             MenuAction::DeletePlaylist => Message::DeletePlaylist,
+            MenuAction::DuplicatePlaylist(id) => Message::DuplicatePlaylist(*id),
+            MenuAction::ExportSelected => Message::ExportSelected,
+            MenuAction::GoToNowPlaying => Message::GoToNowPlaying,
+            MenuAction::GoToTrack => Message::GoToTrack,
+            MenuAction::Logs => Message::ToggleContextPage(ContextPage::Logs),
+            MenuAction::ShuffleLibrary => Message::ShuffleLibrary,
+            MenuAction::StartBpmRadio => Message::StartBpmRadio,
+            MenuAction::ScanReport => Message::ToggleContextPage(ContextPage::ScanReport),
             MenuAction::MoveNavDown => Message::MoveNavDown,
             MenuAction::MoveNavUp => Message::MoveNavUp,
             MenuAction::NewPlaylist => Message::NewPlaylist,
+            MenuAction::PreviewLibraryUpdate => Message::PreviewLibraryUpdate,
+            MenuAction::ImportFolderAsPlaylist => Message::ImportFolderDialog,
             MenuAction::RenamePlaylist => Message::RenamePlaylist,
+            MenuAction::QuickAddCurrent => Message::QuickAddCurrent,
+            MenuAction::CopyNowPlaying(format) => Message::CopyNowPlaying(format),
             MenuAction::Quit => Message::Quit,
+            MenuAction::ResetPlayStats => Message::ResetPlayStats(None),
+            MenuAction::RevealNowPlayingInLibrary => Message::RevealNowPlayingInLibrary,
+            MenuAction::SaveQueue => Message::SaveQueue,
             MenuAction::SelectAll => Message::SelectAll,
             MenuAction::SetViewMode(view_mode) => Message::SetViewMode(*view_mode),
             MenuAction::Settings => Message::ToggleContextPage(ContextPage::Settings),
+            MenuAction::ToggleFavoriteNowPlaying => Message::ToggleFavoriteNowPlaying,
+            MenuAction::ToggleFooter => Message::ToggleFooter,
             MenuAction::ToggleMute => Message::ToggleMute,
             MenuAction::ToggleRepeat => Message::ToggleRepeat,
             MenuAction::ToggleRepeatMode => Message::ToggleRepeatMode,
             MenuAction::ToggleShuffle => Message::ToggleShuffle,
             MenuAction::TrackInfoPanel => Message::ToggleContextPage(ContextPage::TrackInfo),
+            MenuAction::UndoLastScan => Message::UndoLastScan,
             MenuAction::UpdateLibrary => Message::UpdateLibrary,
             MenuAction::VolumeDown => Message::VolumeDown,
             MenuAction::VolumeUp => Message::VolumeUp,
@@ -3919,16 +8741,34 @@ impl menu::action::MenuAction for MenuAction {
 #[derive(Clone, Debug)]
 pub enum DialogPage {
     NewPlaylist(String),
+    SaveQueueAsPlaylist(String),
     RenamePlaylist {
         id: u32,
         name: String,
     },
     DeletePlaylist(u32),
-    DeleteSelectedFromPlaylist,
+    DeleteSelectedFromPlaylist {
+        dont_ask_again: bool,
+    },
+    GoToTrack {
+        number: String,
+        play: bool,
+    },
+    ResetPlayStats,
+    ExportTracks {
+        destination: PathBuf,
+        template: String,
+        format: ExportFormat,
+        bitrate_kbps: u32,
+    },
     ConfirmPlaylistDuplicate {
         destination_id: PlaylistId,
         tracks: VecDeque<Track>,
     },
+    QueueEnded(QueueEndAction),
+    EnterLibraryPath(String),
+    CommandPalette(String),
+    ScanPreview(ScanPreview),
 }
 
 pub struct DialogPages {
@@ -3967,6 +8807,10 @@ impl DialogPages {
             self.pages[0] = page;
         }
     }
+
+    pub fn retain(&mut self, keep: impl FnMut(&DialogPage) -> bool) {
+        self.pages.retain(keep);
+    }
 }
 
 #[derive(PartialEq, Clone, Debug, Serialize, Deserialize)]
@@ -3981,6 +8825,7 @@ pub enum SortBy {
     Genre,
     Title,
     TrackTotal,
+    Bpm,
 }
 
 #[derive(PartialEq, Clone, Debug, Serialize, Deserialize)]
@@ -3992,15 +8837,58 @@ pub enum SortDirection {
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum PlaylistKind {
     Library,
+    Favorites,
     User,
 }
 
+/// Background work reported by the footer progress bar
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum UpdateActivity {
+    Library,
+    LoudnessAnalysis,
+    Export,
+}
+
+impl UpdateActivity {
+    fn label(self) -> String {
+        match self {
+            UpdateActivity::Library => fl!("updating-library"),
+            UpdateActivity::LoudnessAnalysis => fl!("analyzing-loudness"),
+            UpdateActivity::Export => fl!("exporting-tracks"),
+        }
+    }
+}
+
 #[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub enum ViewMode {
     List,
     Grid,
 }
 
+/// Warning dialog for actions that can't be undone. `dont_ask_again` adds a checkbox with its
+/// current state and the message it sends when toggled, for actions that are easy to redo.
+fn confirm_dialog<'a>(
+    title: String,
+    body: String,
+    confirm_label: String,
+    on_confirm: Message,
+    dont_ask_again: Option<(bool, fn(bool) -> Message)>,
+) -> widget::Dialog<'a, Message> {
+    let dialog = widget::dialog()
+        .title(title)
+        .icon(widget::icon::from_name("dialog-warning").size(64))
+        .body(body)
+        .primary_action(widget::button::destructive(confirm_label).on_press(on_confirm))
+        .secondary_action(widget::button::standard(fl!("cancel")).on_press(Message::DialogCancel));
+
+    match dont_ask_again {
+        Some((checked, on_toggle)) => {
+            dialog.control(widget::checkbox(fl!("dont-ask-again"), checked).on_toggle(on_toggle))
+        }
+        None => dialog,
+    }
+}
+
 fn track_info_row<'a>(title: String, data: String) -> widget::Row<'a, Message> {
     let cosmic_theme::Spacing { space_xxs, .. } = theme::active().cosmic().spacing;
 
@@ -4032,6 +8920,7 @@ fn list_column_label(column: ListColumn) -> String {
         ListColumn::Genre => fl!("genre"),
         ListColumn::FilePath => fl!("file-path"),
         ListColumn::Duration => fl!("duration"),
+        ListColumn::Bpm => fl!("bpm"),
     }
 }
 
@@ -4079,6 +8968,9 @@ fn list_column_settings_control<'a>(
             ListColumn::Title => toggler(config.list_show_title_column)
                 .on_toggle(Message::ToggleListTitleColumn)
                 .into(),
+            ListColumn::Bpm => toggler(config.list_show_bpm_column)
+                .on_toggle(Message::ToggleListBpmColumn)
+                .into(),
         };
 
         controls = controls.push(toggle);
@@ -4086,14 +8978,18 @@ fn list_column_settings_control<'a>(
 
     controls
         .push(
-            widget::button::icon(widget::icon::from_name("pan-up-symbolic"))
-                .extra_small()
-                .on_press_maybe(can_move_up.then_some(Message::MoveListColumnUp(column))),
+            icon_button(
+                widget::icon::from_name("pan-up-symbolic").size(14),
+                fl!("move-column-up", column = list_column_label(column)),
+            )
+            .on_press_maybe(can_move_up.then_some(Message::MoveListColumnUp(column))),
         )
         .push(
-            widget::button::icon(widget::icon::from_name("pan-down-symbolic"))
-                .extra_small()
-                .on_press_maybe(can_move_down.then_some(Message::MoveListColumnDown(column))),
+            icon_button(
+                widget::icon::from_name("pan-down-symbolic").size(14),
+                fl!("move-column-down", column = list_column_label(column)),
+            )
+            .on_press_maybe(can_move_down.then_some(Message::MoveListColumnDown(column))),
         )
         .into()
 }
@@ -4116,6 +9012,7 @@ struct CachedGridCardBaseKey {
     playlist_id: PlaylistId,
     normalized_search: Option<String>,
     group_by: GridGroupBy,
+    merge_featured_artists: bool,
 }
 
 struct CachedGridCardBase {
@@ -4201,6 +9098,7 @@ struct GridLayoutMetrics {
 
 pub struct GridViewModel {
     pub visible_cards: Vec<GridCardModel>,
+    pub prefetch_artwork: Vec<String>,
     pub selected_track_ids: Arc<Vec<String>>,
     pub column_count: usize,
     pub card_width: f32,
@@ -4228,6 +9126,32 @@ pub struct ListViewModel {
     pub wrapping: Wrapping,
     pub row_align: Alignment,
     pub sort_direction_icon: String,
+    /// Show names taken from the file path in place of missing tags
+    pub path_fallbacks: bool,
+}
+
+impl ListViewModel {
+    /// Layout for a few rows shown outside the main list, such as the footer queue. Nothing
+    /// is scrolled or selected.
+    pub fn compact(row_height: f32, path_fallbacks: bool) -> Self {
+        Self {
+            visible_track_indices: Vec::new(),
+            selected_track_ids: Arc::new(Vec::new()),
+            max_track_number_chars: 2,
+            list_start: 0,
+            number_column_width: 22.0,
+            icon_column_width: 24.0,
+            row_stride: row_height,
+            viewport_height: 0.0,
+            is_playing_playlist: false,
+            row_height,
+            scroll_offset: 0.0,
+            wrapping: Wrapping::None,
+            row_align: Alignment::Center,
+            sort_direction_icon: String::new(),
+            path_fallbacks,
+        }
+    }
 }
 
 /// Drag payload
@@ -4293,6 +9217,13 @@ fn nav_activate(id: nav_bar::Id) -> Action<Message> {
     Action::Cosmic(cosmic::app::Action::NavBar(id))
 }
 
+/// Text format used when copying the playing track to the clipboard
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ShareFormat {
+    Plain,
+    Markdown,
+}
+
 #[derive(Clone, Copy, Debug)]
 pub enum PlaylistDuplicateDialogAction {
     Add,
@@ -2,7 +2,10 @@
 
 use crate::app::{AppModel, SortBy, SortDirection, ViewMode};
 use crate::constants::{
-    ARTWORK_MEDIUM_SUFFIX, ARTWORK_SMALL_SUFFIX, DEFAULT_CROSSFADE_DURATION_SECS,
+    ARTWORK_MEDIUM_SUFFIX, ARTWORK_SMALL_SUFFIX, DEFAULT_BPM_RADIO_TOLERANCE,
+    DEFAULT_CROSSFADE_DURATION_SECS, DEFAULT_EXPORT_BITRATE_KBPS, DEFAULT_EXPORT_NAME_TEMPLATE,
+    DEFAULT_FADE_OUT_MS, DEFAULT_PREVIOUS_RESTART_THRESHOLD_SECS, DEFAULT_SHARE_MARKDOWN_TEMPLATE,
+    DEFAULT_SHARE_TEMPLATE, DEFAULT_SKIP_SILENCE_THRESHOLD_DB,
 };
 use crate::helpers::artwork_variant_filename;
 use crate::playback_state::RepeatMode;
@@ -13,6 +16,7 @@ use cosmic::{
 };
 use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
+use std::path::PathBuf;
 
 pub const CONFIG_VERSION: u64 = 1;
 
@@ -22,6 +26,152 @@ pub enum TitleSortMode {
     TrackNumber,
 }
 
+/// Audio format for exported tracks, `Copy` keeps the original file
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub enum ExportFormat {
+    #[default]
+    Copy,
+    Mp3,
+    Opus,
+    Aac,
+}
+
+impl ExportFormat {
+    pub const ALL: [ExportFormat; 4] = [
+        ExportFormat::Copy,
+        ExportFormat::Mp3,
+        ExportFormat::Opus,
+        ExportFormat::Aac,
+    ];
+
+    /// File extension for transcoded files, `None` keeps the source extension
+    pub fn extension(self) -> Option<&'static str> {
+        match self {
+            ExportFormat::Copy => None,
+            ExportFormat::Mp3 => Some("mp3"),
+            ExportFormat::Opus => Some("opus"),
+            ExportFormat::Aac => Some("m4a"),
+        }
+    }
+
+    pub fn selected_index(self) -> usize {
+        Self::ALL
+            .iter()
+            .position(|format| *format == self)
+            .unwrap_or(0)
+    }
+}
+
+/// What to do once the last track in the queue has finished
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub enum QueueEndAction {
+    #[default]
+    Stop,
+    Quit,
+    Sleep,
+}
+
+impl QueueEndAction {
+    pub const ALL: [QueueEndAction; 3] = [
+        QueueEndAction::Stop,
+        QueueEndAction::Quit,
+        QueueEndAction::Sleep,
+    ];
+
+    pub fn selected_index(self) -> usize {
+        Self::ALL
+            .iter()
+            .position(|action| *action == self)
+            .unwrap_or(0)
+    }
+}
+
+/// What the play button does when no track is loaded
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub enum EmptyPlayAction {
+    #[default]
+    PlayView,
+    ResumeLastTrack,
+    ShuffleLibrary,
+}
+
+impl EmptyPlayAction {
+    pub const ALL: [EmptyPlayAction; 3] = [
+        EmptyPlayAction::PlayView,
+        EmptyPlayAction::ResumeLastTrack,
+        EmptyPlayAction::ShuffleLibrary,
+    ];
+
+    pub fn selected_index(self) -> usize {
+        Self::ALL
+            .iter()
+            .position(|action| *action == self)
+            .unwrap_or(0)
+    }
+}
+
+/// Shape of the volume envelope for crossfades and play/pause fades
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub enum FadeCurve {
+    Linear,
+    #[default]
+    EqualPower,
+    Logarithmic,
+}
+
+impl FadeCurve {
+    pub const ALL: [FadeCurve; 3] = [
+        FadeCurve::Linear,
+        FadeCurve::EqualPower,
+        FadeCurve::Logarithmic,
+    ];
+
+    pub fn selected_index(self) -> usize {
+        Self::ALL
+            .iter()
+            .position(|curve| *curve == self)
+            .unwrap_or(0)
+    }
+}
+
+/// Most detailed log messages to keep, `ETHEREAL_WAVES_LOG` overrides it
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub enum LogLevel {
+    Error,
+    #[default]
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+impl LogLevel {
+    pub const ALL: [LogLevel; 5] = [
+        LogLevel::Error,
+        LogLevel::Warn,
+        LogLevel::Info,
+        LogLevel::Debug,
+        LogLevel::Trace,
+    ];
+
+    pub fn selected_index(self) -> usize {
+        Self::ALL
+            .iter()
+            .position(|level| *level == self)
+            .unwrap_or(0)
+    }
+
+    pub fn filter(self) -> log::LevelFilter {
+        match self {
+            LogLevel::Error => log::LevelFilter::Error,
+            LogLevel::Warn => log::LevelFilter::Warn,
+            LogLevel::Info => log::LevelFilter::Info,
+            LogLevel::Debug => log::LevelFilter::Debug,
+            LogLevel::Trace => log::LevelFilter::Trace,
+        }
+    }
+}
+
 #[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
 pub enum PlaylistDuplicatePolicy {
     Allow,
@@ -112,10 +262,11 @@ pub enum ListColumn {
     Duration,
     FilePath,
     Genre,
+    Bpm,
 }
 
 impl ListColumn {
-    pub const ALL: [Self; 11] = [
+    pub const ALL: [Self; 12] = [
         Self::TrackTotal,
         Self::TrackNumber,
         Self::Title,
@@ -127,6 +278,7 @@ impl ListColumn {
         Self::Duration,
         Self::FilePath,
         Self::Genre,
+        Self::Bpm,
     ];
 
     pub fn default_order() -> Vec<Self> {
@@ -139,6 +291,7 @@ impl ListColumn {
             Self::Album
                 | Self::AlbumArtist
                 | Self::Artist
+                | Self::Bpm
                 | Self::DiscNumber
                 | Self::DiscTotal
                 | Self::Duration
@@ -155,6 +308,7 @@ impl ListColumn {
             Self::Album => config.list_show_album_column,
             Self::AlbumArtist => config.list_show_album_artist_column,
             Self::Artist => config.list_show_artist_column,
+            Self::Bpm => config.list_show_bpm_column,
             Self::DiscNumber => config.list_show_disc_number_column,
             Self::DiscTotal => config.list_show_disc_total_column,
             Self::Duration => config.list_show_duration_column,
@@ -179,6 +333,7 @@ impl ListColumn {
             Self::Duration => Some(SortBy::Duration),
             Self::Genre => Some(SortBy::Genre),
             Self::FilePath => Some(SortBy::FilePath),
+            Self::Bpm => Some(SortBy::Bpm),
         }
     }
 
@@ -201,7 +356,7 @@ impl ListColumn {
     }
 }
 
-#[derive(Clone, CosmicConfigEntry, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[derive(Clone, CosmicConfigEntry, Debug, Deserialize, PartialEq, Serialize)]
 #[version = 1]
 #[serde(default)]
 pub struct Config {
@@ -212,11 +367,14 @@ pub struct Config {
     pub grid_artwork_size: ArtworkSize,
     #[serde(default)]
     pub regenerate_thumbnails_on_update: bool,
+    pub collapse_tag_whitespace: bool,
+    pub analyze_loudness_on_update: bool,
     pub list_text_wrap: bool,
     pub list_row_align_top: bool,
     pub list_show_album_column: bool,
     pub list_show_album_artist_column: bool,
     pub list_show_artist_column: bool,
+    pub list_show_bpm_column: bool,
     pub list_show_disc_number_column: bool,
     pub list_show_disc_total_column: bool,
     pub list_show_duration_column: bool,
@@ -228,9 +386,85 @@ pub struct Config {
     pub list_column_order: Vec<ListColumn>,
     pub title_sort: TitleSortMode,
     pub sort_case_sensitive: bool,
+    /// Sort "The Beatles" under B when a track has no sort name tag
+    pub sort_ignore_articles: bool,
+    /// Group "Artist feat. Guest" under "Artist" when browsing by artist
+    pub merge_featured_artists: bool,
+    /// Show the A–Z strip beside the list and grid
+    pub show_jump_strip: bool,
+    /// Expand the list of upcoming tracks in the footer
+    pub show_footer_queue: bool,
+    /// Show the file name and folder in list columns whose tags are missing
+    pub path_fallbacks: bool,
+    /// Ask before removing selected tracks from a playlist
+    pub confirm_remove_from_playlist: bool,
+    /// Playlist the quick add shortcut adds the playing track to, created on first use
+    pub quick_add_playlist: Option<u32>,
+    /// Tint the playback controls with a color picked from the current artwork
+    pub tint_from_artwork: bool,
+    #[serde(default)]
+    pub log_level: LogLevel,
     pub playlist_duplicate_policy: PlaylistDuplicatePolicy,
     pub playback_transition_mode: PlaybackTransitionMode,
     pub crossfade_duration_secs: i32,
+    #[serde(default)]
+    pub fade_curve: FadeCurve,
+    /// Consecutive tracks of an album play gaplessly instead of crossfading
+    pub gapless_within_albums: bool,
+    /// Left/right balance in percent, negative values favor the left channel
+    pub balance: i32,
+    /// Output volume from 0.0 to 1.0, clamped again when applied
+    pub volume: f64,
+    pub clear_now_playing_on_stop: bool,
+    pub resume_rewind_secs: u32,
+    /// Seek to the exact position when resuming a saved position or jumping to a chapter.
+    /// Slower than seeking to the nearest keyframe, but VBR files without a seek table can
+    /// otherwise land seconds away. Dragging the seek slider always uses the fast seek.
+    #[serde(default)]
+    pub accurate_seeking: bool,
+    /// Seconds paused before the audio device is released for other apps, 0 keeps it open.
+    /// Resuming afterwards takes a moment while the pipeline is reopened.
+    #[serde(default)]
+    pub release_device_after_secs: u32,
+    /// Play tracks at a similar loudness using their ReplayGain track gain, lowered where the
+    /// track's peak would otherwise clip
+    #[serde(default)]
+    pub replay_gain_normalization: bool,
+    /// Start tracks where their audio begins and move on once only silence is left
+    pub skip_silence: bool,
+    /// Peak level in dB below which the lead-in and tail of a track count as silence
+    pub skip_silence_threshold_db: i32,
+    /// Volume ramps when playback starts and when it pauses or stops, 0 disables them
+    pub fade_in_ms: u32,
+    pub fade_out_ms: u32,
+    pub previous_restarts_track: bool,
+    pub previous_restart_threshold_secs: u32,
+    pub continue_to_next_album: bool,
+    /// BPM either side of the playing track that Play Similar Tempo queues
+    pub bpm_radio_tolerance: u32,
+    pub bpm_radio_same_genre: bool,
+    #[serde(default)]
+    pub queue_end_action: QueueEndAction,
+    pub empty_play_action: EmptyPlayAction,
+    pub follow_current_track: bool,
+    pub remember_scroll_position: bool,
+    pub reduce_background_activity: bool,
+    pub show_launcher_progress: bool,
+    pub show_waveform_preview: bool,
+    pub open_replaces_queue: bool,
+    /// Double-clicking below the list shuffles the tracks shown instead of playing them in order
+    #[serde(default)]
+    pub play_view_shuffles: bool,
+    pub export_name_template: String,
+    /// Templates for copying the playing track to the clipboard, fields as in exports
+    pub share_template: String,
+    pub share_markdown_template: String,
+    #[serde(default)]
+    pub export_format: ExportFormat,
+    pub export_bitrate_kbps: u32,
+    pub min_duration_secs: Option<u64>,
+    /// Glob patterns matched against full paths, matching files and folders aren't scanned
+    pub scan_excludes: Vec<String>,
     pub view_mode: ViewMode,
 }
 
@@ -267,11 +501,14 @@ impl Default for Config {
             grid_group_by: GridGroupBy::Track,
             grid_artwork_size: ArtworkSize::Medium,
             regenerate_thumbnails_on_update: false,
+            collapse_tag_whitespace: false,
+            analyze_loudness_on_update: false,
             list_text_wrap: true,
             list_row_align_top: false,
             list_show_album_column: true,
             list_show_album_artist_column: false,
             list_show_artist_column: true,
+            list_show_bpm_column: false,
             list_show_disc_number_column: false,
             list_show_disc_total_column: false,
             list_show_genre_column: false,
@@ -283,9 +520,52 @@ impl Default for Config {
             list_column_order: ListColumn::default_order(),
             title_sort: TitleSortMode::Alphabetical,
             sort_case_sensitive: false,
+            sort_ignore_articles: true,
+            merge_featured_artists: false,
+            show_jump_strip: true,
+            show_footer_queue: true,
+            path_fallbacks: false,
+            confirm_remove_from_playlist: true,
+            quick_add_playlist: None,
+            tint_from_artwork: false,
+            log_level: LogLevel::Warn,
             playlist_duplicate_policy: PlaylistDuplicatePolicy::Ask,
             playback_transition_mode: PlaybackTransitionMode::Gapless,
             crossfade_duration_secs: DEFAULT_CROSSFADE_DURATION_SECS,
+            fade_curve: FadeCurve::EqualPower,
+            gapless_within_albums: false,
+            balance: 0,
+            volume: 1.0,
+            clear_now_playing_on_stop: false,
+            resume_rewind_secs: 0,
+            accurate_seeking: false,
+            release_device_after_secs: 0,
+            replay_gain_normalization: false,
+            skip_silence: false,
+            skip_silence_threshold_db: DEFAULT_SKIP_SILENCE_THRESHOLD_DB,
+            fade_in_ms: 0,
+            fade_out_ms: DEFAULT_FADE_OUT_MS,
+            previous_restarts_track: false,
+            previous_restart_threshold_secs: DEFAULT_PREVIOUS_RESTART_THRESHOLD_SECS,
+            continue_to_next_album: false,
+            bpm_radio_tolerance: DEFAULT_BPM_RADIO_TOLERANCE,
+            bpm_radio_same_genre: false,
+            queue_end_action: QueueEndAction::Stop,
+            empty_play_action: EmptyPlayAction::PlayView,
+            follow_current_track: false,
+            remember_scroll_position: true,
+            reduce_background_activity: false,
+            show_launcher_progress: true,
+            show_waveform_preview: false,
+            open_replaces_queue: true,
+            play_view_shuffles: false,
+            export_name_template: DEFAULT_EXPORT_NAME_TEMPLATE.to_string(),
+            share_template: DEFAULT_SHARE_TEMPLATE.to_string(),
+            share_markdown_template: DEFAULT_SHARE_MARKDOWN_TEMPLATE.to_string(),
+            export_format: ExportFormat::Copy,
+            export_bitrate_kbps: DEFAULT_EXPORT_BITRATE_KBPS,
+            min_duration_secs: None,
+            scan_excludes: Vec::new(),
             view_mode: ViewMode::List,
         }
     }
@@ -294,6 +574,7 @@ impl Default for Config {
 #[derive(Clone, CosmicConfigEntry, Debug, Deserialize, PartialEq, Serialize)]
 #[serde(default)]
 pub struct State {
+    pub footer_hidden: bool,
     pub muted: bool,
     pub playlist_nav_order: Vec<u32>,
     pub repeat: bool,
@@ -303,14 +584,16 @@ pub struct State {
     pub grid_size_multiplier: Option<f32>,
     pub sort_by: SortBy,
     pub sort_direction: SortDirection,
-    pub volume: i32,
     pub window_height: f32,
     pub window_width: f32,
+    /// Path and position in seconds of the track playing when the app last quit
+    pub last_track: Option<(PathBuf, f32)>,
 }
 
 impl Default for State {
     fn default() -> Self {
         Self {
+            footer_hidden: false,
             muted: false,
             playlist_nav_order: Vec::new(),
             repeat: false,
@@ -320,9 +603,9 @@ impl Default for State {
             grid_size_multiplier: None,
             sort_by: SortBy::Artist,
             sort_direction: SortDirection::Ascending,
-            volume: 100,
             window_height: 1024.0,
             window_width: 768.0,
+            last_track: None,
         }
     }
 }
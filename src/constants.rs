@@ -8,7 +8,15 @@ pub const LIST_MAX_SIZE_MULTIPLIER: f32 = 30.0;
 pub const GRID_MIN_SIZE_MULTIPLIER: f32 = 8.0;
 pub const GRID_MAX_SIZE_MULTIPLIER: f32 = 22.0;
 pub const ZOOM_STEP: f32 = 2.0;
-pub const FOOTER_CONDENSED_BREAKPOINT: f32 = 700.0;
+// The full footer splits its width into three columns and the right one needs room for the
+// icon buttons plus the volume slider, below this the stacked layout is used
+pub const FOOTER_CONDENSED_BREAKPOINT: f32 = 860.0;
+pub const FOOTER_VOLUME_SLIDER_WIDTH: f32 = 150.0;
+// Approximate nav bar width, the footer only gets the space beside it
+pub const NAV_BAR_WIDTH: f32 = 280.0;
+pub const MIN_WINDOW_WIDTH: f32 = 360.0;
+// Header bar, the stacked footer and a few rows of content
+pub const MIN_WINDOW_HEIGHT: f32 = 320.0;
 pub const COMPACT_COLUMN_WIDTH: f32 = 96.0;
 pub const DURATION_COLUMN_WIDTH: f32 = 104.0;
 pub const GRID_ARTWORK_SCALE: f32 = 12.0;
@@ -23,29 +31,102 @@ pub const GRID_INFO_HEIGHT: f32 = 18.0;
 pub const GRID_CARD_CONTENT_SPACING: f32 = 4.0;
 pub const GRID_STATUS_ICON_SIZE: u16 = 14;
 pub const GRID_STATUS_ICON_SLOT: f32 = 16.0;
+pub const SEEK_SLIDER_HEIGHT: f32 = 24.0;
+pub const WAVEFORM_HEIGHT: f32 = 32.0;
+pub const WAVEFORM_BAR_WIDTH: f32 = 2.0;
+// The footer queue lists this many upcoming tracks and shows a few of them at a time
+pub const FOOTER_QUEUE_LENGTH: usize = 10;
+pub const FOOTER_QUEUE_VISIBLE_ROWS: f32 = 3.0;
+pub const FOOTER_QUEUE_ROW_HEIGHT: f32 = 24.0;
+// Missing files listed in the library update preview scroll past this height
+pub const SCAN_PREVIEW_LIST_HEIGHT: f32 = 160.0;
 
 /// UI Display Constants
+pub const WAVEFORM_BUCKETS: usize = 64;
 pub const TRACK_INFO_LIST_TOTAL: usize = 100;
+// Longer tag values are cut in rows and the footer, Track Info shows them in full
+pub const MAX_DISPLAY_CHARS: usize = 256;
+pub const DEBUG_LOG_CAPACITY: usize = 200;
+pub const LOG_BUFFER_CAPACITY: usize = 1000;
 pub const SEARCH_INPUT_WIDTH: f32 = 240.0;
+// The command palette lists at most this many entries, and this many of each library kind
+pub const COMMAND_PALETTE_MAX_RESULTS: usize = 12;
+pub const COMMAND_PALETTE_LIBRARY_RESULTS: usize = 5;
 
 /// File System Constants
 pub const LIBRARY_FILENAME: &str = "library.json";
+// Read by `--stream -`, the player opens it like any other file
+pub const STDIN_PATH: &str = "/dev/stdin";
+// Mount table of this process, for flagging read-only and network library folders
+pub const MOUNT_INFO_PATH: &str = "/proc/self/mountinfo";
+pub const NETWORK_FILESYSTEMS: &[&str] = &[
+    "9p",
+    "afs",
+    "ceph",
+    "cifs",
+    "davfs",
+    "fuse.gvfsd-fuse",
+    "fuse.rclone",
+    "fuse.sshfs",
+    "glusterfs",
+    "ncpfs",
+    "nfs",
+    "nfs4",
+    "smb3",
+    "smbfs",
+];
+// Bump when library.json changes shape and add a step to `Library::migrate`
+pub const LIBRARY_VERSION: u32 = 3;
 pub const PLAYLISTS_DIR: &str = "playlists";
 pub const ARTWORK_DIR: &str = "artwork";
+pub const WAVEFORM_DIR: &str = "waveforms";
+// Files the last scan couldn't read, shown in the scan report
+pub const SCAN_REPORT_FILENAME: &str = "scan_report.json";
 pub const ARTWORK_MEDIUM_SIZE: u32 = 256;
 pub const ARTWORK_SMALL_SIZE: u32 = 128;
 pub const ARTWORK_MEDIUM_SUFFIX: &str = "medium";
 pub const ARTWORK_SMALL_SUFFIX: &str = "small";
 pub const MIN_FILE_SIZE: u64 = 4096;
+pub const MAX_MIN_DURATION_SECS: u32 = 120;
+pub const DEFAULT_EXPORT_NAME_TEMPLATE: &str = "{track:02} - {title}";
+pub const DEFAULT_SHARE_TEMPLATE: &str = "🎵 Now playing: {artist} – {title} ({album})";
+pub const DEFAULT_SHARE_MARKDOWN_TEMPLATE: &str =
+    "🎵 Now playing: **{title}** by {artist} from *{album}*";
+pub const EXPORT_BITRATES_KBPS: &[u32] = &[128, 192, 256, 320];
+pub const DEFAULT_EXPORT_BITRATE_KBPS: u32 = 192;
 
 /// Timing Constants
 pub const DOUBLE_CLICK_THRESHOLD_MS: u64 = 400;
 pub const TICK_INTERVAL_MS: u64 = 100;
+pub const FADE_STEP_MS: u64 = 10;
 pub const PROGRESS_UPDATE_INTERVAL_MS: u64 = 200;
+pub const LAUNCHER_UPDATE_INTERVAL_MS: u64 = 1000;
 pub const LIBRARY_UPDATE_INTERVAL_SECS: u64 = 10;
+// The footer confirms a finished scan for this long before the update row collapses
+pub const SCAN_COMPLETE_LINGER_SECS: u64 = 4;
+// Large imports also send a partial update, which the app saves, after this many tracks
+pub const LIBRARY_AUTOSAVE_TRACKS: usize = 500;
+// Messages a library task can queue before it waits for the app, progress updates are skipped
+pub const LIBRARY_PROGRESS_CHANNEL_CAPACITY: usize = 64;
 pub const GSTREAMER_TIMEOUT_SECS: u64 = 5;
 pub const IMAGE_CACHE_TTL_SECS: u64 = 300;
 pub const IMAGE_CACHE_SWEEP_SECS: u64 = 30;
+pub const IMAGE_LOAD_CONCURRENCY: usize = 4;
+pub const IMAGE_DECODE_SCALE: f32 = 2.0;
+pub const IMAGE_DECODE_SIZE_STEP: u32 = 64;
+// Reads failing with a transient error are retried after 0.5s, 1s and 2s
+pub const IMAGE_LOAD_RETRIES: u32 = 3;
+pub const IMAGE_LOAD_RETRY_DELAY_MS: u64 = 500;
+// Artwork modified this recently that doesn't decode is likely still being written and is retried
+pub const IMAGE_RECENTLY_MODIFIED_SECS: u64 = 10;
+// Rows of grid covers loaded ahead of the viewport in the direction of scrolling
+pub const GRID_PREFETCH_ROWS: usize = 3;
+// Artwork is shrunk to this many pixels a side before picking its accent color
+pub const ACCENT_SAMPLE_SIZE: u32 = 32;
+
+/// Environment
+pub const DEBUG_ENV_VAR: &str = "ETHEREAL_WAVES_DEBUG";
+pub const LOG_ENV_VAR: &str = "ETHEREAL_WAVES_LOG";
 
 /// Audio File Extensions
 pub const VALID_AUDIO_EXTENSIONS: &[&str] = &["flac", "m4a", "mp3", "ogg", "opus", "wav"];
@@ -53,6 +134,11 @@ pub const VALID_AUDIO_EXTENSIONS: &[&str] = &["flac", "m4a", "mp3", "ogg", "opus
 /// Widget IDs
 pub const NEW_PLAYLIST_INPUT_ID: &str = "new_playlist_input_id";
 pub const RENAME_PLAYLIST_INPUT_ID: &str = "rename_playlist_input_id";
+pub const GO_TO_TRACK_INPUT_ID: &str = "go_to_track_input_id";
+pub const SAVE_QUEUE_INPUT_ID: &str = "save_queue_input_id";
+pub const EXPORT_TEMPLATE_INPUT_ID: &str = "export_template_input_id";
+pub const LIBRARY_PATH_INPUT_ID: &str = "library_path_input_id";
+pub const COMMAND_PALETTE_INPUT_ID: &str = "command_palette_input_id";
 pub const SEARCH_INPUT_ID: &str = "Text Search";
 pub const MENU_WIDGET_ID: &str = "responsive_menu";
 
@@ -64,3 +150,41 @@ pub const LIBRARY_TRACK_DROP_PREFIX: &str = "library:";
 pub const DEFAULT_CROSSFADE_DURATION_SECS: i32 = 5;
 pub const MIN_CROSSFADE_DURATION_SECS: i32 = 1;
 pub const MAX_CROSSFADE_DURATION_SECS: i32 = 30;
+pub const MAX_RESUME_REWIND_SECS: u32 = 30;
+pub const MAX_RELEASE_DEVICE_AFTER_SECS: u32 = 600;
+// Previous restarts the track once playback is at least this far in, 3s like most car stereos
+pub const DEFAULT_PREVIOUS_RESTART_THRESHOLD_SECS: u32 = 3;
+pub const MAX_PREVIOUS_RESTART_THRESHOLD_SECS: u32 = 15;
+// A second press of Previous within this window always skips back
+pub const PREVIOUS_DOUBLE_PRESS_MS: u64 = 1000;
+// Shuffling the library samples this many tracks from larger ones so the queue stays quick
+// to build
+pub const SHUFFLE_LIBRARY_MAX_TRACKS: usize = 5000;
+// Play Similar Tempo queues tracks within a few BPM of the playing one, enough for a workout
+pub const DEFAULT_BPM_RADIO_TOLERANCE: u32 = 5;
+pub const MAX_BPM_RADIO_TOLERANCE: u32 = 20;
+pub const BPM_RADIO_MAX_TRACKS: usize = 100;
+// Time to cancel quitting or suspending after the queue ends
+pub const QUEUE_END_COUNTDOWN_SECS: u64 = 30;
+pub const MIN_MANUAL_GAIN_DB: f32 = -12.0;
+pub const MAX_MANUAL_GAIN_DB: f32 = 12.0;
+pub const MANUAL_GAIN_STEP_DB: f32 = 0.5;
+// Enough headroom for the maximum manual gain (+12 dB is roughly 3.98x)
+pub const MAX_PLAYER_VOLUME: f64 = 4.0;
+pub const MAX_BALANCE_PERCENT: i32 = 100;
+// Played at when a saved volume can't be read, loud enough to notice without being startling
+pub const FALLBACK_VOLUME: f64 = 0.5;
+// Volume changes are saved once they settle, dragging the slider shouldn't write every step
+pub const VOLUME_SAVE_DELAY_MS: u64 = 500;
+// Short fades around play and pause avoid clicks, long ones would feel laggy
+pub const DEFAULT_FADE_OUT_MS: u32 = 100;
+pub const MAX_FADE_MS: u32 = 2000;
+// Logarithmic fades ramp in dB from this level, below it the track is effectively silent
+pub const FADE_CURVE_FLOOR_DB: f64 = -60.0;
+// Lead-ins and tails quieter than the threshold are only skipped when they last this long, so
+// short pauses and fade-outs play as mastered
+pub const DEFAULT_SKIP_SILENCE_THRESHOLD_DB: i32 = -60;
+pub const MIN_SKIP_SILENCE_THRESHOLD_DB: i32 = -80;
+pub const MAX_SKIP_SILENCE_THRESHOLD_DB: i32 = -40;
+pub const SKIP_SILENCE_MIN_SECS: f32 = 2.0;
+pub const SILENCE_DETECTION_INTERVAL_MS: u64 = 50;
@@ -0,0 +1,293 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// CD frames per second, the unit of the last field of an INDEX time
+const FRAMES_PER_SECOND: u64 = 75;
+
+/// Album-wide fields of a cue sheet and the tracks it places in one audio file
+#[derive(Debug, Default)]
+pub struct CueSheet {
+    pub title: Option<String>,
+    pub performer: Option<String>,
+    pub genre: Option<String>,
+    pub tracks: Vec<CueTrack>,
+}
+
+#[derive(Debug)]
+pub struct CueTrack {
+    pub number: u32,
+    pub title: Option<String>,
+    pub performer: Option<String>,
+    /// Start of INDEX 01 in milliseconds
+    pub start_ms: u64,
+}
+
+/// Look for `album.cue` or `album.flac.cue` beside `file`. `None` when there isn't one or it
+/// can't be used, the file is then added as a single track.
+pub fn find_for(file: &Path) -> Option<CueSheet> {
+    let mut with_suffix = file.as_os_str().to_owned();
+    with_suffix.push(".cue");
+
+    [file.with_extension("cue"), PathBuf::from(with_suffix)]
+        .into_iter()
+        .filter(|candidate| candidate.is_file())
+        .find_map(|candidate| match fs::read(&candidate) {
+            Ok(bytes) => {
+                let sheet = parse(&decode(bytes), file);
+                if sheet.is_none() {
+                    log::warn!("Ignoring unusable cue sheet {:?}", candidate);
+                }
+                sheet
+            }
+            Err(err) => {
+                log::warn!("Failed to read cue sheet {:?}: {}", candidate, err);
+                None
+            }
+        })
+}
+
+/// Cue sheets are often written by Windows rippers in a legacy code page, bytes that aren't
+/// UTF-8 are read as Latin-1 so the titles are at least mostly right
+fn decode(bytes: Vec<u8>) -> String {
+    let text = String::from_utf8(bytes)
+        .unwrap_or_else(|err| err.into_bytes().into_iter().map(char::from).collect());
+
+    text.trim_start_matches('\u{feff}').to_string()
+}
+
+/// Parse the tracks belonging to `file`. A sheet naming several files only contributes the
+/// tracks under the entry with the same file stem, a sheet naming one file is trusted even
+/// when the name differs since rips are often converted after the sheet was written.
+fn parse(content: &str, file: &Path) -> Option<CueSheet> {
+    let stem = file.file_stem()?.to_string_lossy().to_lowercase();
+    let file_count = content
+        .lines()
+        .filter(|line| command(line.trim()).0 == "FILE")
+        .count();
+
+    let mut sheet = CueSheet::default();
+    // Album fields come before the first FILE, later ones belong to tracks
+    let mut seen_file = false;
+    let mut in_file = false;
+    // The track being read and whether its INDEX 01 has been seen
+    let mut current: Option<(CueTrack, bool)> = None;
+    // Tracks need INDEX 01, one without it makes the whole sheet suspect
+    let mut missing_index = false;
+
+    for line in content.lines() {
+        let (keyword, rest) = command(line.trim());
+
+        match keyword.as_str() {
+            "FILE" => {
+                finish_track(&mut current, &mut sheet, &mut missing_index);
+                // The file type follows the name, e.g. `FILE "Album.wav" WAVE`
+                let name = if rest.starts_with('"') {
+                    quoted(rest)
+                } else {
+                    rest.rsplit_once(char::is_whitespace)
+                        .map_or(rest, |(name, _)| name)
+                        .to_string()
+                };
+                let named_stem = Path::new(&name)
+                    .file_stem()
+                    .map(|stem| stem.to_string_lossy().to_lowercase());
+                seen_file = true;
+                in_file = file_count == 1 || named_stem.as_deref() == Some(stem.as_str());
+            }
+            "TRACK" if in_file => {
+                finish_track(&mut current, &mut sheet, &mut missing_index);
+                let mut parts = rest.split_whitespace();
+                let number = parts.next().and_then(|number| number.parse().ok())?;
+                // Data tracks on mixed mode discs have nothing to play
+                if parts
+                    .next()
+                    .is_some_and(|kind| kind.eq_ignore_ascii_case("AUDIO"))
+                {
+                    let track = CueTrack {
+                        number,
+                        title: None,
+                        performer: None,
+                        start_ms: 0,
+                    };
+                    current = Some((track, false));
+                }
+            }
+            "TITLE" => match &mut current {
+                Some((track, _)) => track.title = non_empty(quoted(rest)),
+                None if !seen_file => sheet.title = non_empty(quoted(rest)),
+                None => {}
+            },
+            "PERFORMER" => match &mut current {
+                Some((track, _)) => track.performer = non_empty(quoted(rest)),
+                None if !seen_file => sheet.performer = non_empty(quoted(rest)),
+                None => {}
+            },
+            "REM" if !seen_file => {
+                let (field, value) = command(rest);
+                if field == "GENRE" {
+                    sheet.genre = non_empty(quoted(value));
+                }
+            }
+            "INDEX" => {
+                if let Some((track, has_index)) = &mut current {
+                    let mut parts = rest.split_whitespace();
+                    if parts.next().and_then(|index| index.parse::<u32>().ok()) == Some(1) {
+                        track.start_ms = parts.next().and_then(parse_time)?;
+                        *has_index = true;
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    finish_track(&mut current, &mut sheet, &mut missing_index);
+
+    let ordered = sheet
+        .tracks
+        .windows(2)
+        .all(|pair| pair[0].start_ms < pair[1].start_ms);
+
+    // A single track sheet wouldn't split anything
+    if missing_index || !ordered || sheet.tracks.len() < 2 {
+        return None;
+    }
+
+    Some(sheet)
+}
+
+fn finish_track(
+    current: &mut Option<(CueTrack, bool)>,
+    sheet: &mut CueSheet,
+    missing_index: &mut bool,
+) {
+    match current.take() {
+        Some((track, true)) => sheet.tracks.push(track),
+        Some((_, false)) => *missing_index = true,
+        None => {}
+    }
+}
+
+/// Split a line into its upper-cased command and the rest
+fn command(line: &str) -> (String, &str) {
+    let (command, rest) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+    (command.to_ascii_uppercase(), rest.trim())
+}
+
+/// The first quoted string of a value, or the whole value when it isn't quoted
+fn quoted(value: &str) -> String {
+    match value.strip_prefix('"') {
+        Some(rest) => rest.split('"').next().unwrap_or_default().to_string(),
+        None => value.to_string(),
+    }
+}
+
+fn non_empty(value: String) -> Option<String> {
+    let value = value.trim();
+    (!value.is_empty()).then(|| value.to_string())
+}
+
+/// `mm:ss:ff` to milliseconds, minutes may go past 99 on long rips
+fn parse_time(time: &str) -> Option<u64> {
+    let mut parts = time.split(':').map(|part| part.parse::<u64>().ok());
+    let (minutes, seconds, frames) = (parts.next()??, parts.next()??, parts.next()??);
+
+    if parts.next().is_some() || seconds >= 60 || frames >= FRAMES_PER_SECOND {
+        return None;
+    }
+
+    Some((minutes * 60 + seconds) * 1000 + frames * 1000 / FRAMES_PER_SECOND)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALBUM: &str = r#"REM GENRE "Hard Bop"
+PERFORMER "John Coltrane"
+TITLE "Blue Train"
+FILE "Blue Train.flac" WAVE
+  TRACK 01 AUDIO
+    TITLE "Blue Train"
+    INDEX 01 00:00:00
+  TRACK 02 AUDIO
+    TITLE Moment's Notice
+    PERFORMER "John Coltrane Sextet"
+    INDEX 00 10:40:00
+    INDEX 01 10:43:37
+"#;
+
+    #[test]
+    fn parse_reads_album_and_track_fields() {
+        let sheet = parse(ALBUM, Path::new("/music/Blue Train.flac")).unwrap();
+
+        assert_eq!(sheet.title.as_deref(), Some("Blue Train"));
+        assert_eq!(sheet.performer.as_deref(), Some("John Coltrane"));
+        assert_eq!(sheet.genre.as_deref(), Some("Hard Bop"));
+        assert_eq!(sheet.tracks.len(), 2);
+        assert_eq!(sheet.tracks[1].number, 2);
+        assert_eq!(sheet.tracks[1].title.as_deref(), Some("Moment's Notice"));
+        assert_eq!(
+            sheet.tracks[1].performer.as_deref(),
+            Some("John Coltrane Sextet")
+        );
+    }
+
+    #[test]
+    fn parse_starts_tracks_at_index_01() {
+        let sheet = parse(ALBUM, Path::new("/music/Blue Train.flac")).unwrap();
+
+        assert_eq!(sheet.tracks[0].start_ms, 0);
+        // 10:43 and 37 of 75 frames, the pregap of INDEX 00 is skipped
+        assert_eq!(sheet.tracks[1].start_ms, 643_493);
+    }
+
+    #[test]
+    fn parse_time_checks_fields() {
+        assert_eq!(parse_time("120:00:74"), Some(7_200_986));
+        assert_eq!(parse_time("01:60:00"), None);
+        assert_eq!(parse_time("01:00:75"), None);
+        assert_eq!(parse_time("01:00"), None);
+    }
+
+    #[test]
+    fn parse_multiple_files_keeps_matching_tracks() {
+        let content = r#"FILE "Disc 1.wav" WAVE
+  TRACK 01 AUDIO
+    INDEX 01 00:00:00
+  TRACK 02 AUDIO
+    INDEX 01 04:00:00
+FILE "Disc 2.wav" WAVE
+  TRACK 03 AUDIO
+    TITLE "Side B"
+    INDEX 01 00:00:00
+  TRACK 04 AUDIO
+    INDEX 01 05:00:00
+"#;
+
+        let sheet = parse(content, Path::new("/music/disc 2.flac")).unwrap();
+
+        let numbers: Vec<u32> = sheet.tracks.iter().map(|track| track.number).collect();
+        assert_eq!(numbers, [3, 4]);
+        assert_eq!(sheet.tracks[0].title.as_deref(), Some("Side B"));
+    }
+
+    #[test]
+    fn parse_rejects_track_without_index() {
+        let content = r#"FILE "Album.flac" WAVE
+  TRACK 01 AUDIO
+    INDEX 01 00:00:00
+  TRACK 02 AUDIO
+    TITLE "No Index"
+"#;
+
+        assert!(parse(content, Path::new("/music/Album.flac")).is_none());
+    }
+
+    #[test]
+    fn quoted_takes_first_string() {
+        assert_eq!(quoted(r#""Side A" extra"#), "Side A");
+        assert_eq!(quoted("Unquoted Title"), "Unquoted Title");
+    }
+}
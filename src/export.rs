@@ -0,0 +1,391 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use crate::config::ExportFormat;
+use crate::library::MediaMetaData;
+use gst::prelude::*;
+use gstreamer as gst;
+use gstreamer_pbutils as pbutils;
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+use url::Url;
+
+/// Characters that are not allowed in file names on common filesystems
+const INVALID_FILENAME_CHARS: &[char] = &['/', '\\', ':', '*', '?', '"', '<', '>', '|'];
+
+/// Expand a naming template such as `{album_artist}/{album}/{track:02} - {title}` into a
+/// relative path for `source`, keeping the source extension.
+///
+/// Supported fields are `title`, `artist`, `album`, `album_artist`, `genre`, `track`,
+/// `track_count`, `disc` and `disc_count`. Numeric fields accept a zero padded width, e.g.
+/// `{track:02}`. Unknown fields are kept as written so mistakes show up in the preview.
+pub fn expand_template(template: &str, metadata: &MediaMetaData, source: &Path) -> PathBuf {
+    let expanded = fill_template(template, metadata, source, sanitize_component);
+
+    // Literal slashes in the template create folders, empty segments are dropped
+    let mut path: PathBuf = expanded
+        .split('/')
+        .map(sanitize_component)
+        .filter(|component| !component.is_empty())
+        .collect();
+
+    if path.as_os_str().is_empty() {
+        path.push(fallback_title(source));
+    }
+
+    if let Some(extension) = source.extension() {
+        let mut file_name = path.file_name().unwrap_or_default().to_os_string();
+        file_name.push(".");
+        file_name.push(extension);
+        path.set_file_name(file_name);
+    }
+
+    path
+}
+
+/// Replace the fields of `template` with the tags of `source`, passing each value through
+/// `escape`. Takes the same fields as `expand_template`, also used for sharing now playing.
+pub fn fill_template(
+    template: &str,
+    metadata: &MediaMetaData,
+    source: &Path,
+    escape: impl Fn(&str) -> String,
+) -> String {
+    let mut expanded = String::new();
+    let mut rest = template;
+
+    while let Some(start) = rest.find('{') {
+        expanded.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+
+        let Some(end) = after.find('}') else {
+            expanded.push_str(&rest[start..]);
+            rest = "";
+            break;
+        };
+
+        let placeholder = &after[..end];
+        match field_value(placeholder, metadata, source) {
+            Some(value) => expanded.push_str(&escape(&value)),
+            None => {
+                expanded.push('{');
+                expanded.push_str(placeholder);
+                expanded.push('}');
+            }
+        }
+
+        rest = &after[end + 1..];
+    }
+    expanded.push_str(rest);
+
+    expanded
+}
+
+/// Where `source` ends up when exported to `destination`, with the extension of the chosen
+/// format when transcoding
+pub fn export_target(
+    destination: &Path,
+    template: &str,
+    metadata: &MediaMetaData,
+    source: &Path,
+    format: ExportFormat,
+) -> PathBuf {
+    let mut target = destination.join(expand_template(template, metadata, source));
+
+    if let Some(extension) = format.extension() {
+        target.set_extension(extension);
+    }
+
+    target
+}
+
+/// Copy `source` to `destination`, creating folders as needed. Existing files are never
+/// overwritten, a numbered suffix is added instead. Returns the path that was written.
+pub fn copy_track(source: &Path, destination: &Path) -> io::Result<PathBuf> {
+    if let Some(parent) = destination.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let destination = unique_path(destination);
+    fs::copy(source, &destination)?;
+
+    Ok(destination)
+}
+
+fn field_value(placeholder: &str, metadata: &MediaMetaData, source: &Path) -> Option<String> {
+    let (name, width) = match placeholder.split_once(':') {
+        Some((name, width)) => (name.trim(), width.trim().parse::<usize>().ok()),
+        None => (placeholder.trim(), None),
+    };
+
+    let number = |value: Option<u32>| {
+        let value = value.unwrap_or(0);
+        match width {
+            Some(width) => format!("{value:0width$}"),
+            None => value.to_string(),
+        }
+    };
+    let text = |value: Option<&String>, fallback: &str| {
+        value
+            .map(|value| value.trim())
+            .filter(|value| !value.is_empty())
+            .unwrap_or(fallback)
+            .to_string()
+    };
+
+    let value = match name {
+        "title" => match metadata.title.as_ref() {
+            Some(title) if !title.trim().is_empty() => title.trim().to_string(),
+            _ => fallback_title(source),
+        },
+        "artist" => text(metadata.artist.as_ref(), "Unknown Artist"),
+        "album" => text(metadata.album.as_ref(), "Unknown Album"),
+        "album_artist" => metadata.display_album_artist().to_string(),
+        "genre" => text(metadata.genres.first(), "Unknown Genre"),
+        "track" => number(metadata.track_number),
+        "track_count" => number(metadata.track_count),
+        "disc" => number(metadata.album_disc_number),
+        "disc_count" => number(metadata.album_disc_count),
+        _ => return None,
+    };
+
+    Some(value)
+}
+
+fn fallback_title(source: &Path) -> String {
+    source
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_else(|| "Untitled".to_string())
+}
+
+// Replace characters that are invalid in file names and trim what some systems reject
+fn sanitize_component(value: &str) -> String {
+    let replaced: String = value
+        .chars()
+        .map(|c| {
+            if INVALID_FILENAME_CHARS.contains(&c) || c.is_control() {
+                '_'
+            } else {
+                c
+            }
+        })
+        .collect();
+
+    replaced
+        .trim_matches(|c: char| c == '.' || c.is_whitespace())
+        .to_string()
+}
+
+/// Backslash the characters Markdown would treat as formatting
+pub fn escape_markdown(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        if "\\`*_[]<>|~".contains(c) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+fn unique_path(path: &Path) -> PathBuf {
+    if !path.exists() {
+        return path.to_path_buf();
+    }
+
+    let stem = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let extension = path
+        .extension()
+        .map(|extension| format!(".{}", extension.to_string_lossy()))
+        .unwrap_or_default();
+
+    (2..)
+        .map(|n| path.with_file_name(format!("{stem} ({n}){extension}")))
+        .find(|candidate| !candidate.exists())
+        .unwrap_or_else(|| path.to_path_buf())
+}
+
+/// Whether GStreamer has an encoder and muxer for `format`. Encoders often live in optional
+/// plugin packages, so this is checked before an export starts.
+pub fn encoder_available(format: ExportFormat) -> bool {
+    let Some((container_caps, audio_caps)) = format_caps(format) else {
+        return true;
+    };
+
+    if gst::init().is_err() {
+        return false;
+    }
+
+    let can_produce = |factory_type, caps: &gst::Caps| {
+        gst::ElementFactory::factories_with_type(factory_type, gst::Rank::MARGINAL)
+            .iter()
+            .any(|factory| factory.can_src_any_caps(caps))
+    };
+
+    can_produce(gst::ElementFactoryType::AUDIO_ENCODER, &audio_caps)
+        && can_produce(gst::ElementFactoryType::MUXER, &container_caps)
+}
+
+/// Decode `source` and encode it to `format` at `destination`, creating folders as needed.
+/// Existing files are never overwritten, a numbered suffix is added instead. A partially
+/// written file is removed on failure. Returns the path that was written.
+pub fn transcode_track(
+    source: &Path,
+    destination: &Path,
+    format: ExportFormat,
+    bitrate_kbps: u32,
+) -> Result<PathBuf, String> {
+    let Some((container_caps, audio_caps)) = format_caps(format) else {
+        return copy_track(source, destination).map_err(|err| err.to_string());
+    };
+
+    gst::init().map_err(|err| format!("failed to initialize GStreamer: {err}"))?;
+
+    if let Some(parent) = destination.parent() {
+        fs::create_dir_all(parent).map_err(|err| err.to_string())?;
+    }
+    let destination = unique_path(destination);
+
+    let uri = Url::from_file_path(source).map_err(|_| "Failed to create URI".to_string())?;
+    let profile = pbutils::EncodingContainerProfile::builder(&container_caps)
+        .add_profile(pbutils::EncodingAudioProfile::builder(&audio_caps).build())
+        .build();
+
+    let make = |name: &str| {
+        gst::ElementFactory::make(name)
+            .build()
+            .map_err(|_| format!("missing GStreamer element {name}"))
+    };
+
+    let pipeline = gst::Pipeline::new();
+    let decode = make("uridecodebin")?;
+    decode.set_property("uri", uri.as_str());
+    let convert = make("audioconvert")?;
+    let resample = make("audioresample")?;
+    let encode = make("encodebin")?;
+    encode.set_property("profile", &profile);
+    let sink = make("filesink")?;
+    sink.set_property("location", destination.to_string_lossy().to_string());
+
+    // Bitrate isn't part of the profile, set it on the encoder encodebin picks
+    if let Some(bin) = encode.downcast_ref::<gst::Bin>() {
+        bin.connect_deep_element_added(move |_, _, element| {
+            let is_encoder = element
+                .factory()
+                .is_some_and(|factory| factory.has_type(gst::ElementFactoryType::AUDIO_ENCODER));
+            if !is_encoder || !element.has_property("bitrate") {
+                return;
+            }
+
+            // LAME takes kbit/s and ignores the bitrate unless targeting it, the rest take bit/s
+            let is_lame = element
+                .factory()
+                .is_some_and(|factory| factory.name().starts_with("lame"));
+            if is_lame {
+                element.set_property_from_str("target", "bitrate");
+                element.set_property_from_str("bitrate", &bitrate_kbps.to_string());
+            } else {
+                element.set_property_from_str("bitrate", &(bitrate_kbps * 1000).to_string());
+            }
+        });
+    }
+
+    let link = || -> Result<(), String> {
+        pipeline
+            .add_many([&decode, &convert, &resample, &encode, &sink])
+            .map_err(|err| err.to_string())?;
+        gst::Element::link_many([&convert, &resample]).map_err(|err| err.to_string())?;
+
+        let encode_pad = encode
+            .request_pad_simple("audio_%u")
+            .ok_or_else(|| format!("no encoder for {:?} is installed", format))?;
+        resample
+            .static_pad("src")
+            .ok_or_else(|| "audioresample has no src pad".to_string())?
+            .link(&encode_pad)
+            .map_err(|err| err.to_string())?;
+        encode.link(&sink).map_err(|err| err.to_string())?;
+
+        let convert_sink = convert
+            .static_pad("sink")
+            .ok_or_else(|| "audioconvert has no sink pad".to_string())?;
+        decode.connect_pad_added(move |_, pad| {
+            let is_audio = pad
+                .current_caps()
+                .and_then(|caps| {
+                    caps.structure(0)
+                        .map(|structure| structure.name().starts_with("audio/"))
+                })
+                .unwrap_or(false);
+            if is_audio && !convert_sink.is_linked() {
+                let _ = pad.link(&convert_sink);
+            }
+        });
+
+        Ok(())
+    };
+
+    let result = link().and_then(|_| run_to_end(&pipeline));
+    let _ = pipeline.set_state(gst::State::Null);
+
+    match result {
+        Ok(()) => Ok(destination),
+        Err(err) => {
+            let _ = fs::remove_file(&destination);
+            Err(err)
+        }
+    }
+}
+
+/// Container and audio caps for a transcode target, `None` when copying
+fn format_caps(format: ExportFormat) -> Option<(gst::Caps, gst::Caps)> {
+    let (container, audio) = match format {
+        ExportFormat::Copy => return None,
+        ExportFormat::Mp3 => (
+            gst::Caps::builder("application/x-id3").build(),
+            gst::Caps::builder("audio/mpeg")
+                .field("mpegversion", 1i32)
+                .field("layer", 3i32)
+                .build(),
+        ),
+        ExportFormat::Opus => (
+            gst::Caps::builder("application/ogg").build(),
+            gst::Caps::builder("audio/x-opus").build(),
+        ),
+        ExportFormat::Aac => (
+            gst::Caps::builder("video/quicktime")
+                .field("variant", "iso")
+                .build(),
+            gst::Caps::builder("audio/mpeg")
+                .field("mpegversion", 4i32)
+                .build(),
+        ),
+    };
+
+    Some((container, audio))
+}
+
+fn run_to_end(pipeline: &gst::Pipeline) -> Result<(), String> {
+    let bus = pipeline
+        .bus()
+        .ok_or_else(|| "pipeline has no bus".to_string())?;
+
+    pipeline
+        .set_state(gst::State::Playing)
+        .map_err(|err| format!("failed to start pipeline: {err}"))?;
+
+    for msg in bus.iter_timed(gst::ClockTime::NONE) {
+        match msg.view() {
+            gst::MessageView::Eos(_) => return Ok(()),
+            gst::MessageView::Error(err) => return Err(err.error().to_string()),
+            _ => {}
+        }
+    }
+
+    Ok(())
+}
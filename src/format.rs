@@ -0,0 +1,174 @@
+// SPDX-License-Identifier: GPL-3.0
+
+//! Formatting of durations, counts and other numbers for display. Decimal and digit group
+//! separators come from the active translation, the same way `fl!` strings do.
+
+use crate::fl;
+use crate::helpers::clamp;
+use chrono::{DateTime, Local};
+
+/// Format seconds as MM:SS
+pub fn format_time(seconds: f32) -> String {
+    let total_seconds = seconds.max(0.0).floor() as u32;
+    let minutes = total_seconds / 60;
+    let secs = total_seconds % 60;
+
+    format!("{minutes}:{secs:02}")
+}
+
+/// Format seconds as H:MM:SS when needed, otherwise M:SS
+pub fn format_duration(seconds: f32) -> String {
+    let total_seconds = seconds.max(0.0).floor() as u32;
+    let hours = total_seconds / 3600;
+    let minutes = (total_seconds % 3600) / 60;
+    let secs = total_seconds % 60;
+
+    if hours > 0 {
+        format!("{hours}:{minutes:02}:{secs:02}")
+    } else {
+        format!("{minutes}:{secs:02}")
+    }
+}
+
+/// Format an optional duration as H:MM:SS when needed, otherwise M:SS
+pub fn format_optional_duration(duration: Option<f32>) -> String {
+    duration.map(format_duration).unwrap_or_default()
+}
+
+/// Format remaining time as -MM:SS
+pub fn format_time_left(current: f32, duration: f32) -> String {
+    let time_left = clamp(duration - current, 0.0, duration);
+
+    format!("-{}", format_time(time_left))
+}
+
+/// Format a count with digit grouping, e.g. 12,345
+pub fn format_count(count: usize) -> String {
+    localize_number(&count.to_string())
+}
+
+/// Format a number with a fixed number of decimals, e.g. 1,234.5
+pub fn format_decimal(value: f64, decimals: usize) -> String {
+    localize_number(&format!("{value:.decimals$}"))
+}
+
+/// Format a number with a fixed number of decimals and a sign when it's positive, e.g. +1.5
+pub fn format_signed_decimal(value: f64, decimals: usize) -> String {
+    localize_number(&format!("{value:+.decimals$}"))
+}
+
+/// Format a percentage with a fixed number of decimals, e.g. 42.50%
+pub fn format_percent(value: f64, decimals: usize) -> String {
+    fl!("percent", value = format_decimal(value, decimals))
+}
+
+/// Format a byte count using binary units, e.g. 8.4 MiB
+pub fn format_file_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+
+    format!("{} {}", format_decimal(size, 1), UNITS[unit])
+}
+
+/// Format a stored play time for display. Times saved before they were stored as RFC 3339
+/// are shown as they are.
+pub fn format_last_played(last_played: &str) -> String {
+    match DateTime::parse_from_rfc3339(last_played) {
+        Ok(time) => time
+            .with_timezone(&Local)
+            .format("%Y-%m-%d %H:%M")
+            .to_string(),
+        Err(_) => last_played.to_string(),
+    }
+}
+
+/// Format bits per second as kbps
+pub fn format_bitrate(bits_per_second: u32) -> String {
+    format!(
+        "{} kbps",
+        format_decimal(bits_per_second as f64 / 1000.0, 0)
+    )
+}
+
+/// Format a sample rate in Hz as kHz, with only as many decimals as needed, e.g. 44.1 kHz
+pub fn format_sample_rate(hz: u32) -> String {
+    format!("{} kHz", localize_number(&(hz as f64 / 1000.0).to_string()))
+}
+
+/// Swap the separators of a number Rust formatted, like `-1234.5`, for the locale's ones
+fn localize_number(formatted: &str) -> String {
+    with_separators(
+        formatted,
+        &fl!("decimal-separator"),
+        &fl!("digit-group-separator"),
+    )
+}
+
+fn with_separators(formatted: &str, decimal_separator: &str, group_separator: &str) -> String {
+    let (sign, unsigned) = match formatted.strip_prefix(['-', '+']) {
+        Some(unsigned) => formatted.split_at(formatted.len() - unsigned.len()),
+        None => ("", formatted),
+    };
+    let (integer, fraction) = match unsigned.split_once('.') {
+        Some((integer, fraction)) => (integer, Some(fraction)),
+        None => (unsigned, None),
+    };
+
+    let mut localized = String::from(sign);
+    for (index, digit) in integer.chars().enumerate() {
+        if index > 0 && (integer.len() - index) % 3 == 0 {
+            localized.push_str(group_separator);
+        }
+        localized.push(digit);
+    }
+
+    if let Some(fraction) = fraction {
+        localized.push_str(decimal_separator);
+        localized.push_str(fraction);
+    }
+
+    localized
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn with_separators_english() {
+        assert_eq!(with_separators("1234567.89", ".", ","), "1,234,567.89");
+        assert_eq!(with_separators("-1234.5", ".", ","), "-1,234.5");
+        assert_eq!(with_separators("+1.5", ".", ","), "+1.5");
+    }
+
+    #[test]
+    fn with_separators_german() {
+        assert_eq!(with_separators("1234567.89", ",", "."), "1.234.567,89");
+        assert_eq!(with_separators("-0.5", ",", "."), "-0,5");
+    }
+
+    #[test]
+    fn with_separators_french() {
+        // Groups are separated by a narrow no-break space
+        assert_eq!(
+            with_separators("12345.6", ",", "\u{202f}"),
+            "12\u{202f}345,6"
+        );
+    }
+
+    #[test]
+    fn with_separators_swedish() {
+        assert_eq!(with_separators("1000", ",", "\u{a0}"), "1\u{a0}000");
+        assert_eq!(with_separators("999", ",", "\u{a0}"), "999");
+    }
+}
@@ -1,45 +1,13 @@
-use crate::constants::{ARTWORK_MEDIUM_SUFFIX, ARTWORK_SMALL_SUFFIX};
+use crate::constants::{ARTWORK_MEDIUM_SUFFIX, ARTWORK_SMALL_SUFFIX, MAX_DISPLAY_CHARS};
 
 use std::{
+    ffi::OsString,
+    fs::{self, File},
+    io::{self, BufWriter, Write},
     path::Path,
     time::{Duration, Instant},
 };
 
-/// Format seconds as MM:SS
-pub fn format_time(seconds: f32) -> String {
-    let total_seconds = seconds.max(0.0).floor() as u32;
-    let minutes = total_seconds / 60;
-    let secs = total_seconds % 60;
-
-    format!("{minutes}:{secs:02}")
-}
-
-/// Format seconds as H:MM:SS when needed, otherwise M:SS
-pub fn format_duration(seconds: f32) -> String {
-    let total_seconds = seconds.max(0.0).floor() as u32;
-    let hours = total_seconds / 3600;
-    let minutes = (total_seconds % 3600) / 60;
-    let secs = total_seconds % 60;
-
-    if hours > 0 {
-        format!("{hours}:{minutes:02}:{secs:02}")
-    } else {
-        format!("{minutes}:{secs:02}")
-    }
-}
-
-/// Format an optional duration as H:MM:SS when needed, otherwise M:SS
-pub fn format_optional_duration(duration: Option<f32>) -> String {
-    duration.map(format_duration).unwrap_or_default()
-}
-
-/// Format remaining time as -MM:SS
-pub fn format_time_left(current: f32, duration: f32) -> String {
-    let time_left = clamp(duration - current, 0.0, duration);
-
-    format!("-{}", format_time(time_left))
-}
-
 /// Check if two instants represent a double-click
 pub fn is_double_click(last: Instant, threshold_ms: u64) -> bool {
     Instant::now().duration_since(last) <= Duration::from_millis(threshold_ms)
@@ -68,6 +36,76 @@ pub fn fallback_text(value: Option<&str>, fallback: &str) -> String {
     non_empty_text(value).unwrap_or_else(|| fallback.to_string())
 }
 
+// Lowercase markers that start a featured artist credit
+const FEATURING_MARKERS: &[&str] = &["featuring ", "feat.", "feat ", "ft.", "ft "];
+
+/// The main artist of a credit like "Artist feat. Guest" or "Artist (ft. Guest)", for grouping
+/// tracks without touching their tags. Credits without a featured artist are returned as-is.
+pub fn primary_artist(artist: &str) -> &str {
+    // ASCII lowercasing keeps byte offsets valid for slicing the original
+    let lower = artist.to_ascii_lowercase();
+    let cut = lower.char_indices().skip(1).find_map(|(index, _)| {
+        let after_separator = matches!(lower.as_bytes()[index - 1], b' ' | b'(' | b'[');
+        let is_marker = FEATURING_MARKERS
+            .iter()
+            .any(|marker| lower[index..].starts_with(marker));
+        (after_separator && is_marker).then_some(index)
+    });
+
+    let Some(cut) = cut else {
+        return artist;
+    };
+
+    let primary =
+        artist[..cut].trim_end_matches(|c: char| c.is_whitespace() || c == '(' || c == '[');
+
+    if primary.is_empty() { artist } else { primary }
+}
+
+// Lowercase articles dropped from the start of a name when sorting, the space keeps
+// "Theory" and "Abba" whole
+const LEADING_ARTICLES: &[&str] = &["the ", "a ", "an "];
+
+/// A name without its leading article, so "The Beatles" sorts under B. A name that is only
+/// an article is returned as-is.
+pub fn strip_leading_article(text: &str) -> &str {
+    let text = text.trim_start();
+    // ASCII lowercasing keeps byte offsets valid for slicing the original
+    let lower = text.to_ascii_lowercase();
+
+    LEADING_ARTICLES
+        .iter()
+        .find(|article| lower.starts_with(*article))
+        .map(|article| text[article.len()..].trim_start())
+        .filter(|rest| !rest.is_empty())
+        .unwrap_or(text)
+}
+
+/// Keep tag values on one line and cut them at `MAX_DISPLAY_CHARS` with an ellipsis, so
+/// lyrics pasted into a title can't take over the layout
+pub fn display_text(value: &str) -> String {
+    let mut chars = value
+        .chars()
+        .map(|c| if c == '\n' || c == '\r' { ' ' } else { c });
+    let mut text: String = chars.by_ref().take(MAX_DISPLAY_CHARS).collect();
+
+    if chars.next().is_some() {
+        text.truncate(text.trim_end().len());
+        text.push('…');
+    }
+
+    text
+}
+
+/// Uppercase first letter of an entry for jumping through a sorted view, '#' when it starts
+/// with anything else
+pub fn jump_letter(text: &str) -> char {
+    match text.trim_start().chars().next() {
+        Some(c) if c.is_alphabetic() => c.to_uppercase().next().unwrap_or(c),
+        _ => '#',
+    }
+}
+
 /// Return a readable display name for a file path
 pub fn path_display_name(path: &Path) -> String {
     path.file_stem()
@@ -115,3 +153,139 @@ pub fn clamp<T: PartialOrd>(value: T, min: T, max: T) -> T {
         value
     }
 }
+
+/// Write a file by filling a temporary file next to it and renaming it into place, so a crash
+/// or a failed write leaves the previous contents intact instead of a truncated file
+pub fn write_atomic<F>(path: &Path, write: F) -> io::Result<()>
+where
+    F: FnOnce(&mut BufWriter<File>) -> io::Result<()>,
+{
+    let file_name = path
+        .file_name()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "path has no file name"))?;
+    let mut temp_name = OsString::from(".");
+    temp_name.push(file_name);
+    temp_name.push(".tmp");
+    // Same directory so the rename can't cross filesystems
+    let temp_path = path.with_file_name(temp_name);
+
+    let result = File::create(&temp_path).and_then(|file| {
+        let mut writer = BufWriter::new(file);
+        write(&mut writer)?;
+        writer.flush()?;
+        let file = writer.into_inner().map_err(|err| err.into_error())?;
+        file.sync_all()?;
+        fs::rename(&temp_path, path)
+    });
+
+    if result.is_err() {
+        let _ = fs::remove_file(&temp_path);
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::TempDir;
+
+    #[test]
+    fn primary_artist_cuts_featured_artists() {
+        assert_eq!(
+            primary_artist("Daft Punk feat. Pharrell Williams"),
+            "Daft Punk"
+        );
+        assert_eq!(primary_artist("Gorillaz ft. De La Soul"), "Gorillaz");
+        assert_eq!(primary_artist("Santana (Featuring Rob Thomas)"), "Santana");
+        assert_eq!(
+            primary_artist("Mark Ronson [FT. Bruno Mars]"),
+            "Mark Ronson"
+        );
+    }
+
+    #[test]
+    fn primary_artist_keeps_collaborations() {
+        // Joint credits name equal artists, only featured ones are dropped
+        assert_eq!(primary_artist("Simon & Garfunkel"), "Simon & Garfunkel");
+        assert_eq!(primary_artist("Skrillex x Diplo"), "Skrillex x Diplo");
+    }
+
+    #[test]
+    fn primary_artist_keeps_words_containing_markers() {
+        assert_eq!(primary_artist("Left Foot"), "Left Foot");
+        assert_eq!(primary_artist("Soft Cell"), "Soft Cell");
+        assert_eq!(primary_artist("feat. Nobody"), "feat. Nobody");
+    }
+
+    #[test]
+    fn strip_leading_article_drops_articles() {
+        assert_eq!(strip_leading_article("The Beatles"), "Beatles");
+        assert_eq!(
+            strip_leading_article("a Tribe Called Quest"),
+            "Tribe Called Quest"
+        );
+        assert_eq!(strip_leading_article("  An  Horse"), "Horse");
+    }
+
+    #[test]
+    fn strip_leading_article_keeps_whole_words() {
+        assert_eq!(
+            strip_leading_article("Theory of a Deadman"),
+            "Theory of a Deadman"
+        );
+        assert_eq!(strip_leading_article("Abba"), "Abba");
+        assert_eq!(strip_leading_article("Anthrax"), "Anthrax");
+    }
+
+    #[test]
+    fn strip_leading_article_keeps_names_that_are_only_an_article() {
+        assert_eq!(strip_leading_article("The"), "The");
+        assert_eq!(strip_leading_article("The "), "The ");
+    }
+
+    #[test]
+    fn display_text_cuts_long_titles() {
+        let title = "la ".repeat(3_334);
+        assert!(title.chars().count() > 10_000);
+
+        let text = display_text(&title);
+
+        assert!(text.ends_with('…'));
+        assert!(text.chars().count() <= MAX_DISPLAY_CHARS + 1);
+        assert!(!text.trim_end_matches('…').ends_with(' '));
+    }
+
+    #[test]
+    fn display_text_keeps_one_line() {
+        assert_eq!(display_text("Verse\r\nChorus\n"), "Verse  Chorus ");
+        assert_eq!(display_text("Short title"), "Short title");
+    }
+
+    #[test]
+    fn write_atomic_replaces_contents() {
+        let dir = TempDir::new("helpers", "write");
+        let path = dir.join("state.json");
+        fs::write(&path, "old").unwrap();
+
+        write_atomic(&path, |writer| writer.write_all(b"new")).unwrap();
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "new");
+    }
+
+    #[test]
+    fn write_atomic_failure_keeps_previous_file() {
+        let dir = TempDir::new("helpers", "failure");
+        let path = dir.join("state.json");
+        fs::write(&path, "old").unwrap();
+
+        let result = write_atomic(&path, |writer| {
+            writer.write_all(b"partial")?;
+            Err(io::Error::other("disk full"))
+        });
+
+        assert!(result.is_err());
+        assert_eq!(fs::read_to_string(&path).unwrap(), "old");
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 1);
+    }
+}
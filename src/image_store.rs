@@ -1,61 +1,190 @@
 // SPDX-License-Identifier: GPL-3.0
 
-use crate::constants::{IMAGE_CACHE_SWEEP_SECS, IMAGE_CACHE_TTL_SECS};
+use crate::constants::{
+    ACCENT_SAMPLE_SIZE, IMAGE_CACHE_SWEEP_SECS, IMAGE_CACHE_TTL_SECS, IMAGE_DECODE_SCALE,
+    IMAGE_DECODE_SIZE_STEP, IMAGE_LOAD_CONCURRENCY, IMAGE_LOAD_RETRIES, IMAGE_LOAD_RETRY_DELAY_MS,
+    IMAGE_RECENTLY_MODIFIED_SECS,
+};
+use cosmic::iced::Color;
 use cosmic::widget::image::Handle;
 use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt;
 use std::fs;
-use std::path::PathBuf;
+use std::io;
+use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
-use std::time::{Duration, Instant};
-use tokio::sync::mpsc;
+use std::time::{Duration, Instant, SystemTime};
+use tokio::sync::{Notify, Semaphore};
+
+/// A cached image is identified by its file and the size it was decoded at.
+/// `size` is `None` for the full resolution image. Keys with `accent` set load the file's
+/// accent color instead of an image.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+struct ImageKey {
+    path: PathBuf,
+    size: Option<u32>,
+    accent: bool,
+}
 
 pub struct ImageStore {
     artwork_dir: PathBuf,
-    cache: Arc<Mutex<HashMap<PathBuf, CachedImage>>>,
-    queue: Arc<Mutex<VecDeque<PathBuf>>>,
-    tx: mpsc::Sender<PathBuf>,
+    cache: Arc<Mutex<HashMap<ImageKey, CachedImage>>>,
+    queue: Arc<Mutex<VecDeque<ImageKey>>>,
+    loading: Arc<Mutex<HashSet<ImageKey>>>,
+    notify: Arc<Notify>,
+    // Failed attempts for images being retried after a transient read error, and images that
+    // won't load with the modification time of the file that failed. Failed images aren't
+    // requested again until the file changes or the cache is cleared.
+    retries: Arc<Mutex<HashMap<ImageKey, u32>>>,
+    failed: Arc<Mutex<HashMap<ImageKey, Option<SystemTime>>>>,
+    // Accent colors are tiny and kept for the whole session, `None` when the artwork has no
+    // color worth using
+    accents: Arc<Mutex<HashMap<PathBuf, Option<Color>>>>,
 }
 
 impl ImageStore {
     pub fn new(artwork_dir: PathBuf) -> Self {
-        let (tx, mut rx) = mpsc::channel::<PathBuf>(64);
-
         let cache = Arc::new(Mutex::new(HashMap::new()));
         let queue = Arc::new(Mutex::new(VecDeque::new()));
+        let loading = Arc::new(Mutex::new(HashSet::new()));
+        let notify = Arc::new(Notify::new());
+        let retries = Arc::new(Mutex::new(HashMap::new()));
+        let failed = Arc::new(Mutex::new(HashMap::new()));
+        let accents = Arc::new(Mutex::new(HashMap::new()));
 
         let cache_clone = cache.clone();
         let queue_clone = queue.clone();
+        let loading_clone = loading.clone();
+        let notify_clone = notify.clone();
+        let retries_clone = retries.clone();
+        let failed_clone = failed.clone();
+        let accents_clone = accents.clone();
 
         let cache_eviction = cache.clone();
 
+        // Requests are never dropped. They wait in the queue and at most
+        // IMAGE_LOAD_CONCURRENCY files are decoded at once.
         tokio::spawn(async move {
-            while let Some(path) = rx.recv().await {
-                // Remove path from queue
-                queue_clone.lock().unwrap().retain(|p| p != &path);
+            let semaphore = Arc::new(Semaphore::new(IMAGE_LOAD_CONCURRENCY));
 
-                // If path is already in cache, skip loading
-                if cache_clone.lock().unwrap().contains_key(&path) {
+            loop {
+                let Ok(permit) = semaphore.clone().acquire_owned().await else {
+                    return;
+                };
+
+                let key = loop {
+                    let next = queue_clone.lock().unwrap().pop_front();
+                    if let Some(key) = next {
+                        break key;
+                    }
+                    notify_clone.notified().await;
+                };
+
+                // If the image or accent is already known, skip loading
+                if is_loaded(&cache_clone, &accents_clone, &key) {
                     continue;
                 }
 
-                let path_for_read = path.clone();
-                match tokio::task::spawn_blocking(move || fs::read(&path_for_read)).await {
-                    Ok(Ok(data)) => {
-                        cache_clone.lock().unwrap().insert(
-                            path,
-                            CachedImage {
-                                handle: Arc::new(cosmic::widget::image::Handle::from_bytes(data)),
-                                last_used: Instant::now(),
-                            },
-                        );
-                    }
-                    Ok(Err(err)) => {
-                        eprintln!("Failed to load image: {:?} {}", path, err);
-                    }
-                    Err(err) => {
-                        eprintln!("Failed to join image load task for {:?}: {}", path, err);
+                loading_clone.lock().unwrap().insert(key.clone());
+
+                let cache = cache_clone.clone();
+                let queue = queue_clone.clone();
+                let loading = loading_clone.clone();
+                let notify = notify_clone.clone();
+                let retries = retries_clone.clone();
+                let failed = failed_clone.clone();
+                let accents = accents_clone.clone();
+
+                tokio::spawn(async move {
+                    let key_for_read = key.clone();
+                    let result = tokio::task::spawn_blocking(move || {
+                        if key_for_read.accent {
+                            load_accent(&key_for_read.path).map(Loaded::Accent)
+                        } else {
+                            load_handle(&key_for_read.path, key_for_read.size).map(Loaded::Image)
+                        }
+                    })
+                    .await;
+                    drop(permit);
+
+                    let retry_delay = match result {
+                        Ok(Ok(loaded)) => {
+                            match loaded {
+                                Loaded::Image(handle) => {
+                                    cache.lock().unwrap().insert(
+                                        key.clone(),
+                                        CachedImage {
+                                            handle: Arc::new(handle),
+                                            last_used: Instant::now(),
+                                        },
+                                    );
+                                }
+                                Loaded::Accent(accent) => {
+                                    accents.lock().unwrap().insert(key.path.clone(), accent);
+                                }
+                            }
+                            retries.lock().unwrap().remove(&key);
+                            failed.lock().unwrap().remove(&key);
+                            None
+                        }
+                        // A file that's locked or still being written often reads fine shortly
+                        // after, wait longer after each failed attempt
+                        Ok(Err(LoadError::Transient(err))) => {
+                            let mut retries = retries.lock().unwrap();
+                            let attempts = retries.entry(key.clone()).or_insert(0);
+                            *attempts += 1;
+
+                            if *attempts <= IMAGE_LOAD_RETRIES {
+                                log::debug!(
+                                    "Retrying image {:?} after attempt {}: {}",
+                                    key.path,
+                                    attempts,
+                                    err
+                                );
+                                Some(Duration::from_millis(
+                                    IMAGE_LOAD_RETRY_DELAY_MS << (*attempts - 1),
+                                ))
+                            } else {
+                                log::warn!("Failed to load image: {:?} {}", key.path, err);
+                                retries.remove(&key);
+                                failed
+                                    .lock()
+                                    .unwrap()
+                                    .insert(key.clone(), modified(&key.path));
+                                None
+                            }
+                        }
+                        Ok(Err(err)) => {
+                            log::warn!("Failed to load image: {:?} {}", key.path, err);
+                            failed
+                                .lock()
+                                .unwrap()
+                                .insert(key.clone(), modified(&key.path));
+                            None
+                        }
+                        Err(err) => {
+                            log::warn!(
+                                "Failed to join image load task for {:?}: {}",
+                                key.path,
+                                err
+                            );
+                            None
+                        }
+                    };
+
+                    // The key stays in `loading` while waiting so requests don't queue it twice
+                    if let Some(delay) = retry_delay {
+                        tokio::time::sleep(delay).await;
+                        let mut queue = queue.lock().unwrap();
+                        loading.lock().unwrap().remove(&key);
+                        queue.push_back(key);
+                        drop(queue);
+                        notify.notify_one();
+                        return;
                     }
-                }
+
+                    loading.lock().unwrap().remove(&key);
+                });
             }
         });
 
@@ -77,38 +206,131 @@ impl ImageStore {
             artwork_dir,
             cache,
             queue,
-            tx,
+            loading,
+            notify,
+            retries,
+            failed,
+            accents,
         }
     }
+
+    /// Pixel size to decode artwork at for a given on-screen size. Sizes are
+    /// rounded up to IMAGE_DECODE_SIZE_STEP so zooming doesn't create a new
+    /// cache entry for every step.
+    pub fn decode_size(display_size: f32) -> u32 {
+        let pixels = (display_size.max(1.0) * IMAGE_DECODE_SCALE).ceil() as u32;
+        pixels.div_ceil(IMAGE_DECODE_SIZE_STEP) * IMAGE_DECODE_SIZE_STEP
+    }
 }
 
 impl ImageStore {
+    /// Request the full resolution image
     pub fn request(&self, path: String) {
-        let artwork_path = self.artwork_dir.join(path);
+        self.request_sized(path, None);
+    }
+
+    /// Request an image downscaled to fit within `size` pixels
+    pub fn request_sized(&self, path: String, size: Option<u32>) {
+        let Some(key) = self.loadable_key(path, size, false) else {
+            return;
+        };
+
+        self.enqueue(key);
+    }
 
-        if !artwork_path.is_file() {
+    /// Request the accent color of an artwork file, read by `accent` once it's loaded
+    pub fn request_accent(&self, path: String) {
+        let Some(key) = self.loadable_key(path, None, true) else {
+            return;
+        };
+
+        self.enqueue(key);
+    }
+
+    fn enqueue(&self, key: ImageKey) {
+        let mut q = self.queue.lock().unwrap();
+        if q.contains(&key) {
             return;
         }
 
-        if self.cache.lock().unwrap().contains_key(&artwork_path) {
+        q.push_back(key);
+        drop(q);
+
+        self.notify.notify_one();
+    }
+
+    /// Move the given artwork to the front of the load queue, keeping their order.
+    /// Used to load the covers that are currently on screen before anything else.
+    pub fn warm<I>(&self, paths: I, size: Option<u32>)
+    where
+        I: IntoIterator<Item = String>,
+    {
+        let keys: Vec<ImageKey> = paths
+            .into_iter()
+            .filter_map(|path| self.loadable_key(path, size, false))
+            .collect();
+
+        if keys.is_empty() {
             return;
         }
 
         let mut q = self.queue.lock().unwrap();
-        if q.contains(&artwork_path) {
-            return;
+        q.retain(|key| !keys.contains(key));
+        for key in keys.into_iter().rev() {
+            q.push_front(key);
+        }
+        drop(q);
+
+        self.notify.notify_one();
+    }
+
+    // Build a cache key, returning None if the file is missing, cached, already loading or
+    // failed to load and hasn't changed since
+    fn loadable_key(&self, path: String, size: Option<u32>, accent: bool) -> Option<ImageKey> {
+        let key = ImageKey {
+            path: self.artwork_dir.join(path),
+            size,
+            accent,
+        };
+
+        if !key.path.is_file() {
+            return None;
         }
 
-        if self.tx.try_send(artwork_path.clone()).is_ok() {
-            q.push_back(artwork_path);
+        if is_loaded(&self.cache, &self.accents, &key) {
+            return None;
+        }
+
+        if self.loading.lock().unwrap().contains(&key) {
+            return None;
+        }
+
+        if self
+            .failed
+            .lock()
+            .unwrap()
+            .get(&key)
+            .is_some_and(|failed_modified| *failed_modified == modified(&key.path))
+        {
+            return None;
         }
+
+        Some(key)
     }
 
     pub fn get(&self, path: &str) -> Option<Arc<Handle>> {
-        let artwork_path = self.artwork_dir.join(path);
+        self.get_sized(path, None)
+    }
+
+    pub fn get_sized(&self, path: &str, size: Option<u32>) -> Option<Arc<Handle>> {
+        let key = ImageKey {
+            path: self.artwork_dir.join(path),
+            size,
+            accent: false,
+        };
         let mut cache = self.cache.lock().unwrap();
 
-        if let Some(entry) = cache.get_mut(&artwork_path) {
+        if let Some(entry) = cache.get_mut(&key) {
             entry.last_used = Instant::now();
             return Some(entry.handle.clone());
         }
@@ -116,6 +338,21 @@ impl ImageStore {
         None
     }
 
+    /// The accent color of an artwork file once `request_accent` has loaded it
+    pub fn accent(&self, path: &str) -> Option<Color> {
+        self.accents
+            .lock()
+            .unwrap()
+            .get(&self.artwork_dir.join(path))
+            .copied()
+            .flatten()
+    }
+
+    /// Number of decoded images currently held in memory
+    pub fn len(&self) -> usize {
+        self.cache.lock().unwrap().len()
+    }
+
     pub fn exists(&self, path: &str) -> bool {
         self.artwork_dir.join(path).is_file()
     }
@@ -123,6 +360,7 @@ impl ImageStore {
     pub fn clear(&self) {
         self.cache.lock().unwrap().clear();
         self.queue.lock().unwrap().clear();
+        self.failed.lock().unwrap().clear();
     }
 
     pub fn cleanup_unused(&self, used_filenames: &HashSet<String>) {
@@ -130,9 +368,10 @@ impl ImageStore {
             Ok(entries) => entries,
             Err(err) if err.kind() == std::io::ErrorKind::NotFound => return,
             Err(err) => {
-                eprintln!(
+                log::warn!(
                     "Failed to read artwork cache directory {:?}: {}",
-                    self.artwork_dir, err
+                    self.artwork_dir,
+                    err
                 );
                 return;
             }
@@ -160,9 +399,10 @@ impl ImageStore {
                     removed_paths.insert(path);
                 }
                 Err(err) => {
-                    eprintln!(
+                    log::warn!(
                         "Failed to remove unused artwork cache file {:?}: {}",
-                        path, err
+                        path,
+                        err
                     );
                 }
             }
@@ -173,13 +413,21 @@ impl ImageStore {
         }
 
         self.cache
+            .lock()
+            .unwrap()
+            .retain(|key, _| !removed_paths.contains(&key.path));
+        self.accents
             .lock()
             .unwrap()
             .retain(|path, _| !removed_paths.contains(path));
         self.queue
             .lock()
             .unwrap()
-            .retain(|path| !removed_paths.contains(path));
+            .retain(|key| !removed_paths.contains(&key.path));
+        self.failed
+            .lock()
+            .unwrap()
+            .retain(|key, _| !removed_paths.contains(&key.path));
     }
 }
 
@@ -187,3 +435,200 @@ struct CachedImage {
     handle: Arc<cosmic::widget::image::Handle>,
     last_used: Instant,
 }
+
+/// What the loader produced for a key
+enum Loaded {
+    Image(Handle),
+    Accent(Option<Color>),
+}
+
+fn is_loaded(
+    cache: &Mutex<HashMap<ImageKey, CachedImage>>,
+    accents: &Mutex<HashMap<PathBuf, Option<Color>>>,
+    key: &ImageKey,
+) -> bool {
+    if key.accent {
+        accents.lock().unwrap().contains_key(&key.path)
+    } else {
+        cache.lock().unwrap().contains_key(key)
+    }
+}
+
+/// Why an image couldn't be loaded. Transient errors may go away, such as a locked file or one
+/// that's still being written, a missing file or one that can't be decoded won't load on a
+/// retry.
+enum LoadError {
+    Transient(io::Error),
+    Permanent(String),
+}
+
+impl fmt::Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Transient(err) => write!(f, "{}", err),
+            Self::Permanent(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+fn read_image_file(path: &Path) -> Result<Vec<u8>, LoadError> {
+    fs::read(path).map_err(|err| match err.kind() {
+        io::ErrorKind::NotFound | io::ErrorKind::PermissionDenied | io::ErrorKind::IsADirectory => {
+            LoadError::Permanent(err.to_string())
+        }
+        _ => LoadError::Transient(err),
+    })
+}
+
+// Data that doesn't decode is a broken file, unless the file was just modified and is likely
+// still being written
+fn decode_error(path: &Path, err: image::ImageError) -> LoadError {
+    let recently_modified = modified(path)
+        .and_then(|modified| modified.elapsed().ok())
+        .is_some_and(|age| age < Duration::from_secs(IMAGE_RECENTLY_MODIFIED_SECS));
+
+    if recently_modified {
+        LoadError::Transient(io::Error::new(io::ErrorKind::InvalidData, err))
+    } else {
+        LoadError::Permanent(err.to_string())
+    }
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
+
+// Read an image file, downscaling it when a target size is given and the image is larger
+fn load_handle(path: &Path, size: Option<u32>) -> Result<Handle, LoadError> {
+    let data = read_image_file(path)?;
+
+    let Some(size) = size else {
+        return Ok(Handle::from_bytes(data));
+    };
+
+    let image = image::load_from_memory(&data).map_err(|err| decode_error(path, err))?;
+    if image.width() <= size && image.height() <= size {
+        return Ok(Handle::from_bytes(data));
+    }
+
+    let thumbnail = image.thumbnail(size, size).to_rgba8();
+    Ok(Handle::from_rgba(
+        thumbnail.width(),
+        thumbnail.height(),
+        thumbnail.into_raw(),
+    ))
+}
+
+// Pick the most common saturated hue of a downscaled copy and average the pixels of that hue.
+// Grey, near black and near white pixels are skipped so borders and backgrounds don't win.
+// Returns `None` when no hue covers enough of the artwork.
+fn load_accent(path: &Path) -> Result<Option<Color>, LoadError> {
+    const HUE_BUCKETS: usize = 12;
+
+    let data = read_image_file(path)?;
+    let image = image::load_from_memory(&data).map_err(|err| decode_error(path, err))?;
+    let sample = image
+        .thumbnail(ACCENT_SAMPLE_SIZE, ACCENT_SAMPLE_SIZE)
+        .to_rgb8();
+
+    // Weight and summed RGB per hue bucket
+    let mut buckets = [(0.0f32, [0.0f32; 3]); HUE_BUCKETS];
+
+    for pixel in sample.pixels() {
+        let [r, g, b] = pixel.0.map(|channel| channel as f32 / 255.0);
+        let max = r.max(g).max(b);
+        let min = r.min(g).min(b);
+        let chroma = max - min;
+        let saturation = if max > 0.0 { chroma / max } else { 0.0 };
+
+        if saturation < 0.25 || !(0.2..=0.95).contains(&max) {
+            continue;
+        }
+
+        let hue = if max == r {
+            ((g - b) / chroma).rem_euclid(6.0)
+        } else if max == g {
+            (b - r) / chroma + 2.0
+        } else {
+            (r - g) / chroma + 4.0
+        };
+        let bucket = ((hue / 6.0 * HUE_BUCKETS as f32) as usize).min(HUE_BUCKETS - 1);
+
+        let (weight, sum) = &mut buckets[bucket];
+        *weight += saturation;
+        sum[0] += r * saturation;
+        sum[1] += g * saturation;
+        sum[2] += b * saturation;
+    }
+
+    // A few colored pixels on a black and white cover aren't its color
+    let min_weight = (sample.width() * sample.height()) as f32 * 0.02;
+
+    Ok(buckets
+        .iter()
+        .filter(|(weight, _)| *weight > min_weight)
+        .max_by(|a, b| a.0.total_cmp(&b.0))
+        .map(|(weight, sum)| Color::from_rgb(sum[0] / weight, sum[1] / weight, sum[2] / weight)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::TempDir;
+    use std::io::Cursor;
+
+    fn png() -> Vec<u8> {
+        let mut data = Vec::new();
+        image::RgbImage::from_pixel(8, 8, image::Rgb([200, 40, 40]))
+            .write_to(&mut Cursor::new(&mut data), image::ImageFormat::Png)
+            .unwrap();
+        data
+    }
+
+    #[test]
+    fn artwork_being_written_loads_once_complete() {
+        let dir = TempDir::new("image", "being-written");
+        let path = dir.join("cover.png");
+        let data = png();
+
+        fs::write(&path, &data[..data.len() / 2]).unwrap();
+        assert!(matches!(
+            load_handle(&path, Some(4)),
+            Err(LoadError::Transient(_))
+        ));
+
+        fs::write(&path, &data).unwrap();
+        assert!(load_handle(&path, Some(4)).is_ok());
+    }
+
+    #[test]
+    fn broken_artwork_that_isnt_changing_fails_for_good() {
+        let dir = TempDir::new("image", "broken");
+        let path = dir.join("cover.png");
+        fs::write(&path, b"not an image").unwrap();
+        fs::File::options()
+            .write(true)
+            .open(&path)
+            .unwrap()
+            .set_modified(SystemTime::now() - Duration::from_secs(IMAGE_RECENTLY_MODIFIED_SECS * 2))
+            .unwrap();
+
+        assert!(matches!(
+            load_handle(&path, Some(4)),
+            Err(LoadError::Permanent(_))
+        ));
+    }
+
+    #[test]
+    fn missing_artwork_fails_for_good() {
+        let dir = TempDir::new("image", "missing");
+        let path = dir.join("cover.png");
+
+        assert!(matches!(
+            load_handle(&path, None),
+            Err(LoadError::Permanent(_))
+        ));
+    }
+}
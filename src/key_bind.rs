@@ -30,10 +30,18 @@ pub fn key_binds() -> HashMap<KeyBind, MenuAction> {
     bind!([Ctrl], Key::Character("=".into()), ZoomIn);
     bind!([Ctrl], Key::Character("n".into()), NewPlaylist);
     bind!([Ctrl], Key::Character("a".into()), SelectAll);
+    bind!([Ctrl], Key::Character("g".into()), GoToTrack);
+    bind!([Ctrl], Key::Character("d".into()), ToggleFavoriteNowPlaying);
+    bind!([Ctrl], Key::Character("b".into()), QuickAddCurrent);
+    bind!([Ctrl], Key::Character("k".into()), CommandPalette);
+    bind!([Ctrl, Shift], Key::Character("d".into()), DebugPanel);
+    bind!([Ctrl, Shift], Key::Character("s".into()), ShuffleLibrary);
     bind!([], Key::Named(Named::F2), RenamePlaylist);
     bind!([Ctrl], Key::Named(Named::ArrowUp), MoveNavUp);
     bind!([Ctrl], Key::Named(Named::ArrowDown), MoveNavDown);
     bind!([], Key::Named(Named::F1), TrackInfoPanel);
+    bind!([], Key::Named(Named::F9), ToggleFooter);
+    bind!([Ctrl], Key::Character("l".into()), GoToNowPlaying);
     bind!([], Key::Character("m".into()), ToggleMute);
     bind!([], Key::Character("-".into()), VolumeDown);
     bind!([], Key::Character("=".into()), VolumeUp);
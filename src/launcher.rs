@@ -0,0 +1,37 @@
+// SPDX-License-Identifier: GPL-3.0
+use std::collections::HashMap;
+use zbus::zvariant::Value;
+
+const LAUNCHER_ENTRY_PATH: &str = "/com/galacticpirateradio/etherealwaves/launcher";
+const LAUNCHER_ENTRY_INTERFACE: &str = "com.canonical.Unity.LauncherEntry";
+
+/// Publish playback progress through the Unity launcher entry API. Docks and taskbars that
+/// support it draw a progress overlay on the app icon, everything else ignores the signal.
+///
+/// `progress` is a fraction from 0.0 to 1.0, `None` hides the overlay.
+pub fn update_progress(connection: &zbus::Connection, app_id: &str, progress: Option<f64>) {
+    let connection = connection.clone();
+    let app_uri = format!("application://{}.desktop", app_id);
+
+    tokio::spawn(async move {
+        let mut properties: HashMap<&str, Value<'_>> = HashMap::new();
+        properties.insert(
+            "progress",
+            Value::from(progress.unwrap_or(0.0).clamp(0.0, 1.0)),
+        );
+        properties.insert("progress-visible", Value::from(progress.is_some()));
+
+        if let Err(err) = connection
+            .emit_signal(
+                None::<zbus::names::BusName<'_>>,
+                LAUNCHER_ENTRY_PATH,
+                LAUNCHER_ENTRY_INTERFACE,
+                "Update",
+                &(app_uri.as_str(), properties),
+            )
+            .await
+        {
+            log::debug!("launcher entry update failed: {}", err);
+        }
+    });
+}
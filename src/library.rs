@@ -1,15 +1,81 @@
 // SPDX-License-Identifier: GPL-3.0
 
-use serde::{Deserialize, Serialize};
+use chrono::Local;
+use serde::{Deserialize, Deserializer, Serialize};
 use serde_json;
-use std::collections::HashMap;
-use std::error::Error;
-use std::fs::File;
-use std::io::{BufWriter, Write};
-use std::path::PathBuf;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
 use xdg::BaseDirectories;
 
-use crate::constants::LIBRARY_FILENAME;
+use crate::constants::{LIBRARY_FILENAME, LIBRARY_VERSION};
+use crate::helpers::{strip_leading_article, write_atomic};
+
+#[derive(Debug)]
+pub enum LibraryError {
+    Io(std::io::Error),
+    Json(serde_json::Error),
+    /// The XDG data directory couldn't be created
+    Xdg(std::io::Error),
+    /// No library has been saved yet
+    NotFound,
+    InvalidData(String),
+}
+
+impl std::fmt::Display for LibraryError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LibraryError::Io(e) => write!(f, "IO error: {}", e),
+            LibraryError::Json(e) => write!(f, "JSON error: {}", e),
+            LibraryError::Xdg(e) => write!(f, "Data directory error: {}", e),
+            LibraryError::NotFound => write!(f, "Library file not found"),
+            LibraryError::InvalidData(s) => write!(f, "Invalid data: {}", s),
+        }
+    }
+}
+
+impl std::error::Error for LibraryError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            LibraryError::Io(e) | LibraryError::Xdg(e) => Some(e),
+            LibraryError::Json(e) => Some(e),
+            LibraryError::NotFound | LibraryError::InvalidData(_) => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for LibraryError {
+    fn from(err: std::io::Error) -> Self {
+        LibraryError::Io(err)
+    }
+}
+
+impl From<serde_json::Error> for LibraryError {
+    fn from(err: serde_json::Error) -> Self {
+        LibraryError::Json(err)
+    }
+}
+
+/// On-disk form of `library.json`. Version 1 files are a bare media map, later versions wrap
+/// it with the schema version so new fields can be migrated.
+#[derive(Deserialize)]
+struct LibraryFile {
+    version: u32,
+    media: HashMap<PathBuf, MediaMetaData>,
+}
+
+// Read ahead of the rest of the file to pick its form, `None` for a version 1 media map
+#[derive(Deserialize)]
+struct LibraryFileVersion {
+    version: Option<u32>,
+}
+
+#[derive(Serialize)]
+struct LibraryFileRef<'a> {
+    version: u32,
+    media: &'a HashMap<PathBuf, MediaMetaData>,
+}
 
 #[derive(Debug, Clone)]
 pub struct Library {
@@ -23,19 +89,160 @@ impl Library {
         }
     }
 
+    // Load media from the home data directory, `NotFound` when nothing has been saved yet
+    pub fn load(app_xdg_dirs: &BaseDirectories) -> Result<Library, LibraryError> {
+        let file_path = app_xdg_dirs
+            .find_data_file(LIBRARY_FILENAME)
+            .ok_or(LibraryError::NotFound)?;
+        Self::load_from(&file_path)
+    }
+
+    fn load_from(file_path: &Path) -> Result<Library, LibraryError> {
+        let content = match fs::read_to_string(file_path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Err(LibraryError::NotFound),
+            Err(e) => return Err(e.into()),
+        };
+
+        // Parsing each form on its own keeps the line and column of any error
+        let (version, media) = match serde_json::from_str::<LibraryFileVersion>(&content)? {
+            LibraryFileVersion {
+                version: Some(version),
+            } => {
+                // Refuse rather than let a save drop fields this version doesn't know about
+                if version > LIBRARY_VERSION {
+                    return Err(LibraryError::InvalidData(format!(
+                        "library version {} is newer than the supported version {}",
+                        version, LIBRARY_VERSION
+                    )));
+                }
+                let file: LibraryFile = serde_json::from_str(&content)?;
+                (file.version, file.media)
+            }
+            LibraryFileVersion { version: None } => (1, serde_json::from_str(&content)?),
+        };
+
+        let mut library = Library {
+            media: Self::migrate(version, media),
+        };
+        library.resolve_duplicate_ids();
+        Ok(library)
+    }
+
+    /// Upgrade media loaded from an older schema. Fields added since are filled with their
+    /// serde defaults while parsing, steps that need more than that go here.
+    fn migrate(
+        _version: u32,
+        media: HashMap<PathBuf, MediaMetaData>,
+    ) -> HashMap<PathBuf, MediaMetaData> {
+        // Version 1 only lacked the envelope and the single genre of version 2 is read into
+        // `genres` while parsing
+        media
+    }
+
+    /// Move the saved library aside to `library.json.bak-<time>` so a library that failed to
+    /// load isn't replaced by the next save. Returns where it was moved.
+    pub fn back_up(app_xdg_dirs: &BaseDirectories) -> io::Result<PathBuf> {
+        let file_path = app_xdg_dirs
+            .find_data_file(LIBRARY_FILENAME)
+            .ok_or_else(|| io::Error::from(io::ErrorKind::NotFound))?;
+        Self::back_up_file(&file_path)
+    }
+
+    fn back_up_file(file_path: &Path) -> io::Result<PathBuf> {
+        let mut backup_name = file_path.file_name().unwrap_or_default().to_os_string();
+        backup_name.push(format!(".bak-{}", Local::now().format("%Y%m%d-%H%M%S")));
+        let backup_path = file_path.with_file_name(backup_name);
+
+        fs::rename(file_path, &backup_path)?;
+        Ok(backup_path)
+    }
+
     // Save the current media to the home data directory
-    pub fn save(&self, app_xdg_dirs: &BaseDirectories) -> Result<(), Box<dyn Error>> {
-        let file_path = app_xdg_dirs.place_data_file(LIBRARY_FILENAME).unwrap();
-        let file = File::create(file_path)?;
-        let mut writer = BufWriter::new(file);
-        serde_json::to_writer(&mut writer, &self.media)?;
-        writer.flush()?;
+    pub fn save(&self, app_xdg_dirs: &BaseDirectories) -> Result<(), LibraryError> {
+        let file_path = app_xdg_dirs
+            .place_data_file(LIBRARY_FILENAME)
+            .map_err(LibraryError::Xdg)?;
+        write_atomic(&file_path, |writer| {
+            let file = LibraryFileRef {
+                version: LIBRARY_VERSION,
+                media: &self.media,
+            };
+            serde_json::to_writer(writer, &file).map_err(io::Error::from)
+        })?;
         Ok(())
     }
 
+    /// Give entries sharing an id a new one so id lookups find a single track. Paths are
+    /// visited in order so the first keeps its id and a rescan assigns the same ones again.
+    /// Returns how many ids were changed.
+    pub fn resolve_duplicate_ids(&mut self) -> usize {
+        let mut paths: Vec<PathBuf> = self.media.keys().cloned().collect();
+        paths.sort();
+
+        let mut taken = HashSet::new();
+        let mut resolved = 0;
+
+        for path in paths {
+            let Some(metadata) = self.media.get_mut(&path) else {
+                continue;
+            };
+            let Some(id) = metadata.id.clone() else {
+                continue;
+            };
+            if taken.insert(id.clone()) {
+                continue;
+            }
+
+            let Some(new_id) = (1u32..)
+                .map(|salt| sha256::digest(format!("{}#{}", path.to_string_lossy(), salt)))
+                .find(|candidate| !taken.contains(candidate))
+            else {
+                continue;
+            };
+
+            log::warn!(
+                "Track id {} of {:?} is already in use, using {}",
+                id,
+                path,
+                new_id
+            );
+            taken.insert(new_id.clone());
+            metadata.id = Some(new_id);
+            resolved += 1;
+        }
+
+        resolved
+    }
+
     pub fn from_id(&self, id: &String) -> Option<(&PathBuf, &MediaMetaData)> {
         self.media.iter().find(|(_, v)| v.id.as_deref() == Some(id))
     }
+
+    pub fn from_id_mut(&mut self, id: &str) -> Option<&mut MediaMetaData> {
+        self.media
+            .values_mut()
+            .find(|v| v.id.as_deref() == Some(id))
+    }
+}
+
+/// Genres as a list, or the single string and null older versions saved
+fn deserialize_genres<'de, D>(deserializer: D) -> Result<Vec<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Genres {
+        One(String),
+        Many(Vec<String>),
+    }
+
+    Ok(match Option::<Genres>::deserialize(deserializer)? {
+        Some(Genres::One(genre)) => vec![genre],
+        Some(Genres::Many(genres)) => genres,
+        None => Vec::new(),
+    })
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -45,13 +252,85 @@ pub struct MediaMetaData {
     pub artist: Option<String>,
     pub album: Option<String>,
     pub album_artist: Option<String>,
-    pub genre: Option<String>,
+    /// Every genre tag in file order, the first is the primary one. Read from the single
+    /// `genre` of libraries and playlists saved before version 3.
+    #[serde(default, alias = "genre", deserialize_with = "deserialize_genres")]
+    pub genres: Vec<String>,
     pub album_disc_number: Option<u32>,
     pub album_disc_count: Option<u32>,
     pub track_number: Option<u32>,
     pub track_count: Option<u32>,
     pub duration: Option<f32>,
     pub artwork_filename: Option<String>,
+    #[serde(default)]
+    pub bpm: Option<f64>,
+    #[serde(default)]
+    pub grouping: Option<String>,
+    #[serde(default)]
+    pub codec: Option<String>,
+    #[serde(default)]
+    pub container: Option<String>,
+    #[serde(default)]
+    pub bitrate: Option<u32>,
+    #[serde(default)]
+    pub sample_rate: Option<u32>,
+    #[serde(default)]
+    pub channels: Option<u32>,
+    #[serde(default)]
+    pub file_size: Option<u64>,
+    #[serde(default)]
+    pub play_count: u32,
+    #[serde(default)]
+    pub last_played: Option<String>,
+    #[serde(default)]
+    pub manual_gain_db: Option<f32>,
+    /// Chapter start times in milliseconds with their titles, empty when the file has no TOC
+    #[serde(default)]
+    pub chapters: Vec<(u64, String)>,
+    #[serde(default)]
+    pub favorite: bool,
+    /// ReplayGain track gain in dB, from tags or a loudness analysis
+    #[serde(default)]
+    pub replay_gain_track_db: Option<f64>,
+    #[serde(default)]
+    pub replay_gain_track_peak: Option<f64>,
+    /// Where a track split from a file by a cue sheet starts and ends in milliseconds, the
+    /// end is `None` for the last track
+    #[serde(default)]
+    pub cue_start_ms: Option<u64>,
+    #[serde(default)]
+    pub cue_end_ms: Option<u64>,
+    /// Sort name tags, such as "Beatles, The", used for ordering in place of the displayed name
+    #[serde(default)]
+    pub title_sort: Option<String>,
+    #[serde(default)]
+    pub artist_sort: Option<String>,
+    #[serde(default)]
+    pub album_sort: Option<String>,
+    #[serde(default)]
+    pub album_artist_sort: Option<String>,
+}
+
+/// Text a value is ordered by: its sort name tag when there is one, otherwise the value
+/// itself, without a leading "The", "A" or "An" when `ignore_articles` is set
+fn sort_key<'a>(
+    value: Option<&'a str>,
+    sort_name: Option<&'a str>,
+    ignore_articles: bool,
+) -> Option<&'a str> {
+    let sort_name = sort_name.map(str::trim).filter(|name| !name.is_empty());
+
+    match sort_name {
+        Some(sort_name) => Some(sort_name),
+        None if ignore_articles => value.map(strip_leading_article),
+        None => value,
+    }
+}
+
+/// Library key for a track of a cue sheet. A file can't also be a folder, so this never
+/// collides with a real file and the parent is always the audio file.
+pub fn cue_track_path(file: &Path, number: u32) -> PathBuf {
+    file.join(format!("cue-track-{:02}", number))
 }
 
 impl MediaMetaData {
@@ -62,13 +341,358 @@ impl MediaMetaData {
             artist: None,
             album: None,
             album_artist: None,
-            genre: None,
+            genres: Vec::new(),
             album_disc_number: None,
             album_disc_count: None,
             track_number: None,
             track_count: None,
             duration: None,
             artwork_filename: None,
+            bpm: None,
+            grouping: None,
+            codec: None,
+            container: None,
+            bitrate: None,
+            sample_rate: None,
+            channels: None,
+            file_size: None,
+            play_count: 0,
+            last_played: None,
+            manual_gain_db: None,
+            chapters: Vec::new(),
+            favorite: false,
+            replay_gain_track_db: None,
+            replay_gain_track_peak: None,
+            cue_start_ms: None,
+            cue_end_ms: None,
+            title_sort: None,
+            artist_sort: None,
+            album_sort: None,
+            album_artist_sort: None,
         }
     }
+
+    /// The first genre, for sorting and single-value fields
+    pub fn genre(&self) -> Option<&str> {
+        self.genres.first().map(String::as_str)
+    }
+
+    pub fn is_cue_track(&self) -> bool {
+        self.cue_start_ms.is_some()
+    }
+
+    /// The audio file to decode for the entry at `path`, cue tracks are keyed below theirs
+    pub fn media_file<'a>(&self, path: &'a Path) -> &'a Path {
+        if self.is_cue_track() {
+            path.parent().unwrap_or(path)
+        } else {
+            path
+        }
+    }
+
+    /// Whether `next` is the following track of the same album, including the first track of
+    /// the next disc. Untagged track numbers never match.
+    pub fn continues_album(&self, next: &MediaMetaData) -> bool {
+        let album = self.album.as_deref().map(str::trim).unwrap_or_default();
+        let next_album = next.album.as_deref().map(str::trim).unwrap_or_default();
+
+        if album.is_empty()
+            || album != next_album
+            || self.display_album_artist() != next.display_album_artist()
+        {
+            return false;
+        }
+
+        let (Some(track), Some(next_track)) = (self.track_number, next.track_number) else {
+            return false;
+        };
+        let disc = self.album_disc_number.unwrap_or(1);
+        let next_disc = next.album_disc_number.unwrap_or(1);
+
+        (next_disc == disc && next_track == track + 1) || (next_disc == disc + 1 && next_track == 1)
+    }
+
+    /// Album artist used for grouping and sorting. Falls back to the track artist so
+    /// tagged and untagged tracks of the same album end up together.
+    pub fn display_album_artist(&self) -> &str {
+        [self.album_artist.as_deref(), self.artist.as_deref()]
+            .into_iter()
+            .flatten()
+            .map(str::trim)
+            .find(|value| !value.is_empty())
+            .unwrap_or("Unknown Album Artist")
+    }
+
+    pub fn title_sort_key(&self, ignore_articles: bool) -> Option<&str> {
+        sort_key(
+            self.title.as_deref(),
+            self.title_sort.as_deref(),
+            ignore_articles,
+        )
+    }
+
+    pub fn artist_sort_key(&self, ignore_articles: bool) -> Option<&str> {
+        sort_key(
+            self.artist.as_deref(),
+            self.artist_sort.as_deref(),
+            ignore_articles,
+        )
+    }
+
+    pub fn album_sort_key(&self, ignore_articles: bool) -> Option<&str> {
+        sort_key(
+            self.album.as_deref(),
+            self.album_sort.as_deref(),
+            ignore_articles,
+        )
+    }
+
+    /// Sort key for `display_album_artist`, taken from whichever tag it's showing
+    pub fn album_artist_sort_key(&self, ignore_articles: bool) -> &str {
+        let uses_album_artist = self
+            .album_artist
+            .as_deref()
+            .is_some_and(|album_artist| !album_artist.trim().is_empty());
+        let sort_name = if uses_album_artist {
+            self.album_artist_sort.as_deref()
+        } else {
+            self.artist_sort.as_deref()
+        };
+
+        sort_key(
+            Some(self.display_album_artist()),
+            sort_name,
+            ignore_articles,
+        )
+        .unwrap_or_default()
+    }
+
+    pub fn record_play(&mut self) {
+        self.play_count = self.play_count.saturating_add(1);
+        self.last_played = Some(Local::now().to_rfc3339());
+    }
+
+    // Clear play statistics only, leaving tag data untouched
+    pub fn reset_play_stats(&mut self) {
+        self.play_count = 0;
+        self.last_played = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::TempDir;
+
+    // A library file of its own, removed with its folder when dropped
+    struct TempFile {
+        _dir: TempDir,
+        path: PathBuf,
+    }
+
+    impl TempFile {
+        fn new(name: &str) -> Self {
+            let dir = TempDir::new("library", name);
+            let path = dir.join(LIBRARY_FILENAME);
+            Self { _dir: dir, path }
+        }
+    }
+
+    fn with_artists(artist: Option<&str>, album_artist: Option<&str>) -> MediaMetaData {
+        MediaMetaData {
+            artist: artist.map(String::from),
+            album_artist: album_artist.map(String::from),
+            ..MediaMetaData::new()
+        }
+    }
+
+    #[test]
+    fn display_album_artist_prefers_album_artist() {
+        let metadata = with_artists(Some("Track Artist"), Some(" Album Artist "));
+        assert_eq!(metadata.display_album_artist(), "Album Artist");
+    }
+
+    #[test]
+    fn display_album_artist_falls_back_to_artist() {
+        let untagged = with_artists(Some("Track Artist"), None);
+        assert_eq!(untagged.display_album_artist(), "Track Artist");
+
+        let blank = with_artists(Some("Track Artist"), Some("  "));
+        assert_eq!(blank.display_album_artist(), "Track Artist");
+    }
+
+    #[test]
+    fn display_album_artist_without_artists() {
+        let metadata = with_artists(None, Some(""));
+        assert_eq!(metadata.display_album_artist(), "Unknown Album Artist");
+    }
+
+    fn with_id(id: &str) -> MediaMetaData {
+        MediaMetaData {
+            id: Some(id.to_string()),
+            ..MediaMetaData::new()
+        }
+    }
+
+    #[test]
+    fn resolve_duplicate_ids_gives_each_path_its_own_id() {
+        let mut library = Library::new();
+        for path in ["/music/c.flac", "/music/a.flac", "/music/b.flac"] {
+            library.media.insert(PathBuf::from(path), with_id("shared"));
+        }
+        library
+            .media
+            .insert(PathBuf::from("/music/d.flac"), with_id("unique"));
+
+        assert_eq!(library.resolve_duplicate_ids(), 2);
+
+        let ids: HashSet<_> = library
+            .media
+            .values()
+            .filter_map(|metadata| metadata.id.clone())
+            .collect();
+        assert_eq!(ids.len(), library.media.len());
+
+        // The first path keeps the id, so a rescan ends up with the same ids
+        assert_eq!(
+            library.media[Path::new("/music/a.flac")].id.as_deref(),
+            Some("shared")
+        );
+        assert_eq!(
+            library.media[Path::new("/music/d.flac")].id.as_deref(),
+            Some("unique")
+        );
+        assert_eq!(library.resolve_duplicate_ids(), 0);
+    }
+
+    #[test]
+    fn sort_key_prefers_sort_name() {
+        assert_eq!(
+            sort_key(Some("The Beatles"), Some(" Beatles, The "), true),
+            Some("Beatles, The")
+        );
+        assert_eq!(
+            sort_key(Some("The Beatles"), Some(""), false),
+            Some("The Beatles")
+        );
+    }
+
+    #[test]
+    fn sort_key_ignores_articles_only_when_asked() {
+        assert_eq!(sort_key(Some("The Cure"), None, true), Some("Cure"));
+        assert_eq!(sort_key(Some("The Cure"), None, false), Some("The Cure"));
+        assert_eq!(sort_key(None, None, true), None);
+    }
+
+    #[test]
+    fn load_missing_file_is_not_found() {
+        let file = TempFile::new("missing");
+
+        assert!(matches!(
+            Library::load_from(&file.path),
+            Err(LibraryError::NotFound)
+        ));
+    }
+
+    #[test]
+    fn load_corrupt_file_is_json_error() {
+        let file = TempFile::new("corrupt");
+        fs::write(&file.path, r#"{"version": 3, "media": {"/music/01.flac": "#).unwrap();
+
+        assert!(matches!(
+            Library::load_from(&file.path),
+            Err(LibraryError::Json(_))
+        ));
+    }
+
+    #[test]
+    fn load_version_1_media_map() {
+        let file = TempFile::new("version-1");
+        fs::write(
+            &file.path,
+            r#"{"/music/01.flac": {"id": "a", "title": "Blue Train", "genre": "Jazz"}}"#,
+        )
+        .unwrap();
+
+        let library = Library::load_from(&file.path).unwrap();
+        let metadata = &library.media[Path::new("/music/01.flac")];
+
+        assert_eq!(metadata.id.as_deref(), Some("a"));
+        assert_eq!(metadata.title.as_deref(), Some("Blue Train"));
+        assert_eq!(metadata.genres, ["Jazz"]);
+    }
+
+    #[test]
+    fn genres_read_the_single_genre_of_older_versions() {
+        let metadata: MediaMetaData = serde_json::from_str(r#"{"genre": "Jazz"}"#).unwrap();
+        assert_eq!(metadata.genres, ["Jazz"]);
+
+        let metadata: MediaMetaData = serde_json::from_str(r#"{"genre": null}"#).unwrap();
+        assert!(metadata.genres.is_empty());
+
+        let metadata: MediaMetaData = serde_json::from_str("{}").unwrap();
+        assert!(metadata.genres.is_empty());
+    }
+
+    #[test]
+    fn genres_keep_two_genres() {
+        let metadata = MediaMetaData {
+            genres: vec!["Jazz".to_string(), "Hard Bop".to_string()],
+            ..MediaMetaData::new()
+        };
+
+        let json = serde_json::to_string(&metadata).unwrap();
+        let read: MediaMetaData = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(read.genres, ["Jazz", "Hard Bop"]);
+        assert_eq!(read.genre(), Some("Jazz"));
+    }
+
+    #[test]
+    fn load_newer_version_is_refused() {
+        let file = TempFile::new("newer");
+        fs::write(
+            &file.path,
+            format!(r#"{{"version": {}, "media": {{}}}}"#, LIBRARY_VERSION + 1),
+        )
+        .unwrap();
+
+        assert!(matches!(
+            Library::load_from(&file.path),
+            Err(LibraryError::InvalidData(_))
+        ));
+    }
+
+    #[test]
+    fn load_error_keeps_position() {
+        let file = TempFile::new("position");
+        fs::write(
+            &file.path,
+            "{\"version\": 3,\n\"media\": {\"/music/01.flac\": 5}}",
+        )
+        .unwrap();
+
+        let Err(LibraryError::Json(err)) = Library::load_from(&file.path) else {
+            panic!("expected a JSON error");
+        };
+        assert_eq!(err.line(), 2);
+    }
+
+    #[test]
+    fn back_up_file_keeps_contents() {
+        let file = TempFile::new("backup");
+        fs::write(&file.path, "not json").unwrap();
+
+        let backup = Library::back_up_file(&file.path).unwrap();
+
+        assert!(!file.path.exists());
+        assert_eq!(fs::read_to_string(&backup).unwrap(), "not json");
+        assert!(
+            backup
+                .file_name()
+                .unwrap()
+                .to_string_lossy()
+                .starts_with("library.json.bak-")
+        );
+    }
 }
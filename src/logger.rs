@@ -0,0 +1,81 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use crate::constants::{LOG_BUFFER_CAPACITY, LOG_ENV_VAR};
+use chrono::Local;
+use log::{Level, LevelFilter, Log, Metadata, Record};
+use std::collections::VecDeque;
+use std::str::FromStr;
+use std::sync::Mutex;
+
+/// Writes records to stderr and keeps the most recent ones for the log viewer
+struct Logger {
+    lines: Mutex<VecDeque<String>>,
+}
+
+static LOGGER: Logger = Logger {
+    lines: Mutex::new(VecDeque::new()),
+};
+
+impl Log for Logger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        // Dependencies only get through with warnings and errors, their debug output would
+        // bury ours
+        metadata.level() <= log::max_level()
+            && (metadata.target().starts_with(env!("CARGO_CRATE_NAME"))
+                || metadata.level() <= Level::Warn)
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+
+        let line = format!(
+            "{} {:<5} {}: {}",
+            Local::now().format("%H:%M:%S%.3f"),
+            record.level(),
+            record.target(),
+            record.args()
+        );
+        eprintln!("{line}");
+
+        let Ok(mut lines) = self.lines.lock() else {
+            return;
+        };
+        if lines.len() == LOG_BUFFER_CAPACITY {
+            lines.pop_front();
+        }
+        lines.push_back(line);
+    }
+
+    fn flush(&self) {}
+}
+
+/// Install the logger. A level set in the environment wins over the configured one.
+pub fn init(level: LevelFilter) {
+    if let Err(err) = log::set_logger(&LOGGER) {
+        eprintln!("Failed to install logger: {err}");
+        return;
+    }
+
+    set_level(level);
+}
+
+pub fn set_level(level: LevelFilter) {
+    log::set_max_level(env_level().unwrap_or(level));
+}
+
+/// `ETHEREAL_WAVES_LOG=debug` and so on, for logs from before the config is read
+fn env_level() -> Option<LevelFilter> {
+    let value = std::env::var(LOG_ENV_VAR).ok()?;
+    LevelFilter::from_str(value.trim()).ok()
+}
+
+/// Buffered log lines, oldest first
+pub fn recent() -> Vec<String> {
+    LOGGER
+        .lines
+        .lock()
+        .map(|lines| lines.iter().cloned().collect())
+        .unwrap_or_default()
+}
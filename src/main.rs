@@ -3,21 +3,34 @@
 mod app;
 mod config;
 mod constants;
+mod cue;
+mod export;
+mod format;
 mod helpers;
 mod i18n;
 mod image_store;
 mod key_bind;
+mod launcher;
 mod library;
+mod logger;
+mod mounts;
 mod mpris;
 mod page;
 mod playback_state;
 mod player;
 mod playlist;
+mod power;
+mod scan_report;
 mod services;
+mod silence;
+#[cfg(test)]
+mod test_helpers;
 mod ui;
+mod waveform;
 
-use app::Flags;
-use config::{Config, State};
+use app::{Flags, OpenRequest};
+use config::{Config, LogLevel, State};
+use constants::{MIN_WINDOW_HEIGHT, MIN_WINDOW_WIDTH};
 use cosmic::{
     app::Settings,
     iced::{Limits, Size},
@@ -30,12 +43,20 @@ fn main() -> cosmic::iced::Result {
     // Enable localizations to be applied.
     i18n::init(&requested_languages);
 
+    // Installed before the config is read so problems loading it are logged
+    logger::init(LogLevel::default().filter());
+
     let (config_handler, config) = Config::load();
+    logger::set_level(config.log_level.filter());
     let (state_handler, state) = State::load();
 
     // Settings for configuring the application window and iced runtime.
     let mut settings: Settings = Settings::default();
-    settings = settings.size_limits(Limits::NONE.min_width(360.0).min_height(180.0));
+    settings = settings.size_limits(
+        Limits::NONE
+            .min_width(MIN_WINDOW_WIDTH)
+            .min_height(MIN_WINDOW_HEIGHT),
+    );
     settings = settings.theme(config.app_theme.theme());
     settings = settings.size(Size::new(state.window_width, state.window_height));
 
@@ -43,8 +64,17 @@ fn main() -> cosmic::iced::Result {
         config_handler,
         state_handler,
         state,
+        open: OpenRequest::from_args(std::env::args().skip(1)),
     };
 
     // Starts the application's event loop with `()` as the application's flags.
+    // With single instance support, opening a file while running hands it to that window.
+    // A running window can't read this process's stdin or descriptors, and won't open pipes
+    // another process names, so streams always play here.
+    #[cfg(feature = "single-instance")]
+    if !flags.open.as_ref().is_some_and(|open| open.stream) {
+        return cosmic::app::run_single_instance::<app::AppModel>(settings, flags);
+    }
+
     cosmic::app::run::<app::AppModel>(settings, flags)
 }
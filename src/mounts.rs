@@ -0,0 +1,110 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use crate::constants::{MOUNT_INFO_PATH, NETWORK_FILESYSTEMS};
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// How the filesystem holding a library folder is mounted
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct MountStatus {
+    pub read_only: bool,
+    /// NFS, SMB, SSHFS and the like, reading from them is slower than a local disk
+    pub network: bool,
+}
+
+struct Mount {
+    mount_point: PathBuf,
+    fs_type: String,
+    read_only: bool,
+}
+
+/// Look up the mount of each folder in the kernel's mount table. Folders that can't be
+/// resolved, like those on an unplugged drive, are left out. Resolving a folder on a network
+/// mount that stopped responding blocks, so this belongs on a blocking thread.
+pub fn mount_statuses<'a>(
+    paths: impl IntoIterator<Item = &'a String>,
+) -> HashMap<String, MountStatus> {
+    let mounts: Vec<Mount> = match fs::read_to_string(MOUNT_INFO_PATH) {
+        Ok(content) => content.lines().filter_map(parse_mount).collect(),
+        Err(err) => {
+            log::warn!("Failed to read the mount table: {}", err);
+            return HashMap::new();
+        }
+    };
+
+    paths
+        .into_iter()
+        .filter_map(|path| {
+            let resolved = fs::canonicalize(path).ok()?;
+            let mount = mount_of(&mounts, &resolved)?;
+            let status = MountStatus {
+                read_only: mount.read_only,
+                network: NETWORK_FILESYSTEMS.contains(&mount.fs_type.as_str()),
+            };
+
+            Some((path.clone(), status))
+        })
+        .collect()
+}
+
+// The deepest mount point containing the path, of mounts stacked on the same point the last
+// one is visible
+fn mount_of<'a>(mounts: &'a [Mount], path: &Path) -> Option<&'a Mount> {
+    mounts
+        .iter()
+        .filter(|mount| path.starts_with(&mount.mount_point))
+        .max_by_key(|mount| mount.mount_point.components().count())
+}
+
+// A mountinfo line looks like
+// `36 35 98:0 /mnt1 /mnt/parent rw,noatime master:1 - ext3 /dev/root rw,errors=continue`,
+// the mount point is the fifth field and the filesystem type follows the separator. Read-only
+// shows in either the mount's or the filesystem's options.
+fn parse_mount(line: &str) -> Option<Mount> {
+    let (mount_fields, fs_fields) = line.split_once(" - ")?;
+    let mut mount_fields = mount_fields.split(' ');
+    let mount_point = mount_fields.nth(4)?;
+    let mount_options = mount_fields.next()?;
+    let mut fs_fields = fs_fields.split(' ');
+    let fs_type = fs_fields.next()?;
+    let fs_options = fs_fields.nth(1).unwrap_or_default();
+
+    let read_only = [mount_options, fs_options]
+        .iter()
+        .any(|options| options.split(',').any(|option| option == "ro"));
+
+    Some(Mount {
+        mount_point: PathBuf::from(unescape(mount_point)),
+        fs_type: fs_type.to_string(),
+        read_only,
+    })
+}
+
+// Spaces and other separators in mount points are written as octal escapes like \040
+fn unescape(field: &str) -> String {
+    let mut unescaped = String::with_capacity(field.len());
+    let mut rest = field;
+
+    while let Some(index) = rest.find('\\') {
+        unescaped.push_str(&rest[..index]);
+        match rest
+            .get(index + 1..index + 4)
+            .and_then(|digits| u8::from_str_radix(digits, 8).ok())
+        {
+            Some(byte) => {
+                unescaped.push(byte as char);
+                rest = &rest[index + 4..];
+            }
+            None => {
+                unescaped.push('\\');
+                rest = &rest[index + 1..];
+            }
+        }
+    }
+
+    unescaped.push_str(rest);
+    unescaped
+}
@@ -2,6 +2,7 @@
 use crate::playback_state::PlaybackStatus;
 use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
+use tokio::sync::Notify;
 use tokio::sync::mpsc::UnboundedSender;
 use zbus::interface;
 
@@ -24,40 +25,49 @@ impl Default for PlaybackStatus {
 pub struct MediaPlayer2Player {
     pub tx: UnboundedSender<MprisCommand>,
     pub state: Arc<Mutex<MprisState>>, // Changed from playback_status
+    // Wakes the app when it is idling without a tick subscription
+    pub wake: Arc<Notify>,
+}
+
+impl MediaPlayer2Player {
+    fn send(&self, command: MprisCommand) {
+        let _ = self.tx.send(command);
+        self.wake.notify_one();
+    }
 }
 
 #[interface(name = "org.mpris.MediaPlayer2.Player")]
 impl MediaPlayer2Player {
     fn play(&self) {
-        let _ = self.tx.send(MprisCommand::Play);
+        self.send(MprisCommand::Play);
     }
 
     fn pause(&self) {
-        let _ = self.tx.send(MprisCommand::Pause);
+        self.send(MprisCommand::Pause);
     }
 
     fn play_pause(&self) {
-        let _ = self.tx.send(MprisCommand::PlayPause);
+        self.send(MprisCommand::PlayPause);
     }
 
     fn next(&self) {
-        let _ = self.tx.send(MprisCommand::Next);
+        self.send(MprisCommand::Next);
     }
 
     fn previous(&self) {
-        let _ = self.tx.send(MprisCommand::Previous);
+        self.send(MprisCommand::Previous);
     }
 
     fn stop(&self) {
-        let _ = self.tx.send(MprisCommand::Stop);
+        self.send(MprisCommand::Stop);
     }
 
     fn seek(&self, offset: i64) {
-        let _ = self.tx.send(MprisCommand::Seek(offset));
+        self.send(MprisCommand::Seek(offset));
     }
 
     fn set_position(&self, _track_id: zbus::zvariant::ObjectPath<'_>, position: i64) {
-        let _ = self.tx.send(MprisCommand::SetPosition(position));
+        self.send(MprisCommand::SetPosition(position));
     }
 
     fn open_uri(&self, _uri: String) {}
@@ -80,7 +90,7 @@ impl MediaPlayer2Player {
 
     #[zbus(property)]
     fn set_loop_status(&self, status: String) {
-        let _ = self.tx.send(MprisCommand::SetLoopStatus(status));
+        self.send(MprisCommand::SetLoopStatus(status));
     }
 
     #[zbus(property)]
@@ -90,7 +100,7 @@ impl MediaPlayer2Player {
 
     #[zbus(property)]
     fn set_shuffle(&self, shuffle: bool) {
-        let _ = self.tx.send(MprisCommand::SetShuffle(shuffle));
+        self.send(MprisCommand::SetShuffle(shuffle));
     }
 
     #[zbus(property)]
@@ -105,7 +115,7 @@ impl MediaPlayer2Player {
 
     #[zbus(property)]
     fn set_volume(&self, volume: f64) {
-        let _ = self.tx.send(MprisCommand::SetVolume(volume));
+        self.send(MprisCommand::SetVolume(volume));
     }
 
     #[zbus(property)]
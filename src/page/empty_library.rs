@@ -26,3 +26,26 @@ pub fn content<'a>() -> widget::Column<'a, Message> {
         .width(Length::Fill)
         .align_x(Alignment::Center)
 }
+
+/// Centered notice for views other than an empty library, with an optional action button
+pub fn notice<'a>(
+    message: String,
+    action: Option<(String, Message)>,
+) -> widget::Column<'a, Message> {
+    let cosmic_theme::Spacing {
+        space_xxs, space_s, ..
+    } = theme::active().cosmic().spacing;
+
+    let mut column = widget::column()
+        .push(widget::text(message))
+        .spacing(space_s)
+        .padding(space_xxs)
+        .width(Length::Fill)
+        .align_x(Alignment::Center);
+
+    if let Some((label, message)) = action {
+        column = column.push(widget::button::standard(label).on_press(message));
+    }
+
+    column
+}
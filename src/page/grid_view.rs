@@ -4,6 +4,7 @@ use crate::app::{AppModel, GridCardModel, Message, SortBy, SortDirection, TrackD
 use crate::config::GridGroupBy;
 use crate::constants::*;
 use crate::fl;
+use crate::image_store::ImageStore;
 use cosmic::{
     Element, cosmic_theme,
     iced::{
@@ -351,6 +352,41 @@ fn grid_card<'a>(
         info_row = info_row.push(widget::text(duration_text.clone()));
     }
 
+    // Album cards can play or shuffle the whole album directly, or show it in the library
+    if app.config.grid_group_by == GridGroupBy::Album {
+        let view_playlist = app
+            .view_playlist
+            .and_then(|id| app.playlist_service.get(id).ok());
+        let album_track_id = view_playlist
+            .and_then(|playlist| playlist.tracks().get(primary_index))
+            .and_then(|track| track.metadata.id.clone());
+
+        if let Some(album_track_id) = album_track_id {
+            info_row = info_row
+                .push(album_action_button(
+                    "media-playback-start-symbolic",
+                    fl!("play"),
+                    Message::PlayAlbum(album_track_id.clone()),
+                ))
+                .push(album_action_button(
+                    "media-playlist-shuffle-symbolic",
+                    fl!("shuffle"),
+                    Message::ShuffleAlbum(album_track_id.clone()),
+                ))
+                .push_maybe(
+                    view_playlist
+                        .is_some_and(|playlist| !playlist.is_library())
+                        .then(|| {
+                            album_action_button(
+                                "find-location-symbolic",
+                                fl!("reveal-in-library"),
+                                Message::RevealInLibrary(album_track_id),
+                            )
+                        }),
+                );
+        }
+    }
+
     let card_contents = widget::column()
         .width(Length::Fixed(card_width))
         .spacing(GRID_CARD_CONTENT_SPACING as u16)
@@ -446,6 +482,21 @@ fn grid_card<'a>(
         .into()
 }
 
+fn album_action_button<'a>(
+    icon_name: &'static str,
+    label: String,
+    message: Message,
+) -> Element<'a, Message> {
+    widget::tooltip(
+        widget::button::icon(widget::icon::from_name(icon_name).size(GRID_STATUS_ICON_SIZE))
+            .padding(0)
+            .on_press(message),
+        widget::text(label),
+        widget::tooltip::Position::Bottom,
+    )
+    .into()
+}
+
 fn artwork_element<'a>(
     app: &'a AppModel,
     artwork_filename: Option<&String>,
@@ -453,30 +504,41 @@ fn artwork_element<'a>(
     has_available_track: bool,
 ) -> Element<'a, Message> {
     if let Some(original_artwork_filename) = artwork_filename {
+        // Decode at roughly the displayed size instead of full resolution
+        let decode_size = Some(ImageStore::decode_size(artwork_size));
+
         // Prefer the in-memory handle before checking the filesystem. This lets already-loaded
         // artwork stay visible even if the backing cache file was deleted after it was loaded.
         let preferred_artwork_filename = app
             .config
             .grid_artwork_size
             .cache_filename(original_artwork_filename);
-        if let Some(handle) = app.image_store.get(&preferred_artwork_filename) {
+        if let Some(handle) = app
+            .image_store
+            .get_sized(&preferred_artwork_filename, decode_size)
+        {
             return artwork_image_element(handle, artwork_size);
         }
 
         let preferred_exists = app.image_store.exists(&preferred_artwork_filename);
         if preferred_exists {
-            app.image_store.request(preferred_artwork_filename.clone());
+            app.image_store
+                .request_sized(preferred_artwork_filename.clone(), decode_size);
         }
 
         let preferred_is_original =
             preferred_artwork_filename == original_artwork_filename.as_str();
         if !preferred_is_original {
-            if let Some(handle) = app.image_store.get(original_artwork_filename) {
+            if let Some(handle) = app
+                .image_store
+                .get_sized(original_artwork_filename, decode_size)
+            {
                 return artwork_image_element(handle, artwork_size);
             }
 
             if !preferred_exists && app.image_store.exists(original_artwork_filename) {
-                app.image_store.request(original_artwork_filename.clone());
+                app.image_store
+                    .request_sized(original_artwork_filename.clone(), decode_size);
             }
         }
     }
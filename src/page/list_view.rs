@@ -4,11 +4,10 @@ use crate::app::{AppModel, Message, SortBy, TrackDropData};
 use crate::config::ListColumn;
 use crate::constants::*;
 use crate::fl;
-use crate::helpers::{format_optional_duration, optional_display};
-use crate::playlist::Track;
+use crate::page::track_row::{TrackRowLayout, button_style, column_width, track_row};
 use cosmic::{
     cosmic_theme,
-    iced::{Alignment, Color, Length, clipboard::dnd::DndAction},
+    iced::{Alignment, Length, clipboard::dnd::DndAction},
     iced_core::widget::Tree,
     theme, widget,
 };
@@ -59,7 +58,8 @@ pub fn content<'a>(app: &AppModel) -> widget::Column<'a, Message> {
             widget::text::heading("#")
                 .align_x(Alignment::End)
                 .width(Length::Fixed(view_model.number_column_width)),
-        );
+        )
+        .push(widget::space::horizontal().width(Length::Fixed(view_model.icon_column_width)));
 
     for column in &visible_columns {
         header_row = header_row.push(list_column_header(
@@ -83,6 +83,12 @@ pub fn content<'a>(app: &AppModel) -> widget::Column<'a, Message> {
 
     let mut count: u32 = view_model.list_start as u32 + 1;
 
+    let row_layout = TrackRowLayout {
+        view_model: &view_model,
+        columns: &visible_columns,
+        track_number_column_width,
+    };
+
     let selected_track_ids = Arc::clone(&view_model.selected_track_ids);
     let selected_count = selected_track_ids.len();
 
@@ -91,74 +97,19 @@ pub fn content<'a>(app: &AppModel) -> widget::Column<'a, Message> {
         let track_id = track.instance_id();
         let is_playing_track = app.is_track_playing(track, view_model.is_playing_playlist);
 
-        let mut row_element = widget::row()
-            .spacing(space_xxs)
-            .height(Length::Fixed(view_model.row_height));
-
-        // Play icon column
-        if is_playing_track {
-            row_element = row_element.push(
-                widget::container(
-                    widget::icon::from_name("media-playback-start-symbolic").size(16),
-                )
-                .width(Length::Fixed(view_model.icon_column_width))
-                .align_x(Alignment::Center)
-                .align_y(Alignment::Center)
-                .height(view_model.row_height),
-            );
-        } else {
-            let is_in_library = app.library.media.contains_key(&track.path);
-
-            if !is_in_library {
-                // Track is not in library, show indicator
-                let icon_with_indicator = widget::row()
-                    .spacing(2)
-                    .align_y(Alignment::Center)
-                    .push(widget::icon::from_name("help-about-symbolic").size(16));
-
-                row_element = row_element.push(
-                    widget::container(icon_with_indicator)
-                        .width(Length::Fixed(view_model.icon_column_width))
-                        .align_x(Alignment::Center)
-                        .align_y(Alignment::Center)
-                        .height(view_model.row_height),
-                );
-            } else {
-                row_element = row_element.push(
-                    widget::space::horizontal().width(Length::Fixed(view_model.icon_column_width)),
-                );
-            }
-        }
-
-        // Row number
-        row_element = row_element.push(
-            widget::container(
-                widget::text(count.to_string())
-                    .width(Length::Fixed(view_model.number_column_width))
-                    .align_x(Alignment::End)
-                    .align_y(view_model.row_align)
-                    .height(view_model.row_height),
-            )
-            .clip(true),
+        let row_button = track_row(
+            track,
+            count as usize,
+            is_playing_track,
+            // Read from the library, playlist copies of the metadata aren't kept in sync
+            app.library
+                .media
+                .get(&track.path)
+                .map(|metadata| metadata.favorite),
+            &row_layout,
+            Message::ChangeTrack(playlist_index),
         );
 
-        for column in &visible_columns {
-            row_element = row_element.push(list_column_cell(
-                track,
-                &view_model,
-                *column,
-                track_number_column_width,
-            ));
-        }
-
-        row_element = row_element.width(Length::Fill);
-
-        let row_button = widget::button::custom(row_element)
-            .class(button_style(track.selected, false))
-            .on_press_down(Message::ChangeTrack(playlist_index))
-            .padding(0)
-            .width(Length::Fill);
-
         let row_mouse =
             widget::mouse_area(row_button).on_release(Message::ListSelectRow(playlist_index));
 
@@ -236,6 +187,10 @@ pub fn content<'a>(app: &AppModel) -> widget::Column<'a, Message> {
         .push(rows)
         .push(widget::space::horizontal().width(space_xxs));
 
+    // Rows take their own clicks, a double-click on the space below them plays the view
+    let scrollable_contents =
+        widget::mouse_area(scrollable_contents).on_double_click(Message::PlayCurrentView);
+
     let scroller = widget::scrollable(scrollable_contents)
         .id(app.list_scroll_id.clone())
         .width(Length::Fill)
@@ -255,7 +210,7 @@ fn list_column_header<'a>(
     spacing: u16,
 ) -> cosmic::Element<'a, Message> {
     let label = list_column_heading(column, track_number_label);
-    let width = list_column_width(column, track_number_column_width);
+    let width = column_width(column, track_number_column_width);
 
     match column.sort_by() {
         Some(sort_by) => create_sort_button(
@@ -274,75 +229,6 @@ fn list_column_header<'a>(
     }
 }
 
-fn list_column_cell<'a>(
-    track: &Track,
-    view_model: &crate::app::ListViewModel,
-    column: ListColumn,
-    track_number_column_width: f32,
-) -> cosmic::Element<'a, Message> {
-    match column {
-        ListColumn::TrackNumber => compact_text_cell(
-            optional_display(track.metadata.track_number),
-            list_column_width(column, track_number_column_width),
-            view_model,
-        ),
-        ListColumn::Title => fill_text_cell(
-            track
-                .metadata
-                .title
-                .clone()
-                .unwrap_or_else(|| track.path.to_string_lossy().to_string()),
-            list_column_width(column, track_number_column_width),
-            view_model,
-        ),
-        ListColumn::Album => fill_text_cell(
-            track.metadata.album.clone().unwrap_or_default(),
-            list_column_width(column, track_number_column_width),
-            view_model,
-        ),
-        ListColumn::Artist => fill_text_cell(
-            track.metadata.artist.clone().unwrap_or_default(),
-            list_column_width(column, track_number_column_width),
-            view_model,
-        ),
-        ListColumn::AlbumArtist => fill_text_cell(
-            track.metadata.album_artist.clone().unwrap_or_default(),
-            list_column_width(column, track_number_column_width),
-            view_model,
-        ),
-        ListColumn::TrackTotal => compact_text_cell(
-            optional_display(track.metadata.track_count),
-            list_column_width(column, track_number_column_width),
-            view_model,
-        ),
-        ListColumn::DiscNumber => compact_text_cell(
-            optional_display(track.metadata.album_disc_number),
-            list_column_width(column, track_number_column_width),
-            view_model,
-        ),
-        ListColumn::DiscTotal => compact_text_cell(
-            optional_display(track.metadata.album_disc_count),
-            list_column_width(column, track_number_column_width),
-            view_model,
-        ),
-        ListColumn::Genre => fill_text_cell(
-            track.metadata.genre.clone().unwrap_or_default(),
-            list_column_width(column, track_number_column_width),
-            view_model,
-        ),
-        ListColumn::FilePath => fill_text_cell(
-            track.path.to_string_lossy().to_string(),
-            list_column_width(column, track_number_column_width),
-            view_model,
-        ),
-        ListColumn::Duration => compact_text_cell(
-            format_optional_duration(track.metadata.duration),
-            list_column_width(column, track_number_column_width),
-            view_model,
-        ),
-    }
-}
-
 fn list_column_heading(column: ListColumn, track_number_label: &str) -> String {
     match column {
         ListColumn::TrackNumber => track_number_label.to_string(),
@@ -356,57 +242,10 @@ fn list_column_heading(column: ListColumn, track_number_label: &str) -> String {
         ListColumn::Genre => fl!("genre"),
         ListColumn::FilePath => fl!("file-path"),
         ListColumn::Duration => fl!("duration"),
+        ListColumn::Bpm => fl!("bpm"),
     }
 }
 
-fn list_column_width(column: ListColumn, track_number_column_width: f32) -> Length {
-    match column {
-        ListColumn::TrackNumber => Length::Fixed(track_number_column_width),
-        ListColumn::Title | ListColumn::FilePath => Length::FillPortion(2),
-        ListColumn::TrackTotal | ListColumn::DiscNumber | ListColumn::DiscTotal => {
-            Length::Fixed(COMPACT_COLUMN_WIDTH)
-        }
-        ListColumn::Duration => Length::Fixed(DURATION_COLUMN_WIDTH),
-        ListColumn::Album | ListColumn::Artist | ListColumn::AlbumArtist | ListColumn::Genre => {
-            Length::FillPortion(1)
-        }
-    }
-}
-
-fn fill_text_cell<'a>(
-    value: String,
-    width: Length,
-    view_model: &crate::app::ListViewModel,
-) -> cosmic::Element<'a, Message> {
-    widget::container(
-        widget::text(value)
-            .align_y(view_model.row_align)
-            .height(view_model.row_height)
-            .wrapping(view_model.wrapping)
-            .width(Length::Fill),
-    )
-    .width(width)
-    .clip(true)
-    .into()
-}
-
-fn compact_text_cell<'a>(
-    value: String,
-    width: Length,
-    view_model: &crate::app::ListViewModel,
-) -> cosmic::Element<'a, Message> {
-    widget::container(
-        widget::text(value)
-            .align_x(Alignment::End)
-            .align_y(view_model.row_align)
-            .height(view_model.row_height)
-            .width(Length::Fill),
-    )
-    .width(width)
-    .clip(true)
-    .into()
-}
-
 // Helper function for sort buttons
 fn create_sort_button<'a>(
     label: String,
@@ -431,46 +270,3 @@ fn create_sort_button<'a>(
         .padding(0)
         .width(width)
 }
-
-// Row theming
-fn button_style(selected: bool, heading: bool) -> theme::Button {
-    theme::Button::Custom {
-        active: Box::new(move |_focus, theme| button_appearance(theme, selected, heading, false)),
-        disabled: Box::new(move |theme| button_appearance(theme, selected, heading, false)),
-        hovered: Box::new(move |_focus, theme| button_appearance(theme, selected, heading, true)),
-        pressed: Box::new(move |_focus, theme| button_appearance(theme, selected, heading, false)),
-    }
-}
-
-fn button_appearance(
-    theme: &theme::Theme,
-    selected: bool,
-    heading: bool,
-    hovered: bool,
-) -> widget::button::Style {
-    let cosmic = theme.cosmic();
-    let mut appearance = widget::button::Style::new();
-
-    if heading {
-        appearance.background = Some(Color::TRANSPARENT.into());
-        appearance.icon_color = Some(Color::from(cosmic.on_bg_color()));
-        appearance.text_color = Some(Color::from(cosmic.on_bg_color()));
-    } else if selected {
-        appearance.background = Some(Color::from(cosmic.accent_color()).into());
-        appearance.icon_color = Some(Color::from(cosmic.on_accent_color()));
-        appearance.text_color = Some(Color::from(cosmic.on_accent_color()));
-    } else if hovered {
-        appearance.background = Some(Color::from(cosmic.bg_component_color()).into());
-        appearance.icon_color = Some(Color::from(cosmic.on_bg_component_color()));
-        appearance.text_color = Some(Color::from(cosmic.on_bg_component_color()));
-    } else {
-        appearance.background = Some(Color::TRANSPARENT.into());
-        appearance.icon_color = Some(Color::from(cosmic.on_bg_color()));
-        appearance.text_color = Some(Color::from(cosmic.on_bg_color()));
-    }
-    appearance.outline_width = 0.0;
-    appearance.border_width = 0.0;
-    appearance.border_radius = cosmic.radius_xs().into();
-
-    appearance
-}
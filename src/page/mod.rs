@@ -4,3 +4,4 @@ pub mod empty_library;
 pub mod grid_view;
 pub mod list_view;
 pub mod loading;
+pub mod track_row;
@@ -0,0 +1,300 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use crate::app::{ListViewModel, Message};
+use crate::config::ListColumn;
+use crate::constants::*;
+use crate::format::format_optional_duration;
+use crate::helpers::{display_text, non_empty_text, optional_display, path_display_name};
+use crate::playlist::Track;
+use cosmic::{
+    cosmic_theme,
+    iced::{Alignment, Color, Length},
+    theme, widget,
+};
+
+/// Column layout shared by every row in one rendering of a track list
+pub struct TrackRowLayout<'a> {
+    pub view_model: &'a ListViewModel,
+    pub columns: &'a [ListColumn],
+    pub track_number_column_width: f32,
+}
+
+/// A single track row: status icon, row number, favorite toggle and the requested columns,
+/// wrapped in a button that emits `on_activate` when pressed. `is_favorite` is `None` for
+/// tracks that aren't in the library and so can't be favorited.
+pub fn track_row<'a>(
+    track: &Track,
+    number: usize,
+    is_playing: bool,
+    is_favorite: Option<bool>,
+    layout: &TrackRowLayout,
+    on_activate: Message,
+) -> widget::Button<'a, Message> {
+    let is_in_library = is_favorite.is_some();
+    let cosmic_theme::Spacing { space_xxs, .. } = theme::active().cosmic().spacing;
+    let view_model = layout.view_model;
+
+    let mut row_element = widget::row()
+        .spacing(space_xxs)
+        .height(Length::Fixed(view_model.row_height));
+
+    // Play icon column
+    if is_playing {
+        row_element = row_element.push(
+            widget::container(widget::icon::from_name("media-playback-start-symbolic").size(16))
+                .width(Length::Fixed(view_model.icon_column_width))
+                .align_x(Alignment::Center)
+                .align_y(Alignment::Center)
+                .height(view_model.row_height),
+        );
+    } else if !is_in_library {
+        // Track is not in library, show indicator
+        let icon_with_indicator = widget::row()
+            .spacing(2)
+            .align_y(Alignment::Center)
+            .push(widget::icon::from_name("help-about-symbolic").size(16));
+
+        row_element = row_element.push(
+            widget::container(icon_with_indicator)
+                .width(Length::Fixed(view_model.icon_column_width))
+                .align_x(Alignment::Center)
+                .align_y(Alignment::Center)
+                .height(view_model.row_height),
+        );
+    } else {
+        row_element = row_element
+            .push(widget::space::horizontal().width(Length::Fixed(view_model.icon_column_width)));
+    }
+
+    // Row number
+    row_element = row_element.push(
+        widget::container(
+            widget::text(number.to_string())
+                .width(Length::Fixed(view_model.number_column_width))
+                .align_x(Alignment::End)
+                .align_y(view_model.row_align)
+                .height(view_model.row_height),
+        )
+        .clip(true),
+    );
+
+    // Favorite toggle
+    let favorite_cell: cosmic::Element<'a, Message> = match (is_favorite, track.metadata.id.clone())
+    {
+        (Some(is_favorite), Some(id)) => widget::button::icon(
+            widget::icon::from_name(if is_favorite {
+                "starred-symbolic"
+            } else {
+                "non-starred-symbolic"
+            })
+            .size(16),
+        )
+        .on_press(Message::ToggleFavorite(id))
+        .padding(0)
+        .into(),
+        _ => widget::space::horizontal().into(),
+    };
+    row_element = row_element.push(
+        widget::container(favorite_cell)
+            .width(Length::Fixed(view_model.icon_column_width))
+            .align_x(Alignment::Center)
+            .align_y(Alignment::Center)
+            .height(view_model.row_height),
+    );
+
+    for column in layout.columns {
+        row_element = row_element.push(column_cell(
+            track,
+            view_model,
+            *column,
+            layout.track_number_column_width,
+        ));
+    }
+
+    widget::button::custom(row_element.width(Length::Fill))
+        .class(button_style(track.selected, false))
+        .on_press_down(on_activate)
+        .padding(0)
+        .width(Length::Fill)
+}
+
+fn column_cell<'a>(
+    track: &Track,
+    view_model: &ListViewModel,
+    column: ListColumn,
+    track_number_column_width: f32,
+) -> cosmic::Element<'a, Message> {
+    match column {
+        ListColumn::TrackNumber => compact_text_cell(
+            optional_display(track.metadata.track_number),
+            column_width(column, track_number_column_width),
+            view_model,
+        ),
+        ListColumn::Title => fill_text_cell(
+            non_empty_text(track.metadata.title.as_deref()).unwrap_or_else(|| {
+                if view_model.path_fallbacks {
+                    path_display_name(track.metadata.media_file(&track.path))
+                } else {
+                    track.path.to_string_lossy().to_string()
+                }
+            }),
+            column_width(column, track_number_column_width),
+            view_model,
+        ),
+        ListColumn::Album => fill_text_cell(
+            non_empty_text(track.metadata.album.as_deref())
+                .or_else(|| view_model.path_fallbacks.then(|| folder_name(track)))
+                .unwrap_or_default(),
+            column_width(column, track_number_column_width),
+            view_model,
+        ),
+        ListColumn::Artist => fill_text_cell(
+            track.metadata.artist.clone().unwrap_or_default(),
+            column_width(column, track_number_column_width),
+            view_model,
+        ),
+        ListColumn::AlbumArtist => fill_text_cell(
+            track.metadata.album_artist.clone().unwrap_or_default(),
+            column_width(column, track_number_column_width),
+            view_model,
+        ),
+        ListColumn::TrackTotal => compact_text_cell(
+            optional_display(track.metadata.track_count),
+            column_width(column, track_number_column_width),
+            view_model,
+        ),
+        ListColumn::DiscNumber => compact_text_cell(
+            optional_display(track.metadata.album_disc_number),
+            column_width(column, track_number_column_width),
+            view_model,
+        ),
+        ListColumn::DiscTotal => compact_text_cell(
+            optional_display(track.metadata.album_disc_count),
+            column_width(column, track_number_column_width),
+            view_model,
+        ),
+        ListColumn::Genre => fill_text_cell(
+            track.metadata.genres.join(", "),
+            column_width(column, track_number_column_width),
+            view_model,
+        ),
+        ListColumn::FilePath => fill_text_cell(
+            track.path.to_string_lossy().to_string(),
+            column_width(column, track_number_column_width),
+            view_model,
+        ),
+        ListColumn::Duration => compact_text_cell(
+            format_optional_duration(track.metadata.duration),
+            column_width(column, track_number_column_width),
+            view_model,
+        ),
+        ListColumn::Bpm => compact_text_cell(
+            optional_display(track.metadata.bpm.map(|bpm| bpm.round() as u32)),
+            column_width(column, track_number_column_width),
+            view_model,
+        ),
+    }
+}
+
+// Name of the folder holding the track's file, untagged rips are usually filed by album
+fn folder_name(track: &Track) -> String {
+    track
+        .metadata
+        .media_file(&track.path)
+        .parent()
+        .and_then(|folder| folder.file_name())
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+pub fn column_width(column: ListColumn, track_number_column_width: f32) -> Length {
+    match column {
+        ListColumn::TrackNumber => Length::Fixed(track_number_column_width),
+        ListColumn::Title | ListColumn::FilePath => Length::FillPortion(2),
+        ListColumn::TrackTotal
+        | ListColumn::DiscNumber
+        | ListColumn::DiscTotal
+        | ListColumn::Bpm => Length::Fixed(COMPACT_COLUMN_WIDTH),
+        ListColumn::Duration => Length::Fixed(DURATION_COLUMN_WIDTH),
+        ListColumn::Album | ListColumn::Artist | ListColumn::AlbumArtist | ListColumn::Genre => {
+            Length::FillPortion(1)
+        }
+    }
+}
+
+fn fill_text_cell<'a>(
+    value: String,
+    width: Length,
+    view_model: &ListViewModel,
+) -> cosmic::Element<'a, Message> {
+    widget::container(
+        widget::text(display_text(&value))
+            .align_y(view_model.row_align)
+            .height(view_model.row_height)
+            .wrapping(view_model.wrapping)
+            .width(Length::Fill),
+    )
+    .width(width)
+    .clip(true)
+    .into()
+}
+
+fn compact_text_cell<'a>(
+    value: String,
+    width: Length,
+    view_model: &ListViewModel,
+) -> cosmic::Element<'a, Message> {
+    widget::container(
+        widget::text(value)
+            .align_x(Alignment::End)
+            .align_y(view_model.row_align)
+            .height(view_model.row_height)
+            .width(Length::Fill),
+    )
+    .width(width)
+    .clip(true)
+    .into()
+}
+
+// Row theming
+pub fn button_style(selected: bool, heading: bool) -> theme::Button {
+    theme::Button::Custom {
+        active: Box::new(move |_focus, theme| button_appearance(theme, selected, heading, false)),
+        disabled: Box::new(move |theme| button_appearance(theme, selected, heading, false)),
+        hovered: Box::new(move |_focus, theme| button_appearance(theme, selected, heading, true)),
+        pressed: Box::new(move |_focus, theme| button_appearance(theme, selected, heading, false)),
+    }
+}
+
+fn button_appearance(
+    theme: &theme::Theme,
+    selected: bool,
+    heading: bool,
+    hovered: bool,
+) -> widget::button::Style {
+    let cosmic = theme.cosmic();
+    let mut appearance = widget::button::Style::new();
+
+    if heading {
+        appearance.background = Some(Color::TRANSPARENT.into());
+        appearance.icon_color = Some(Color::from(cosmic.on_bg_color()));
+        appearance.text_color = Some(Color::from(cosmic.on_bg_color()));
+    } else if selected {
+        appearance.background = Some(Color::from(cosmic.accent_color()).into());
+        appearance.icon_color = Some(Color::from(cosmic.on_accent_color()));
+        appearance.text_color = Some(Color::from(cosmic.on_accent_color()));
+    } else if hovered {
+        appearance.background = Some(Color::from(cosmic.bg_component_color()).into());
+        appearance.icon_color = Some(Color::from(cosmic.on_bg_component_color()));
+        appearance.text_color = Some(Color::from(cosmic.on_bg_component_color()));
+    } else {
+        appearance.background = Some(Color::TRANSPARENT.into());
+        appearance.icon_color = Some(Color::from(cosmic.on_bg_color()));
+        appearance.text_color = Some(Color::from(cosmic.on_bg_color()));
+    }
+    appearance.outline_width = 0.0;
+    appearance.border_width = 0.0;
+    appearance.border_radius = cosmic.radius_xs().into();
+
+    appearance
+}
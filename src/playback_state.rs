@@ -2,7 +2,10 @@
 
 use crate::library::MediaMetaData;
 use crate::playlist::Track;
+use rand::seq::SliceRandom;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
 
 /// Consolidated playback state
 #[derive(Clone)]
@@ -39,6 +42,40 @@ pub struct PlaybackSession {
     pub index: usize,
 }
 
+impl PlaybackSession {
+    /// Shuffle the upcoming tracks, leaving the played history and the current track in place
+    pub fn shuffle_remaining(&mut self) {
+        if let Some(upcoming) = self.order.get_mut(self.index + 1..) {
+            upcoming.shuffle(&mut rand::rng());
+        }
+    }
+
+    /// Put the upcoming tracks back in the order they have in `original`, leaving the played
+    /// history and the current track in place. Queued tracks that aren't in `original` stay
+    /// upcoming, after the ones that are.
+    pub fn unshuffle_remaining(&mut self, original: &[Track]) {
+        let key = |track: &Track| (track.entry_id, track.path.clone());
+        let positions: HashMap<(u32, PathBuf), usize> = original
+            .iter()
+            .enumerate()
+            .map(|(position, track)| (key(track), position))
+            .collect();
+
+        let Some(upcoming) = self.order.get(self.index + 1..) else {
+            return;
+        };
+        let (mut from_original, queued): (Vec<Track>, Vec<Track>) = upcoming
+            .iter()
+            .cloned()
+            .partition(|track| positions.contains_key(&key(track)));
+        from_original.sort_by_key(|track| positions[&key(track)]);
+
+        self.order.truncate(self.index + 1);
+        self.order.extend(from_original);
+        self.order.extend(queued);
+    }
+}
+
 impl std::fmt::Debug for PlaybackSession {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("PlaybackSession")
@@ -71,3 +108,98 @@ pub enum RepeatMode {
     One,
     All,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tracks(count: u32) -> Vec<Track> {
+        (0..count)
+            .map(|entry_id| Track {
+                entry_id,
+                path: PathBuf::from(format!("/music/{entry_id}.flac")),
+                ..Track::new()
+            })
+            .collect()
+    }
+
+    fn ids(session: &PlaybackSession) -> Vec<u32> {
+        session.order.iter().map(|track| track.entry_id).collect()
+    }
+
+    #[test]
+    fn shuffle_keeps_history_and_current_track() {
+        let original = tracks(20);
+        let mut session = PlaybackSession {
+            playlist_id: 1,
+            order: original.clone(),
+            index: 4,
+        };
+
+        session.shuffle_remaining();
+
+        let order = ids(&session);
+        assert_eq!(order[..=4], [0, 1, 2, 3, 4]);
+        let mut upcoming = order[5..].to_vec();
+        upcoming.sort();
+        assert_eq!(upcoming, (5..20).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn unshuffle_restores_order_of_upcoming_tracks() {
+        let original = tracks(6);
+        let order = [3, 0, 5, 1, 4, 2]
+            .iter()
+            .map(|&id| original[id].clone())
+            .collect();
+        let mut session = PlaybackSession {
+            playlist_id: 1,
+            order,
+            index: 1,
+        };
+
+        session.unshuffle_remaining(&original);
+
+        // Played tracks aren't repeated, and none of the unplayed ones are dropped
+        assert_eq!(ids(&session), [3, 0, 1, 2, 4, 5]);
+        assert_eq!(session.index, 1);
+    }
+
+    #[test]
+    fn unshuffle_keeps_queued_tracks_upcoming() {
+        let original = tracks(4);
+        let queued = Track {
+            entry_id: 99,
+            path: PathBuf::from("/music/queued.flac"),
+            ..Track::new()
+        };
+        let mut session = PlaybackSession {
+            playlist_id: 1,
+            order: vec![
+                original[2].clone(),
+                queued,
+                original[3].clone(),
+                original[0].clone(),
+            ],
+            index: 0,
+        };
+
+        session.unshuffle_remaining(&original);
+
+        assert_eq!(ids(&session), [2, 0, 3, 99]);
+    }
+
+    #[test]
+    fn unshuffle_with_current_track_not_in_original() {
+        let original = tracks(3);
+        let mut session = PlaybackSession {
+            playlist_id: 1,
+            order: vec![original[2].clone(), original[0].clone()],
+            index: 0,
+        };
+
+        session.unshuffle_remaining(&original[..2]);
+
+        assert_eq!(ids(&session), [2, 0]);
+    }
+}
@@ -1,15 +1,22 @@
 // SPDX-License-Identifier: GPL-3.0
 
+use crate::config::FadeCurve;
+use crate::constants::{FADE_CURVE_FLOOR_DB, FADE_STEP_MS, MAX_PLAYER_VOLUME};
 use crate::helpers::clamp;
 use gst::prelude::*;
 use gstreamer::{self as gst};
 use std::sync::mpsc;
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
 pub struct Player {
     pub playbin: gst::Element,
+    panorama: Option<gst::Element>,
     queued_uri: Arc<Mutex<Option<String>>>,
     about_to_finish_rx: mpsc::Receiver<()>,
+    // Bumped to cancel a running fade, held while a fade switches state so a later
+    // play/pause/stop always wins
+    fade_generation: Arc<Mutex<u64>>,
 }
 
 impl Player {
@@ -25,6 +32,22 @@ impl Player {
             .build()
             .expect("Failed to create playbin.");
 
+        // Balance filter, the converters let surround and integer streams reach the
+        // stereo-only panorama. Without gst-plugins-good the balance stays centered.
+        let panorama = match gst::parse::bin_from_description(
+            "audioconvert ! audiopanorama name=panorama method=simple ! audioconvert",
+            true,
+        ) {
+            Ok(filter) => {
+                playbin.set_property("audio-filter", &filter);
+                filter.by_name("panorama")
+            }
+            Err(err) => {
+                log::warn!("Balance filter unavailable: {}", err);
+                None
+            }
+        };
+
         let queued_uri: Arc<Mutex<Option<String>>> = Arc::new(Mutex::new(None));
         let (about_to_finish_tx, about_to_finish_rx) = mpsc::sync_channel::<()>(8);
 
@@ -49,8 +72,10 @@ impl Player {
 
         Self {
             playbin,
+            panorama,
             queued_uri,
             about_to_finish_rx,
+            fade_generation: Arc::new(Mutex::new(0)),
         }
     }
 
@@ -59,6 +84,7 @@ impl Player {
     }
 
     pub fn play(&mut self) {
+        self.cancel_fade();
         match self.playbin.set_state(gst::State::Playing) {
             Ok(_) => {}
             Err(error) => {
@@ -68,6 +94,7 @@ impl Player {
     }
 
     pub fn pause(&mut self) {
+        self.cancel_fade();
         match self.playbin.set_state(gst::State::Paused) {
             Ok(_) => {}
             Err(error) => {
@@ -77,6 +104,7 @@ impl Player {
     }
 
     pub fn stop(&mut self) {
+        self.cancel_fade();
         match self.playbin.set_state(gst::State::Null) {
             Ok(_) => {}
             Err(error) => {
@@ -85,8 +113,115 @@ impl Player {
         }
     }
 
-    pub fn set_volume(&mut self, volume: f64) {
-        self.playbin.set_property("volume", clamp(volume, 0.0, 1.0));
+    /// Values above 1.0 amplify, which is how positive manual gain is applied
+    pub fn set_volume(&self, level: f64) {
+        self.playbin
+            .set_property("volume", clamp(level, 0.0, MAX_PLAYER_VOLUME));
+    }
+
+    pub fn volume(&self) -> f64 {
+        self.playbin.property::<f64>("volume")
+    }
+
+    /// Flushing seek to the keyframe nearest `position`. Works while paused too, the pipeline
+    /// prerolls at the new position.
+    pub fn seek(&self, position: Duration) -> Result<(), gst::glib::BoolError> {
+        self.seek_with_flags(position, gst::SeekFlags::KEY_UNIT)
+    }
+
+    /// Flushing seek with other flags, such as `ACCURATE` to land exactly on `position`
+    pub fn seek_with_flags(
+        &self,
+        position: Duration,
+        flags: gst::SeekFlags,
+    ) -> Result<(), gst::glib::BoolError> {
+        self.playbin.seek_simple(
+            gst::SeekFlags::FLUSH | flags,
+            gst::ClockTime::from_nseconds(position.as_nanos() as u64),
+        )
+    }
+
+    /// Fade from silence up to `volume`, for starting playback without a click
+    pub fn fade_in(&self, volume: f64, duration: Duration, curve: FadeCurve) {
+        self.fade(0.0, volume, duration, curve, None);
+    }
+
+    /// Fade out from the current volume and only then switch to `state`, so pausing or
+    /// stopping doesn't cut the waveform mid-cycle
+    pub fn fade_out_to(&self, state: gst::State, duration: Duration, curve: FadeCurve) {
+        self.fade(self.volume(), 0.0, duration, curve, Some(state));
+    }
+
+    /// Step the volume on a helper thread. Starting another fade or changing state cancels it,
+    /// volume changes made meanwhile are overwritten by the next step.
+    fn fade(
+        &self,
+        from: f64,
+        to: f64,
+        duration: Duration,
+        curve: FadeCurve,
+        then: Option<gst::State>,
+    ) {
+        let generation = {
+            let Ok(mut generation) = self.fade_generation.lock() else {
+                return;
+            };
+            *generation += 1;
+            *generation
+        };
+        let fade_generation = self.fade_generation.clone();
+        let playbin = self.playbin.clone();
+        let steps = (duration.as_millis() as u64 / FADE_STEP_MS).max(1);
+
+        playbin.set_property("volume", clamp(from, 0.0, MAX_PLAYER_VOLUME));
+
+        std::thread::spawn(move || {
+            for step in 1..=steps {
+                std::thread::sleep(Duration::from_millis(FADE_STEP_MS));
+
+                let Ok(current) = fade_generation.lock() else {
+                    return;
+                };
+                if *current != generation {
+                    return;
+                }
+
+                // Falling fades mirror the rising curve so fading out is the reverse of fading in
+                let progress = step as f64 / steps as f64;
+                let volume = if to >= from {
+                    from + (to - from) * curve_gain(curve, progress)
+                } else {
+                    to + (from - to) * curve_gain(curve, 1.0 - progress)
+                };
+                playbin.set_property("volume", clamp(volume, 0.0, MAX_PLAYER_VOLUME));
+
+                if step == steps {
+                    if let Some(state) = then {
+                        if let Err(err) = playbin.set_state(state) {
+                            log::warn!("Failed to change state after fade: {:?}", err);
+                        }
+                    }
+                }
+            }
+        });
+    }
+
+    fn cancel_fade(&self) {
+        if let Ok(mut generation) = self.fade_generation.lock() {
+            *generation += 1;
+        }
+    }
+
+    /// Left/right balance from -1.0 (left only) to 1.0 (right only), independent of volume
+    pub fn set_balance(&self, balance: f32) {
+        if let Some(panorama) = &self.panorama {
+            panorama.set_property("panorama", clamp(balance, -1.0, 1.0));
+        }
+    }
+
+    /// URI of the stream currently playing, if any.
+    pub fn current_uri(&self) -> Option<String> {
+        self.playbin.property::<Option<String>>("current-uri")
     }
 
     /// Set (or clear) the URI to be played gaplessly after the current track.
@@ -108,3 +243,18 @@ impl Player {
         fired
     }
 }
+
+/// Gain from 0.0 to 1.0 for a fade that is `progress` of the way from silence to full volume.
+/// Crossfades give the incoming track `curve_gain(p)` and the outgoing one `curve_gain(1 - p)`.
+pub fn curve_gain(curve: FadeCurve, progress: f64) -> f64 {
+    let progress = progress.clamp(0.0, 1.0);
+
+    match curve {
+        FadeCurve::Linear => progress,
+        // sin² + cos² = 1 keeps the summed power constant, so there's no dip halfway through
+        FadeCurve::EqualPower => (progress * std::f64::consts::FRAC_PI_2).sin(),
+        // Even steps in dB, which sounds like an even change in loudness
+        FadeCurve::Logarithmic if progress <= 0.0 => 0.0,
+        FadeCurve::Logarithmic => 10f64.powf(FADE_CURVE_FLOOR_DB * (1.0 - progress) / 20.0),
+    }
+}
@@ -43,6 +43,33 @@ impl Playlist {
         }
     }
 
+    pub fn favorites() -> Self {
+        Self {
+            id: u32::MAX - 1,
+            name: fl!("favorites"),
+            kind: PlaylistKind::Favorites,
+            tracks: Vec::new(),
+        }
+    }
+
+    /// Copy this playlist's tracks into a new user playlist. Copying the library or favorites
+    /// gives a normal editable playlist that no longer follows the library.
+    pub fn duplicate(&self, new_name: String) -> Playlist {
+        let mut playlist = Playlist::new(new_name);
+        playlist.tracks = self
+            .tracks
+            .iter()
+            .cloned()
+            .map(|mut track| {
+                track.selected = false;
+                track.generate_entry_id();
+                track
+            })
+            .collect();
+
+        playlist
+    }
+
     pub fn clear(&mut self) {
         self.tracks.clear();
     }
@@ -51,6 +78,15 @@ impl Playlist {
         matches!(self.kind, PlaylistKind::Library)
     }
 
+    pub fn is_favorites(&self) -> bool {
+        matches!(self.kind, PlaylistKind::Favorites)
+    }
+
+    /// Playlists the user edits by hand, as opposed to ones built from the library
+    pub fn is_user(&self) -> bool {
+        matches!(self.kind, PlaylistKind::User)
+    }
+
     pub fn id(&self) -> u32 {
         self.id
     }
@@ -71,49 +107,59 @@ impl Playlist {
         self.tracks.len()
     }
 
+    /// Total length in seconds, tracks without a known duration count as 0
+    pub fn total_duration(&self) -> u64 {
+        self.tracks
+            .iter()
+            .filter_map(|track| track.metadata.duration)
+            .map(f64::from)
+            .sum::<f64>() as u64
+    }
+
     pub fn sort(
         &mut self,
         sort_by: SortBy,
         sort_direction: SortDirection,
         title_sort: TitleSortMode,
+        ignore_articles: bool,
         case_sensitive: bool,
     ) {
         self.tracks.sort_by(|a, b| {
             let ordering = match sort_by {
                 SortBy::Artist => compare_optional_text(
-                    a.metadata.artist.as_deref(),
-                    b.metadata.artist.as_deref(),
+                    a.metadata.artist_sort_key(ignore_articles),
+                    b.metadata.artist_sort_key(ignore_articles),
                     case_sensitive,
                 )
                 .then(compare_optional_text(
-                    a.metadata.album.as_deref(),
-                    b.metadata.album.as_deref(),
+                    a.metadata.album_sort_key(ignore_articles),
+                    b.metadata.album_sort_key(ignore_articles),
                     case_sensitive,
                 ))
-                .then_with(|| compare_title(a, b, title_sort, case_sensitive)),
+                .then_with(|| compare_title(a, b, title_sort, ignore_articles, case_sensitive)),
 
                 SortBy::Album => compare_optional_text(
-                    a.metadata.album.as_deref(),
-                    b.metadata.album.as_deref(),
+                    a.metadata.album_sort_key(ignore_articles),
+                    b.metadata.album_sort_key(ignore_articles),
                     case_sensitive,
                 )
-                .then_with(|| compare_title(a, b, title_sort, case_sensitive)),
+                .then_with(|| compare_title(a, b, title_sort, ignore_articles, case_sensitive)),
 
-                SortBy::AlbumArtist => compare_optional_text(
-                    a.metadata.album_artist.as_deref(),
-                    b.metadata.album_artist.as_deref(),
+                SortBy::AlbumArtist => compare_text(
+                    a.metadata.album_artist_sort_key(ignore_articles),
+                    b.metadata.album_artist_sort_key(ignore_articles),
                     case_sensitive,
                 )
                 .then(compare_optional_text(
-                    a.metadata.album.as_deref(),
-                    b.metadata.album.as_deref(),
+                    a.metadata.album_sort_key(ignore_articles),
+                    b.metadata.album_sort_key(ignore_articles),
                     case_sensitive,
                 ))
-                .then_with(|| compare_title(a, b, title_sort, case_sensitive)),
+                .then_with(|| compare_title(a, b, title_sort, ignore_articles, case_sensitive)),
 
                 SortBy::Title => compare_optional_text(
-                    a.metadata.title.as_deref(),
-                    b.metadata.title.as_deref(),
+                    a.metadata.title_sort_key(ignore_articles),
+                    b.metadata.title_sort_key(ignore_articles),
                     case_sensitive,
                 ),
 
@@ -122,18 +168,18 @@ impl Playlist {
                     .track_count
                     .cmp(&b.metadata.track_count)
                     .then(compare_optional_text(
-                        a.metadata.album.as_deref(),
-                        b.metadata.album.as_deref(),
+                        a.metadata.album_sort_key(ignore_articles),
+                        b.metadata.album_sort_key(ignore_articles),
                         case_sensitive,
                     ))
-                    .then_with(|| compare_title(a, b, title_sort, case_sensitive)),
+                    .then_with(|| compare_title(a, b, title_sort, ignore_articles, case_sensitive)),
 
                 SortBy::DiscNumber => a
                     .metadata
                     .album_disc_number
                     .cmp(&b.metadata.album_disc_number)
                     .then(a.metadata.track_number.cmp(&b.metadata.track_number))
-                    .then_with(|| compare_title(a, b, title_sort, case_sensitive)),
+                    .then_with(|| compare_title(a, b, title_sort, ignore_articles, case_sensitive)),
 
                 SortBy::DiscTotal => a
                     .metadata
@@ -145,29 +191,32 @@ impl Playlist {
                             .cmp(&b.metadata.album_disc_number),
                     )
                     .then(a.metadata.track_number.cmp(&b.metadata.track_number))
-                    .then_with(|| compare_title(a, b, title_sort, case_sensitive)),
-
-                SortBy::Genre => compare_optional_text(
-                    a.metadata.genre.as_deref(),
-                    b.metadata.genre.as_deref(),
-                    case_sensitive,
-                )
-                .then(compare_optional_text(
-                    a.metadata.artist.as_deref(),
-                    b.metadata.artist.as_deref(),
-                    case_sensitive,
-                ))
-                .then(compare_optional_text(
-                    a.metadata.album.as_deref(),
-                    b.metadata.album.as_deref(),
-                    case_sensitive,
-                ))
-                .then_with(|| compare_title(a, b, title_sort, case_sensitive)),
+                    .then_with(|| compare_title(a, b, title_sort, ignore_articles, case_sensitive)),
+
+                SortBy::Genre => {
+                    compare_optional_text(a.metadata.genre(), b.metadata.genre(), case_sensitive)
+                        .then(compare_optional_text(
+                            a.metadata.artist_sort_key(ignore_articles),
+                            b.metadata.artist_sort_key(ignore_articles),
+                            case_sensitive,
+                        ))
+                        .then(compare_optional_text(
+                            a.metadata.album_sort_key(ignore_articles),
+                            b.metadata.album_sort_key(ignore_articles),
+                            case_sensitive,
+                        ))
+                        .then_with(|| {
+                            compare_title(a, b, title_sort, ignore_articles, case_sensitive)
+                        })
+                }
 
                 SortBy::FilePath => compare_path(&a.path, &b.path, case_sensitive),
 
                 SortBy::Duration => compare_optional_f32(a.metadata.duration, b.metadata.duration)
-                    .then_with(|| compare_title(a, b, title_sort, case_sensitive)),
+                    .then_with(|| compare_title(a, b, title_sort, ignore_articles, case_sensitive)),
+
+                SortBy::Bpm => compare_optional_f64(a.metadata.bpm, b.metadata.bpm)
+                    .then_with(|| compare_title(a, b, title_sort, ignore_articles, case_sensitive)),
             };
 
             match sort_direction {
@@ -290,12 +339,13 @@ fn compare_title(
     a: &Track,
     b: &Track,
     title_sort: TitleSortMode,
+    ignore_articles: bool,
     case_sensitive: bool,
 ) -> Ordering {
     match title_sort {
         TitleSortMode::Alphabetical => compare_optional_text(
-            a.metadata.title.as_deref(),
-            b.metadata.title.as_deref(),
+            a.metadata.title_sort_key(ignore_articles),
+            b.metadata.title_sort_key(ignore_articles),
             case_sensitive,
         ),
         TitleSortMode::TrackNumber => a
@@ -305,8 +355,8 @@ fn compare_title(
             .then(a.metadata.track_number.cmp(&b.metadata.track_number))
             .then_with(|| {
                 compare_optional_text(
-                    a.metadata.title.as_deref(),
-                    b.metadata.title.as_deref(),
+                    a.metadata.title_sort_key(ignore_articles),
+                    b.metadata.title_sort_key(ignore_articles),
                     case_sensitive,
                 )
             }),
@@ -352,3 +402,12 @@ fn compare_optional_f32(a: Option<f32>, b: Option<f32>) -> Ordering {
         (Some(_), None) => Ordering::Greater,
     }
 }
+
+fn compare_optional_f64(a: Option<f64>, b: Option<f64>) -> Ordering {
+    match (a, b) {
+        (Some(a), Some(b)) => a.total_cmp(&b),
+        (None, None) => Ordering::Equal,
+        (None, Some(_)) => Ordering::Less,
+        (Some(_), None) => Ordering::Greater,
+    }
+}
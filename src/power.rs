@@ -0,0 +1,26 @@
+// SPDX-License-Identifier: GPL-3.0
+
+const LOGIND_DESTINATION: &str = "org.freedesktop.login1";
+const LOGIND_PATH: &str = "/org/freedesktop/login1";
+const LOGIND_MANAGER_INTERFACE: &str = "org.freedesktop.login1.Manager";
+
+/// Ask logind to suspend the system. `interactive` lets polkit prompt for authentication
+/// when the session isn't allowed to suspend on its own.
+pub async fn suspend() -> Result<(), String> {
+    let connection = zbus::Connection::system()
+        .await
+        .map_err(|err| format!("failed to connect to the system bus: {err}"))?;
+
+    connection
+        .call_method(
+            Some(LOGIND_DESTINATION),
+            LOGIND_PATH,
+            Some(LOGIND_MANAGER_INTERFACE),
+            "Suspend",
+            &(true,),
+        )
+        .await
+        .map_err(|err| format!("suspend request failed: {err}"))?;
+
+    Ok(())
+}
@@ -0,0 +1,51 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use crate::constants::SCAN_REPORT_FILENAME;
+use crate::helpers::write_atomic;
+use serde::{Deserialize, Serialize};
+use std::{fs, io, path::PathBuf};
+use xdg::BaseDirectories;
+
+/// A file the last scan couldn't add to the library
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ScanError {
+    pub path: PathBuf,
+    pub reason: ScanErrorReason,
+}
+
+/// Why a file was left out of the library during a scan
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub enum ScanErrorReason {
+    /// Descriptions of the GStreamer plugins needed to read it
+    MissingPlugins(Vec<String>),
+    /// The discoverer gave up before the file was read
+    Timeout,
+    Unreadable(String),
+}
+
+/// Errors of the last scan, empty when there's no report yet or it can't be read
+pub fn load(xdg_dirs: &BaseDirectories) -> Vec<ScanError> {
+    let Some(path) = xdg_dirs.find_data_file(SCAN_REPORT_FILENAME) else {
+        return Vec::new();
+    };
+
+    match fs::read_to_string(path).map(|content| serde_json::from_str(&content)) {
+        Ok(Ok(errors)) => errors,
+        Ok(Err(err)) => {
+            log::warn!("Ignoring unreadable scan report: {}", err);
+            Vec::new()
+        }
+        Err(err) => {
+            log::warn!("Failed to read scan report: {}", err);
+            Vec::new()
+        }
+    }
+}
+
+pub fn save(xdg_dirs: &BaseDirectories, errors: &[ScanError]) -> io::Result<()> {
+    let path = xdg_dirs.place_data_file(SCAN_REPORT_FILENAME)?;
+
+    write_atomic(&path, |writer| {
+        serde_json::to_writer(writer, errors).map_err(io::Error::from)
+    })
+}
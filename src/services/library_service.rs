@@ -2,22 +2,27 @@
 // src/services/library_service.rs
 
 use crate::constants::*;
+use crate::cue;
 use crate::helpers::artwork_variant_filename;
-use crate::library::{Library, MediaMetaData};
+use crate::library::{Library, LibraryError, MediaMetaData, cue_track_path};
+use crate::scan_report::{ScanError, ScanErrorReason};
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use gst::prelude::*;
 use gstreamer as gst;
 use gstreamer_pbutils as pbutils;
 use image::{DynamicImage, ImageFormat};
 use sha256::digest;
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeSet, HashMap, HashSet};
 use std::fs::{self, File};
 use std::io::{Cursor, Write};
 use std::path::{Path, PathBuf};
-use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
-use tokio::sync::mpsc::UnboundedSender;
+use tokio::sync::mpsc::{self, Receiver, Sender, error::SendError};
 use tokio_util::sync::CancellationToken;
 use url::Url;
-use walkdir::WalkDir;
+use walkdir::{DirEntry, WalkDir};
 use xdg::BaseDirectories;
 
 /// Progress updates during library scanning
@@ -29,77 +34,189 @@ pub enum LibraryProgress {
         total: f32,
         percent: f32,
     },
-    /// Partial library update with completed entries
+    /// Partial library with the tracks read so far plus the previous metadata of those not
+    /// reached yet, saved by the app so an interrupted scan doesn't lose anything
     PartialUpdate(HashMap<PathBuf, MediaMetaData>),
     /// Final complete library
     Complete(Library),
+    /// Library folders that couldn't be read, sent before `Complete`. Their tracks are kept.
+    UnreachablePaths(Vec<String>),
+    /// Tracks that couldn't be read because GStreamer plugins are missing, sent before `Complete`
+    MissingPlugins {
+        plugins: Vec<String>,
+        track_count: usize,
+    },
+    /// Files that couldn't be read with the reason, sent before `Complete` or `TracksRescanned`
+    ScanErrors(Vec<ScanError>),
+    /// Track gain and peak computed by a loudness analysis, merged into the library as they arrive
+    LoudnessUpdate(HashMap<PathBuf, (f64, f64)>),
+    /// Loudness analysis finished, sent after the last `LoudnessUpdate`
+    LoudnessComplete,
+    /// Metadata re-read for a handful of tracks, `failed` counts files that couldn't be read
+    TracksRescanned {
+        media: HashMap<PathBuf, MediaMetaData>,
+        failed: usize,
+    },
     Cancelled,
 }
 
-#[derive(Debug)]
-pub enum LibraryError {
-    Io(std::io::Error),
-    Json(serde_json::Error),
-    InvalidData(String),
+/// What updating the library would change, worked out from the files on disk
+#[derive(Clone, Debug, Default)]
+pub struct ScanPreview {
+    pub added: usize,
+    /// Files in the library whose size changed since they were last read
+    pub updated: usize,
+    /// Files in the library that weren't found, sorted
+    pub removed: Vec<PathBuf>,
+    /// Library folders that couldn't be read, their tracks would be kept
+    pub unreachable_paths: Vec<String>,
 }
 
-impl std::fmt::Display for LibraryError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            LibraryError::Io(e) => write!(f, "IO error: {}", e),
-            LibraryError::Json(e) => write!(f, "JSON error: {}", e),
-            LibraryError::InvalidData(s) => write!(f, "Invalid data: {}", s),
-        }
-    }
+/// Sending side of the channel a background library task reports to. The channel is bounded
+/// so a scan can't queue messages faster than the app handles them.
+#[derive(Clone)]
+pub struct ProgressSender {
+    tx: Sender<LibraryProgress>,
 }
 
-impl std::error::Error for LibraryError {}
+impl ProgressSender {
+    pub fn channel() -> (Self, Receiver<LibraryProgress>) {
+        let (tx, rx) = mpsc::channel(LIBRARY_PROGRESS_CHANNEL_CAPACITY);
+        (Self { tx }, rx)
+    }
 
-impl From<std::io::Error> for LibraryError {
-    fn from(err: std::io::Error) -> Self {
-        LibraryError::Io(err)
+    /// Queue a message, waiting for room when the app is behind so results and the final
+    /// message are never dropped. Only call this from the worker thread.
+    pub fn send(&self, progress: LibraryProgress) -> Result<(), SendError<LibraryProgress>> {
+        self.tx.blocking_send(progress)
     }
-}
 
-impl From<serde_json::Error> for LibraryError {
-    fn from(err: serde_json::Error) -> Self {
-        LibraryError::Json(err)
+    /// Report how far along the task is. Skipped while the channel is full, a newer count
+    /// follows shortly and the last one is sent with `send`.
+    pub fn progress(&self, current: f32, total: f32) {
+        let percent = if total > 0.0 {
+            current / total * 100.0
+        } else {
+            100.0
+        };
+        let _ = self.tx.try_send(LibraryProgress::Progress {
+            current,
+            total,
+            percent,
+        });
     }
 }
 
-/// Service for managing the music library
+/// Service for managing the music library. Clones share their save state.
+#[derive(Clone)]
 pub struct LibraryService {
     xdg_dirs: Arc<BaseDirectories>,
+    /// Counts saves in the order they were requested
+    save_generation: Arc<AtomicU64>,
+    /// Generation of the last save written, held while writing so saves run one at a time
+    saved_generation: Arc<Mutex<u64>>,
+    /// Set when a library file that failed to load couldn't be moved aside, saving would
+    /// replace it
+    saving_blocked: Arc<AtomicBool>,
 }
 
 impl LibraryService {
     pub fn new(xdg_dirs: Arc<BaseDirectories>) -> Self {
-        Self { xdg_dirs }
+        Self {
+            xdg_dirs,
+            save_generation: Arc::new(AtomicU64::new(0)),
+            saved_generation: Arc::new(Mutex::new(0)),
+            saving_blocked: Arc::new(AtomicBool::new(false)),
+        }
     }
 
-    /// Load library from disk
+    /// Load library from disk, an empty library when none has been saved yet. A library file
+    /// that can't be loaded, corrupt or from a newer version, is moved aside first so it can
+    /// still be recovered. When that fails too, saving stays off for the session.
     pub fn load(&self) -> Result<Library, LibraryError> {
-        let mut media: HashMap<PathBuf, MediaMetaData> = self
-            .xdg_dirs
-            .find_data_file(LIBRARY_FILENAME)
-            .map(|path| {
-                let content = fs::read_to_string(path)?;
-                Ok::<_, LibraryError>(serde_json::from_str(&content)?)
-            })
-            .transpose()?
-            .unwrap_or_default();
+        let mut library = match Library::load(&self.xdg_dirs) {
+            Ok(library) => library,
+            Err(LibraryError::NotFound) => Library::new(),
+            Err(e) => {
+                match Library::back_up(&self.xdg_dirs) {
+                    Ok(backup) => {
+                        log::warn!("Moved the unreadable library to {}", backup.display())
+                    }
+                    Err(err) => {
+                        log::error!(
+                            "Failed to move the unreadable library aside, it won't be saved: {}",
+                            err
+                        );
+                        self.saving_blocked.store(true, Ordering::SeqCst);
+                    }
+                }
+                return Err(e);
+            }
+        };
 
         // Remove any entry without an id
-        media.retain(|_, v| v.id.is_some());
+        library.media.retain(|_, v| v.id.is_some());
 
-        Ok(Library { media })
+        Ok(library)
     }
 
     /// Save library to disk
     pub fn save(&self, library: &Library) -> Result<(), LibraryError> {
-        library
-            .save(&self.xdg_dirs)
-            .map_err(|e| LibraryError::InvalidData(format!("Failed to save library: {}", e)))
+        if self.saving_blocked.load(Ordering::SeqCst) {
+            return Err(Self::saving_blocked_error());
+        }
+
+        let generation = self.save_generation.fetch_add(1, Ordering::SeqCst) + 1;
+        Self::save_generation(&self.xdg_dirs, &self.saved_generation, generation, library)
+    }
+
+    /// Save a snapshot of the library on a blocking thread so the UI doesn't wait on the
+    /// disk. A snapshot taken before one that has already been written is skipped, so the
+    /// file never goes back in time when saves finish out of order.
+    pub fn save_in_background(
+        &self,
+        library: Library,
+    ) -> impl Future<Output = Result<(), LibraryError>> + Send + 'static {
+        let generation = self.save_generation.fetch_add(1, Ordering::SeqCst) + 1;
+        let xdg_dirs = self.xdg_dirs.clone();
+        let saved_generation = self.saved_generation.clone();
+        let saving_blocked = self.saving_blocked.load(Ordering::SeqCst);
+
+        async move {
+            if saving_blocked {
+                return Err(Self::saving_blocked_error());
+            }
+
+            tokio::task::spawn_blocking(move || {
+                Self::save_generation(&xdg_dirs, &saved_generation, generation, &library)
+            })
+            .await
+            .map_err(|err| LibraryError::Io(std::io::Error::other(err)))?
+        }
+    }
+
+    fn saving_blocked_error() -> LibraryError {
+        LibraryError::InvalidData(
+            "saving is off because the library file couldn't be loaded or moved aside".to_string(),
+        )
+    }
+
+    fn save_generation(
+        xdg_dirs: &BaseDirectories,
+        saved_generation: &Mutex<u64>,
+        generation: u64,
+        library: &Library,
+    ) -> Result<(), LibraryError> {
+        let mut saved_generation = saved_generation
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        if *saved_generation > generation {
+            return Ok(());
+        }
+
+        library.save(xdg_dirs)?;
+        *saved_generation = generation;
+        Ok(())
     }
 
     /// Scan library paths and extract metadata in a background thread
@@ -109,43 +226,50 @@ impl LibraryService {
     /// 2. Extracts metadata using GStreamer
     /// 3. Caches artwork
     /// 4. Sends progress updates via the channel
+    ///
+    /// Play statistics from `previous_media` are kept for files that are still present.
+    /// Tracks shorter than `min_duration_secs` are left out once their duration is known.
+    /// Folders in `disabled_paths` aren't scanned and, like unreachable ones, keep their tracks.
+    /// Files and folders matching a `scan_excludes` glob are skipped.
     pub fn scan_library(
         paths: HashSet<String>,
+        disabled_paths: HashSet<String>,
+        scan_excludes: Vec<String>,
         xdg_dirs: Arc<BaseDirectories>,
-        progress_tx: UnboundedSender<LibraryProgress>,
+        progress_tx: ProgressSender,
         cancel_token: CancellationToken,
         regenerate_thumbnails: bool,
+        collapse_whitespace: bool,
+        min_duration_secs: Option<u64>,
+        previous_media: HashMap<PathBuf, MediaMetaData>,
     ) {
         std::thread::spawn(move || {
             let mut library = Library::new();
+            let mut unreachable_paths = Vec::new();
+            let mut kept_paths: Vec<String> = disabled_paths.into_iter().collect();
 
             // Step 1: Collect all audio file paths
-            for path in paths {
-                if cancel_token.is_cancelled() {
-                    log::info!("Library scan cancelled by user");
-                    let _ = progress_tx.send(LibraryProgress::Cancelled);
-                    return;
-                }
+            let Some(files) = walk_library_paths(
+                paths,
+                &scan_exclude_set(&scan_excludes),
+                &mut kept_paths,
+                &mut unreachable_paths,
+                &cancel_token,
+            ) else {
+                log::info!("Library scan cancelled by user");
+                let _ = progress_tx.send(LibraryProgress::Cancelled);
+                return;
+            };
 
-                for entry in WalkDir::new(&path).into_iter().filter_map(|e| e.ok()) {
-                    let extension = entry
-                        .file_name()
-                        .to_str()
-                        .unwrap_or("")
-                        .split('.')
-                        .last()
-                        .unwrap_or("")
-                        .to_lowercase();
-
-                    let size = entry.metadata().map(|m| m.len()).unwrap_or(0);
-
-                    if VALID_AUDIO_EXTENSIONS.contains(&extension.as_str()) && size > MIN_FILE_SIZE
-                    {
-                        library
-                            .media
-                            .insert(entry.into_path(), MediaMetaData::new());
-                    }
+            for path in files {
+                let mut metadata = MediaMetaData::new();
+
+                if let Some(previous) = previous_media.get(&path) {
+                    carry_over_user_data(previous, &mut metadata);
+                    carry_over_loudness(previous, &mut metadata);
                 }
+
+                library.media.insert(path, metadata);
             }
 
             // Step 2: Extract metadata from each file
@@ -181,7 +305,24 @@ impl LibraryService {
 
             let mut entries: Vec<(PathBuf, MediaMetaData)> = library.media.into_iter().collect();
 
-            let mut completed_entries: HashMap<PathBuf, MediaMetaData> = HashMap::new();
+            // Previous metadata for files still waiting to be read, part of every partial
+            // update. Grouped by file since a cue sheet splits one file into several tracks.
+            let mut unprocessed: HashMap<PathBuf, Vec<(PathBuf, MediaMetaData)>> = entries
+                .iter()
+                .map(|(file, _)| (file.clone(), Vec::new()))
+                .collect();
+            for (path, metadata) in &previous_media {
+                if let Some(tracks) = unprocessed.get_mut(metadata.media_file(path)) {
+                    tracks.push((path.clone(), metadata.clone()));
+                }
+            }
+
+            // Keep what was known about tracks in unreachable and disabled folders
+            let mut completed_entries: HashMap<PathBuf, MediaMetaData> = previous_media
+                .iter()
+                .filter(|(file, _)| kept_paths.iter().any(|kept| file.starts_with(kept)))
+                .map(|(file, metadata)| (file.clone(), metadata.clone()))
+                .collect();
 
             let discoverer = match pbutils::Discoverer::new(gst::ClockTime::from_seconds(
                 GSTREAMER_TIMEOUT_SECS,
@@ -197,6 +338,9 @@ impl LibraryService {
                 }
             };
             let mut refreshed_artwork_hashes = HashSet::new();
+            let mut missing_plugins = BTreeSet::new();
+            let mut missing_plugin_track_count = 0;
+            let mut scan_errors = Vec::new();
 
             for (file, track_metadata) in entries.iter_mut() {
                 if cancel_token.is_cancelled() {
@@ -214,38 +358,57 @@ impl LibraryService {
                     &xdg_dirs,
                     &discoverer,
                     regenerate_thumbnails,
+                    collapse_whitespace,
                     &mut refreshed_artwork_hashes,
                 ) {
                     Ok(_) => true,
-                    Err(e) => {
-                        eprintln!("Failed to extract metadata from {:?}: {}", file, e);
+                    Err(reason) => {
+                        match &reason {
+                            ScanErrorReason::MissingPlugins(plugins) => {
+                                missing_plugins.extend(plugins.iter().cloned());
+                                missing_plugin_track_count += 1;
+                            }
+                            _ => eprintln!(
+                                "Failed to extract metadata from {:?}: {:?}",
+                                file, reason
+                            ),
+                        }
+                        scan_errors.push(ScanError {
+                            path: file.clone(),
+                            reason,
+                        });
                         false
                     }
                 };
 
+                unprocessed.remove(file);
                 if ok {
-                    completed_entries.insert(file.clone(), track_metadata.clone());
+                    let tracks = cue_track_entries(file, track_metadata, &previous_media)
+                        .unwrap_or_else(|| vec![(file.clone(), track_metadata.clone())]);
+                    completed_entries.extend(tracks.into_iter().filter(|(_, metadata)| {
+                        !is_below_min_duration(metadata, min_duration_secs)
+                    }));
                 }
 
                 let now = Instant::now();
 
                 if now.duration_since(last_progress_update) >= update_progress_interval {
                     last_progress_update = now;
-                    let _ = progress_tx.send(LibraryProgress::Progress {
-                        current: update_progress,
-                        total: update_total,
-                        percent: if update_total > 0.0 {
-                            update_progress / update_total * 100.0
-                        } else {
-                            100.0
-                        },
-                    });
+                    progress_tx.progress(update_progress, update_total);
                 }
 
-                if now.duration_since(last_library_update) >= update_library_interval {
+                if now.duration_since(last_library_update) >= update_library_interval
+                    || update_progress as usize % LIBRARY_AUTOSAVE_TRACKS == 0
+                {
                     last_library_update = now;
-                    let _ =
-                        progress_tx.send(LibraryProgress::PartialUpdate(completed_entries.clone()));
+                    let mut partial: HashMap<PathBuf, MediaMetaData> =
+                        unprocessed.values().flatten().cloned().collect();
+                    partial.extend(
+                        completed_entries
+                            .iter()
+                            .map(|(file, metadata)| (file.clone(), metadata.clone())),
+                    );
+                    let _ = progress_tx.send(LibraryProgress::PartialUpdate(partial));
                 }
             }
 
@@ -256,13 +419,335 @@ impl LibraryService {
                 percent: 100.0,
             });
 
+            if !unreachable_paths.is_empty() {
+                unreachable_paths.sort();
+                let _ = progress_tx.send(LibraryProgress::UnreachablePaths(unreachable_paths));
+            }
+
+            if missing_plugin_track_count > 0 {
+                let _ = progress_tx.send(LibraryProgress::MissingPlugins {
+                    plugins: missing_plugins.into_iter().collect(),
+                    track_count: missing_plugin_track_count,
+                });
+            }
+
+            // Always sent so the report of the previous scan is replaced
+            scan_errors.sort_by(|a, b| a.path.cmp(&b.path));
+            let _ = progress_tx.send(LibraryProgress::ScanErrors(scan_errors));
+
             let mut out = Library::new();
             out.media = completed_entries;
+            out.resolve_duplicate_ids();
 
             let _ = progress_tx.send(LibraryProgress::Complete(out));
         });
     }
 
+    /// Walk the library paths like `scan_library` and compare the files found with
+    /// `previous_media` without changing anything. Cue sheets and the minimum duration need the
+    /// tags, so the counts are of audio files and leave those out.
+    pub fn preview_scan(
+        paths: HashSet<String>,
+        disabled_paths: HashSet<String>,
+        scan_excludes: Vec<String>,
+        previous_media: &HashMap<PathBuf, MediaMetaData>,
+    ) -> ScanPreview {
+        let mut unreachable_paths = Vec::new();
+        let mut kept_paths: Vec<String> = disabled_paths.into_iter().collect();
+        let files = walk_library_paths(
+            paths,
+            &scan_exclude_set(&scan_excludes),
+            &mut kept_paths,
+            &mut unreachable_paths,
+            &CancellationToken::new(),
+        )
+        .unwrap_or_default();
+
+        // Size of each file when it was last read, cue tracks share the size of their file
+        let previous_files: HashMap<&Path, Option<u64>> = previous_media
+            .iter()
+            .map(|(path, metadata)| (metadata.media_file(path), metadata.file_size))
+            .collect();
+
+        let mut preview = ScanPreview::default();
+        for file in &files {
+            match previous_files.get(file.as_path()) {
+                None => preview.added += 1,
+                Some(Some(size)) => {
+                    if fs::metadata(file).ok().map(|metadata| metadata.len()) != Some(*size) {
+                        preview.updated += 1;
+                    }
+                }
+                Some(None) => {}
+            }
+        }
+
+        let found: HashSet<&Path> = files.iter().map(PathBuf::as_path).collect();
+        preview.removed = previous_files
+            .into_keys()
+            .filter(|file| !found.contains(file))
+            .filter(|file| !kept_paths.iter().any(|kept| file.starts_with(kept)))
+            .map(Path::to_path_buf)
+            .collect();
+        preview.removed.sort();
+
+        unreachable_paths.sort();
+        preview.unreachable_paths = unreachable_paths;
+        preview
+    }
+
+    /// Re-read metadata for just the given tracks, keeping their play statistics and user
+    /// adjustments. Artwork thumbnails are only created when missing.
+    pub fn rescan_tracks(
+        tracks: Vec<(PathBuf, MediaMetaData)>,
+        xdg_dirs: Arc<BaseDirectories>,
+        progress_tx: ProgressSender,
+        cancel_token: CancellationToken,
+        collapse_whitespace: bool,
+    ) {
+        std::thread::spawn(move || {
+            let mut media = HashMap::new();
+            let mut failed = 0;
+
+            let discoverer = match gst::init().map_err(|err| err.to_string()).and_then(|_| {
+                pbutils::Discoverer::new(gst::ClockTime::from_seconds(GSTREAMER_TIMEOUT_SECS))
+                    .map_err(|err| err.to_string())
+            }) {
+                Ok(discoverer) => discoverer,
+                Err(err) => {
+                    log::error!("Failed to create discoverer: {}", err);
+                    let _ = progress_tx.send(LibraryProgress::TracksRescanned {
+                        media,
+                        failed: tracks.len(),
+                    });
+                    return;
+                }
+            };
+
+            let total = tracks.len() as f32;
+            let mut refreshed_artwork_hashes = HashSet::new();
+            let mut missing_plugins = BTreeSet::new();
+            let mut missing_plugin_track_count = 0;
+            let mut scan_errors = Vec::new();
+
+            for (index, (file, previous)) in tracks.iter().enumerate() {
+                if cancel_token.is_cancelled() {
+                    log::info!("Track rescan cancelled by user");
+                    break;
+                }
+
+                // Cue tracks are re-read through their file and split again below
+                let source = previous.media_file(file).to_path_buf();
+                let mut metadata = MediaMetaData::new();
+                if !previous.is_cue_track() {
+                    carry_over_user_data(previous, &mut metadata);
+                    carry_over_loudness(previous, &mut metadata);
+                }
+
+                match Self::extract_metadata(
+                    &source,
+                    &mut metadata,
+                    &xdg_dirs,
+                    &discoverer,
+                    false,
+                    collapse_whitespace,
+                    &mut refreshed_artwork_hashes,
+                ) {
+                    Ok(_) if previous.is_cue_track() => {
+                        let previous_media = HashMap::from([(file.clone(), previous.clone())]);
+                        match cue_track_entries(&source, &metadata, &previous_media)
+                            .and_then(|tracks| tracks.into_iter().find(|(path, _)| path == file))
+                        {
+                            Some((path, metadata)) => {
+                                media.insert(path, metadata);
+                            }
+                            None => failed += 1,
+                        }
+                    }
+                    Ok(_) => {
+                        media.insert(file.clone(), metadata);
+                    }
+                    Err(reason) => {
+                        match &reason {
+                            ScanErrorReason::MissingPlugins(plugins) => {
+                                missing_plugins.extend(plugins.iter().cloned());
+                                missing_plugin_track_count += 1;
+                            }
+                            _ => log::warn!(
+                                "Failed to extract metadata from {:?}: {:?}",
+                                file,
+                                reason
+                            ),
+                        }
+                        scan_errors.push(ScanError {
+                            path: source,
+                            reason,
+                        });
+                        failed += 1;
+                    }
+                }
+
+                progress_tx.progress((index + 1) as f32, total);
+            }
+
+            if missing_plugin_track_count > 0 {
+                let _ = progress_tx.send(LibraryProgress::MissingPlugins {
+                    plugins: missing_plugins.into_iter().collect(),
+                    track_count: missing_plugin_track_count,
+                });
+            }
+
+            if !scan_errors.is_empty() {
+                let _ = progress_tx.send(LibraryProgress::ScanErrors(scan_errors));
+            }
+
+            let _ = progress_tx.send(LibraryProgress::TracksRescanned { media, failed });
+        });
+    }
+
+    /// Decode each file through `rganalysis` to compute its ReplayGain track gain and peak.
+    /// This plays every file as fast as it decodes, so it's only run when asked for.
+    /// Files that fail to decode are skipped.
+    pub fn analyze_loudness(
+        files: Vec<PathBuf>,
+        progress_tx: ProgressSender,
+        cancel_token: CancellationToken,
+    ) {
+        std::thread::spawn(move || {
+            if let Err(err) = gst::init() {
+                log::error!("Failed to initialize GStreamer: {}", err);
+                let _ = progress_tx.send(LibraryProgress::LoudnessComplete);
+                return;
+            }
+
+            let total = files.len() as f32;
+            let mut analyzed: HashMap<PathBuf, (f64, f64)> = HashMap::new();
+
+            let mut last_progress_update = Instant::now();
+            let update_progress_interval = Duration::from_millis(PROGRESS_UPDATE_INTERVAL_MS);
+
+            let mut last_library_update = Instant::now();
+            let update_library_interval = Duration::from_secs(LIBRARY_UPDATE_INTERVAL_SECS);
+
+            for (index, file) in files.iter().enumerate() {
+                if cancel_token.is_cancelled() {
+                    log::info!("Loudness analysis cancelled by user");
+                    // Keep what was already computed
+                    if !analyzed.is_empty() {
+                        let _ = progress_tx.send(LibraryProgress::LoudnessUpdate(analyzed));
+                    }
+                    let _ = progress_tx.send(LibraryProgress::Cancelled);
+                    return;
+                }
+
+                match Self::analyze_file(file, &cancel_token) {
+                    Ok(Some(result)) => {
+                        analyzed.insert(file.clone(), result);
+                    }
+                    Ok(None) => {}
+                    Err(e) => log::warn!("Failed to analyze loudness of {:?}: {}", file, e),
+                }
+
+                let now = Instant::now();
+                let current = (index + 1) as f32;
+
+                if now.duration_since(last_progress_update) >= update_progress_interval {
+                    last_progress_update = now;
+                    progress_tx.progress(current, total);
+                }
+
+                if now.duration_since(last_library_update) >= update_library_interval
+                    && !analyzed.is_empty()
+                {
+                    last_library_update = now;
+                    let _ = progress_tx.send(LibraryProgress::LoudnessUpdate(std::mem::take(
+                        &mut analyzed,
+                    )));
+                }
+            }
+
+            let _ = progress_tx.send(LibraryProgress::Progress {
+                current: total,
+                total,
+                percent: 100.0,
+            });
+
+            if !analyzed.is_empty() {
+                let _ = progress_tx.send(LibraryProgress::LoudnessUpdate(analyzed));
+            }
+
+            let _ = progress_tx.send(LibraryProgress::LoudnessComplete);
+        });
+    }
+
+    /// Run a single file through `rganalysis`, returning its track gain in dB and peak.
+    /// `None` means decoding finished without a result, e.g. after a cancel.
+    fn analyze_file(
+        file: &Path,
+        cancel_token: &CancellationToken,
+    ) -> Result<Option<(f64, f64)>, String> {
+        let uri = Url::from_file_path(file).map_err(|_| "Failed to create URI".to_string())?;
+
+        let pipeline = gst::parse::launch(&format!(
+            "uridecodebin uri=\"{}\" ! audioconvert ! audioresample \
+             ! rganalysis name=analysis ! fakesink sync=false",
+            uri
+        ))
+        .map_err(|err| format!("failed to create pipeline: {err}"))?;
+
+        let bus = pipeline
+            .bus()
+            .ok_or_else(|| "pipeline has no bus".to_string())?;
+
+        pipeline
+            .set_state(gst::State::Playing)
+            .map_err(|err| format!("failed to start pipeline: {err}"))?;
+
+        let mut gain = None;
+        let mut peak = None;
+        let mut result = Ok(());
+
+        // Poll with a timeout so a cancel doesn't wait for a long file to finish
+        while !cancel_token.is_cancelled() {
+            let Some(msg) = bus.timed_pop_filtered(
+                gst::ClockTime::from_mseconds(PROGRESS_UPDATE_INTERVAL_MS),
+                &[
+                    gst::MessageType::Tag,
+                    gst::MessageType::Eos,
+                    gst::MessageType::Error,
+                ],
+            ) else {
+                continue;
+            };
+
+            match msg.view() {
+                gst::MessageView::Tag(tag) => {
+                    let from_analysis = msg.src().is_some_and(|src| src.name() == "analysis");
+                    if from_analysis {
+                        let tags = tag.tags();
+                        if let Some(value) = tags.get::<gst::tags::TrackGain>() {
+                            gain = Some(value.get());
+                        }
+                        if let Some(value) = tags.get::<gst::tags::TrackPeak>() {
+                            peak = Some(value.get());
+                        }
+                    }
+                }
+                gst::MessageView::Eos(_) => break,
+                gst::MessageView::Error(err) => {
+                    result = Err(err.error().to_string());
+                    break;
+                }
+                _ => {}
+            }
+        }
+
+        let _ = pipeline.set_state(gst::State::Null);
+        result?;
+
+        Ok(gain.zip(peak))
+    }
+
     /// Extract metadata from a single audio file using GStreamer
     fn extract_metadata(
         file: &PathBuf,
@@ -270,30 +755,65 @@ impl LibraryService {
         xdg_dirs: &BaseDirectories,
         discoverer: &pbutils::Discoverer,
         regenerate_thumbnails: bool,
+        collapse_whitespace: bool,
         refreshed_artwork_hashes: &mut HashSet<String>,
-    ) -> Result<(), String> {
+    ) -> Result<(), ScanErrorReason> {
         let file_str = file
             .to_str()
-            .ok_or_else(|| "Invalid file path".to_string())?;
-
-        let uri = Url::from_file_path(file_str).map_err(|_| "Failed to create URI".to_string())?;
-
-        let info = discoverer
-            .discover_uri(uri.as_str())
-            .map_err(|e| format!("Failed to discover: {}", e))?;
+            .ok_or_else(|| ScanErrorReason::Unreadable("Invalid file path".to_string()))?;
+
+        let uri = Url::from_file_path(file_str)
+            .map_err(|_| ScanErrorReason::Unreadable("Failed to create URI".to_string()))?;
+
+        let info = match discoverer.discover_uri(uri.as_str()) {
+            Ok(info) if info.result() == pbutils::DiscovererResult::MissingPlugins => {
+                let details = info.missing_elements_installer_details();
+                let plugins = if details.is_empty() {
+                    vec![missing_plugin_fallback(file)]
+                } else {
+                    details
+                        .iter()
+                        .map(|detail| missing_plugin_description(detail))
+                        .collect()
+                };
+                return Err(ScanErrorReason::MissingPlugins(plugins));
+            }
+            Ok(info) if info.result() == pbutils::DiscovererResult::Timeout => {
+                return Err(ScanErrorReason::Timeout);
+            }
+            Ok(info) => info,
+            // The synchronous discoverer drops the info when it reports an error
+            Err(err) if err.matches(gst::CoreError::MissingPlugin) => {
+                return Err(ScanErrorReason::MissingPlugins(vec![
+                    missing_plugin_fallback(file),
+                ]));
+            }
+            Err(err) => return Err(ScanErrorReason::Unreadable(err.to_string())),
+        };
 
         // Set the unique ID
         track_metadata.id = Some(digest(file_str));
 
         // Extract tags if available
         if let Some(tags) = info.tags() {
-            track_metadata.title = tags.get::<gst::tags::Title>().map(|t| t.get().to_owned());
-            track_metadata.artist = tags.get::<gst::tags::Artist>().map(|t| t.get().to_owned());
-            track_metadata.album = tags.get::<gst::tags::Album>().map(|t| t.get().to_owned());
+            // String tags are trimmed so stray whitespace doesn't split groups
+            track_metadata.title = tags
+                .get::<gst::tags::Title>()
+                .and_then(|t| normalize_tag(t.get(), collapse_whitespace));
+            track_metadata.artist = tags
+                .get::<gst::tags::Artist>()
+                .and_then(|t| normalize_tag(t.get(), collapse_whitespace));
+            track_metadata.album = tags
+                .get::<gst::tags::Album>()
+                .and_then(|t| normalize_tag(t.get(), collapse_whitespace));
             track_metadata.album_artist = tags
                 .get::<gst::tags::AlbumArtist>()
-                .map(|t| t.get().to_owned());
-            track_metadata.genre = tags.get::<gst::tags::Genre>().map(|t| t.get().to_owned());
+                .and_then(|t| normalize_tag(t.get(), collapse_whitespace));
+            // Files can carry several genre tags
+            track_metadata.genres = unique_genres(
+                tags.iter_tag::<gst::tags::Genre>()
+                    .filter_map(|t| normalize_tag(t.get(), collapse_whitespace)),
+            );
             track_metadata.track_number = tags
                 .get::<gst::tags::TrackNumber>()
                 .map(|t| t.get().to_owned());
@@ -306,6 +826,25 @@ impl LibraryService {
             track_metadata.album_disc_count = tags
                 .get::<gst::tags::AlbumVolumeCount>()
                 .map(|t| t.get().to_owned());
+            track_metadata.bpm = tags
+                .get::<gst::tags::BeatsPerMinute>()
+                .map(|t| t.get())
+                .filter(|bpm| bpm.is_finite() && *bpm > 0.0);
+            track_metadata.grouping = tags
+                .get::<gst::tags::Grouping>()
+                .and_then(|t| normalize_tag(t.get(), collapse_whitespace));
+            track_metadata.title_sort = tags
+                .get::<gst::tags::TitleSortname>()
+                .and_then(|t| normalize_tag(t.get(), collapse_whitespace));
+            track_metadata.artist_sort = tags
+                .get::<gst::tags::ArtistSortname>()
+                .and_then(|t| normalize_tag(t.get(), collapse_whitespace));
+            track_metadata.album_sort = tags
+                .get::<gst::tags::AlbumSortname>()
+                .and_then(|t| normalize_tag(t.get(), collapse_whitespace));
+            track_metadata.album_artist_sort = tags
+                .get::<gst::tags::AlbumArtistSortname>()
+                .and_then(|t| normalize_tag(t.get(), collapse_whitespace));
 
             // Duration
             if let Some(duration) = info.duration() {
@@ -328,11 +867,53 @@ impl LibraryService {
                     refreshed_artwork_hashes,
                 );
             }
+
+            // ReplayGain, only replaced when tagged so analyzed values survive a rescan
+            if let Some(gain) = tags.get::<gst::tags::TrackGain>() {
+                track_metadata.replay_gain_track_db = Some(gain.get());
+            }
+            if let Some(peak) = tags.get::<gst::tags::TrackPeak>() {
+                track_metadata.replay_gain_track_peak = Some(peak.get());
+            }
+
+            // Format details
+            track_metadata.codec = tags
+                .get::<gst::tags::AudioCodec>()
+                .and_then(|t| normalize_tag(t.get(), false));
+            track_metadata.container = tags
+                .get::<gst::tags::ContainerFormat>()
+                .and_then(|t| normalize_tag(t.get(), false));
+            track_metadata.bitrate = tags
+                .get::<gst::tags::Bitrate>()
+                .or_else(|| tags.get::<gst::tags::NominalBitrate>())
+                .map(|t| t.get())
+                .filter(|bitrate| *bitrate > 0);
         } else {
             // No metadata - use filename
             track_metadata.title = Some(file.to_string_lossy().to_string());
         }
 
+        if let Some(audio) = info.audio_streams().first() {
+            track_metadata.sample_rate = Some(audio.sample_rate()).filter(|rate| *rate > 0);
+            track_metadata.channels = Some(audio.channels()).filter(|channels| *channels > 0);
+            if track_metadata.bitrate.is_none() {
+                track_metadata.bitrate = Some(audio.bitrate()).filter(|bitrate| *bitrate > 0);
+            }
+        }
+
+        track_metadata.file_size = fs::metadata(file).ok().map(|metadata| metadata.len());
+
+        track_metadata.chapters.clear();
+        if let Some(toc) = info.toc() {
+            collect_chapters(
+                &toc.entries(),
+                collapse_whitespace,
+                &mut track_metadata.chapters,
+            );
+            track_metadata.chapters.sort_by_key(|(start, _)| *start);
+            track_metadata.chapters.dedup_by_key(|(start, _)| *start);
+        }
+
         Ok(())
     }
 
@@ -491,3 +1072,383 @@ impl LibraryService {
         }
     }
 }
+
+/// Build a matcher from the configured scan exclude patterns. Invalid patterns are logged
+/// and ignored so one typo doesn't stop the scan.
+pub fn scan_exclude_set(patterns: &[String]) -> GlobSet {
+    let mut builder = GlobSetBuilder::new();
+
+    for pattern in patterns {
+        match Glob::new(pattern) {
+            Ok(glob) => {
+                builder.add(glob);
+            }
+            Err(err) => log::warn!("Ignoring invalid scan exclude {:?}: {}", pattern, err),
+        }
+    }
+
+    builder.build().unwrap_or_else(|err| {
+        log::warn!("Failed to build scan excludes: {}", err);
+        GlobSet::empty()
+    })
+}
+
+/// Audio files in the library folders not in `kept_paths`, leaving out excluded files and
+/// folders. Folders that can't be read are added to `unreachable_paths` and `kept_paths`.
+/// `None` when cancelled.
+fn walk_library_paths(
+    paths: HashSet<String>,
+    excludes: &GlobSet,
+    kept_paths: &mut Vec<String>,
+    unreachable_paths: &mut Vec<String>,
+    cancel_token: &CancellationToken,
+) -> Option<Vec<PathBuf>> {
+    let mut files = Vec::new();
+
+    for path in paths {
+        if cancel_token.is_cancelled() {
+            return None;
+        }
+
+        if kept_paths.contains(&path) {
+            continue;
+        }
+
+        // An unmounted drive or a permission problem would otherwise look like an
+        // empty folder and drop all of its tracks
+        if let Err(err) = fs::read_dir(&path) {
+            log::warn!("Skipping unreachable library path {}: {}", path, err);
+            kept_paths.push(path.clone());
+            unreachable_paths.push(path);
+            continue;
+        }
+
+        files.extend(
+            WalkDir::new(&path)
+                .into_iter()
+                // Excluded folders are pruned so their contents aren't walked at all
+                .filter_entry(|entry| !excludes.is_match(entry.path()))
+                .filter_map(|e| e.ok())
+                .filter(is_audio_file)
+                .map(|entry| entry.into_path()),
+        );
+    }
+
+    // A folder inside another library folder is walked by both
+    files.sort();
+    files.dedup();
+    Some(files)
+}
+
+/// Audio files in `folder` and its subfolders, sorted by path
+pub fn audio_files_in(folder: &Path) -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = WalkDir::new(folder)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(is_audio_file)
+        .map(|entry| entry.into_path())
+        .collect();
+    files.sort();
+    files
+}
+
+/// Files with a supported extension, tiny ones are usually broken or placeholders
+fn is_audio_file(entry: &DirEntry) -> bool {
+    let extension = entry
+        .file_name()
+        .to_str()
+        .unwrap_or("")
+        .split('.')
+        .last()
+        .unwrap_or("")
+        .to_lowercase();
+
+    let size = entry.metadata().map(|m| m.len()).unwrap_or(0);
+
+    VALID_AUDIO_EXTENSIONS.contains(&extension.as_str()) && size > MIN_FILE_SIZE
+}
+
+/// Keep play statistics and user adjustments when a track's metadata is re-read or replaced
+/// by a scan result
+pub(crate) fn carry_over_user_data(previous: &MediaMetaData, metadata: &mut MediaMetaData) {
+    metadata.play_count = previous.play_count;
+    metadata.last_played = previous.last_played.clone();
+    metadata.manual_gain_db = previous.manual_gain_db;
+    metadata.favorite = previous.favorite;
+}
+
+// Keep analyzed loudness when a track's metadata is re-read, tags read afterwards replace it
+fn carry_over_loudness(previous: &MediaMetaData, metadata: &mut MediaMetaData) {
+    metadata.replay_gain_track_db = previous.replay_gain_track_db;
+    metadata.replay_gain_track_peak = previous.replay_gain_track_peak;
+}
+
+/// Split a file with a cue sheet beside it into one entry per cue track, `None` when there's
+/// no usable sheet. The file's own metadata fills in whatever the sheet leaves out, play
+/// statistics come from the previous entry for the same cue track.
+fn cue_track_entries(
+    file: &Path,
+    metadata: &MediaMetaData,
+    previous_media: &HashMap<PathBuf, MediaMetaData>,
+) -> Option<Vec<(PathBuf, MediaMetaData)>> {
+    let sheet = cue::find_for(file)?;
+    let last_start_ms = sheet.tracks.last()?.start_ms;
+    let file_duration_ms = metadata.duration.map(|duration| (duration * 1000.0) as u64);
+
+    // A sheet for a longer rip doesn't describe this file
+    if file_duration_ms.is_some_and(|duration| last_start_ms >= duration) {
+        log::warn!("Cue sheet for {:?} runs past the end of the file", file);
+        return None;
+    }
+
+    let track_count = sheet.tracks.len() as u32;
+    let defaults = MediaMetaData::new();
+
+    let tracks = sheet
+        .tracks
+        .iter()
+        .enumerate()
+        .map(|(index, track)| {
+            let path = cue_track_path(file, track.number);
+            let end_ms = sheet.tracks.get(index + 1).map(|next| next.start_ms);
+            let mut entry = metadata.clone();
+
+            entry.id = path.to_str().map(digest);
+            entry.title = track.title.clone().or(entry.title);
+            entry.artist = track
+                .performer
+                .clone()
+                .or_else(|| sheet.performer.clone())
+                .or(entry.artist);
+            entry.album = sheet.title.clone().or(entry.album);
+            entry.album_artist = sheet.performer.clone().or(entry.album_artist);
+            if let Some(genre) = &sheet.genre {
+                entry.genres = vec![genre.clone()];
+            }
+            // The file's sort names may not match the names the sheet gives its tracks
+            entry.title_sort = None;
+            entry.artist_sort = None;
+            entry.album_sort = None;
+            entry.album_artist_sort = None;
+            entry.track_number = Some(track.number);
+            entry.track_count = Some(track_count);
+            entry.duration = end_ms
+                .or(file_duration_ms)
+                .map(|end| end.saturating_sub(track.start_ms) as f32 / 1000.0);
+            entry.cue_start_ms = Some(track.start_ms);
+            entry.cue_end_ms = end_ms;
+            entry.chapters.clear();
+            // The file's play statistics and loudness don't belong to any single track
+            let previous = previous_media.get(&path).unwrap_or(&defaults);
+            carry_over_user_data(previous, &mut entry);
+            carry_over_loudness(previous, &mut entry);
+
+            (path, entry)
+        })
+        .collect();
+
+    Some(tracks)
+}
+
+/// Installer details look like `gstreamer|1.0|app|ALAC decoder|decoder-audio/x-alac`,
+/// the fourth field is the human readable description
+fn missing_plugin_description(detail: &str) -> String {
+    detail
+        .split('|')
+        .nth(3)
+        .filter(|description| !description.is_empty())
+        .unwrap_or(detail)
+        .to_string()
+}
+
+fn missing_plugin_fallback(file: &Path) -> String {
+    let extension = file
+        .extension()
+        .map(|extension| extension.to_string_lossy().to_uppercase())
+        .unwrap_or_default();
+
+    format!("{} decoder", extension)
+}
+
+/// Whether a track is shorter than the configured minimum. Tracks with an unknown duration
+/// are kept so a failed duration query doesn't hide them.
+fn is_below_min_duration(metadata: &MediaMetaData, min_duration_secs: Option<u64>) -> bool {
+    match (min_duration_secs, metadata.duration) {
+        (Some(min), Some(duration)) => (duration as f64) < min as f64,
+        _ => false,
+    }
+}
+
+/// Walk a TOC collecting chapter start times in milliseconds. Editions and other container
+/// entries are descended into. Untitled chapters get an empty title.
+fn collect_chapters(
+    entries: &[gst::TocEntry],
+    collapse_whitespace: bool,
+    chapters: &mut Vec<(u64, String)>,
+) {
+    for entry in entries {
+        if entry.entry_type() == gst::TocEntryType::Chapter {
+            if let Some((start, _)) = entry.start_stop_times().filter(|(start, _)| *start >= 0) {
+                let title = entry
+                    .tags()
+                    .and_then(|tags| {
+                        tags.get::<gst::tags::Title>()
+                            .and_then(|title| normalize_tag(title.get(), collapse_whitespace))
+                    })
+                    .unwrap_or_default();
+
+                chapters.push((start as u64 / 1_000_000, title));
+            }
+        }
+
+        collect_chapters(&entry.sub_entries(), collapse_whitespace, chapters);
+    }
+}
+
+/// Trim a string tag, optionally collapsing runs of internal whitespace to a single space.
+/// Tags that are empty after trimming are treated as missing.
+fn normalize_tag(value: &str, collapse_whitespace: bool) -> Option<String> {
+    let value = if collapse_whitespace {
+        value.split_whitespace().collect::<Vec<_>>().join(" ")
+    } else {
+        value.trim().to_string()
+    };
+
+    (!value.is_empty()).then_some(value)
+}
+
+/// Genres in tag order, repeats differing only in case are dropped
+fn unique_genres(tags: impl Iterator<Item = String>) -> Vec<String> {
+    let mut genres: Vec<String> = Vec::new();
+
+    for genre in tags {
+        if !genres
+            .iter()
+            .any(|existing| existing.eq_ignore_ascii_case(&genre))
+        {
+            genres.push(genre);
+        }
+    }
+
+    genres
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unique_genres_keeps_every_genre_in_order() {
+        let tags = ["Jazz", "Hard Bop"].map(String::from);
+        assert_eq!(unique_genres(tags.into_iter()), ["Jazz", "Hard Bop"]);
+    }
+
+    #[test]
+    fn unique_genres_drops_repeats() {
+        let tags = ["Jazz", "Bebop", "JAZZ"].map(String::from);
+        assert_eq!(unique_genres(tags.into_iter()), ["Jazz", "Bebop"]);
+    }
+
+    #[test]
+    fn progress_is_dropped_when_the_channel_is_full() {
+        let (progress_tx, mut rx) = ProgressSender::channel();
+        for current in 0..=LIBRARY_PROGRESS_CHANNEL_CAPACITY {
+            progress_tx.progress(current as f32, 100.0);
+        }
+
+        // `send` waits for room instead of dropping the final message
+        let sender = std::thread::spawn(move || progress_tx.send(LibraryProgress::Cancelled));
+
+        let mut received = Vec::new();
+        loop {
+            match rx.blocking_recv() {
+                Some(LibraryProgress::Progress { current, .. }) => received.push(current),
+                Some(LibraryProgress::Cancelled) => break,
+                other => panic!("unexpected message {:?}", other),
+            }
+        }
+
+        assert!(sender.join().unwrap().is_ok());
+        assert_eq!(received.len(), LIBRARY_PROGRESS_CHANNEL_CAPACITY);
+        assert_eq!(
+            received.last().copied(),
+            Some((LIBRARY_PROGRESS_CHANNEL_CAPACITY - 1) as f32)
+        );
+    }
+
+    #[test]
+    fn normalize_tag_trims() {
+        assert_eq!(
+            normalize_tag("  Blue  Train \t", false).as_deref(),
+            Some("Blue  Train")
+        );
+    }
+
+    #[test]
+    fn normalize_tag_collapses_whitespace() {
+        assert_eq!(
+            normalize_tag("  Blue \t Train\n", true).as_deref(),
+            Some("Blue Train")
+        );
+    }
+
+    #[test]
+    fn normalize_tag_blank_is_missing() {
+        assert_eq!(normalize_tag(" \t ", false), None);
+        assert_eq!(normalize_tag("", true), None);
+    }
+
+    fn with_duration(duration: Option<f32>) -> MediaMetaData {
+        MediaMetaData {
+            duration,
+            ..MediaMetaData::new()
+        }
+    }
+
+    #[test]
+    fn is_below_min_duration_compares_seconds() {
+        assert!(is_below_min_duration(&with_duration(Some(29.9)), Some(30)));
+        assert!(!is_below_min_duration(&with_duration(Some(30.0)), Some(30)));
+        assert!(!is_below_min_duration(
+            &with_duration(Some(240.0)),
+            Some(30)
+        ));
+    }
+
+    #[test]
+    fn is_below_min_duration_keeps_unknown_durations() {
+        assert!(!is_below_min_duration(&with_duration(None), Some(30)));
+    }
+
+    #[test]
+    fn is_below_min_duration_without_minimum() {
+        assert!(!is_below_min_duration(&with_duration(Some(1.0)), None));
+    }
+
+    fn excludes(patterns: &[&str]) -> GlobSet {
+        let patterns: Vec<String> = patterns.iter().map(|pattern| pattern.to_string()).collect();
+        scan_exclude_set(&patterns)
+    }
+
+    #[test]
+    fn scan_exclude_set_matches_full_path() {
+        let excludes = excludes(&["*/Downloads/*", "*.backup/*"]);
+
+        assert!(excludes.is_match("/home/user/Downloads/song.flac"));
+        assert!(excludes.is_match("/music/old.backup/Album/01.mp3"));
+        assert!(!excludes.is_match("/music/Album/01.mp3"));
+    }
+
+    #[test]
+    fn scan_exclude_set_ignores_invalid_patterns() {
+        let excludes = excludes(&["[unclosed", "*/Live/*"]);
+
+        assert!(excludes.is_match("/music/Live/01.flac"));
+        assert!(!excludes.is_match("/music/[unclosed"));
+    }
+
+    #[test]
+    fn scan_exclude_set_without_patterns() {
+        assert!(!excludes(&[]).is_match("/music/Album/01.flac"));
+    }
+}
@@ -1,18 +1,25 @@
 // SPDX-License-Identifier: GPL-3.0
 
 use crate::app::TrackId;
-use crate::config::PlaybackTransitionMode;
+use crate::config::{FadeCurve, PlaybackTransitionMode};
 use crate::constants::{
-    DEFAULT_CROSSFADE_DURATION_SECS, MAX_CROSSFADE_DURATION_SECS, MIN_CROSSFADE_DURATION_SECS,
+    DEBUG_LOG_CAPACITY, DEFAULT_CROSSFADE_DURATION_SECS, DEFAULT_FADE_OUT_MS,
+    MAX_CROSSFADE_DURATION_SECS, MAX_FADE_MS, MAX_RESUME_REWIND_SECS, MIN_CROSSFADE_DURATION_SECS,
 };
 use crate::mpris::MprisCommand;
 use crate::playback_state::{PlaybackSession, PlaybackState, PlaybackStatus, RepeatMode};
-use crate::player::Player;
-use crate::playlist::Playlist;
+use crate::player::{Player, curve_gain};
+use crate::playlist::{Playlist, Track};
+use crate::silence::SilenceBounds;
+use chrono::Local;
 use gst::prelude::*;
 use gstreamer as gst;
+use gstreamer_pbutils as pbutils;
 use rand::seq::SliceRandom;
-use std::time::Instant;
+use std::{
+    collections::{HashMap, VecDeque},
+    time::{Duration, Instant},
+};
 use tokio::sync::mpsc::UnboundedReceiver;
 use url::Url;
 
@@ -23,10 +30,30 @@ pub enum PlaybackEvent {
     GaplessTrackAdvanced,
     CrossfadeTrackAdvanced,
     Error(String),
+    /// A decoder or other plugin needed for the current track isn't installed
+    MissingPlugin(String),
     #[allow(dead_code)]
     PositionUpdate(f32),
 }
 
+/// Gain applied to the playing track
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AppliedGain {
+    pub gain_db: f32,
+    /// Part of the gain comes from the track's ReplayGain
+    pub replay_gain: bool,
+}
+
+/// Snapshot of the active pipeline for the debug panel
+#[derive(Debug, Clone)]
+pub struct PlaybackDebugInfo {
+    pub pipeline_state: String,
+    pub uri: Option<String>,
+    pub last_bus_message: Option<String>,
+    pub position_secs: Option<f32>,
+    pub duration_secs: Option<f32>,
+}
+
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 enum PlayerSlot {
     Primary,
@@ -45,6 +72,8 @@ impl PlayerSlot {
 #[derive(Debug, Clone)]
 struct CrossfadeState {
     fading_out_slot: PlayerSlot,
+    // Linear gain of the fading out track, fixed when the crossfade starts
+    fading_out_gain: f64,
     started_at: Instant,
     duration_secs: f32,
 }
@@ -60,9 +89,30 @@ pub struct PlaybackService {
     // Whether repeat is enabled at all.
     repeat_enabled: bool,
     transition_mode: PlaybackTransitionMode,
+    // Hand consecutive tracks of an album over gaplessly even in crossfade mode
+    gapless_within_albums: bool,
     output_volume: f64,
+    // Per-track manual gain in dB, keyed by library path
+    manual_gains: HashMap<TrackId, f32>,
+    replay_gain_normalization: bool,
     // Preferred crossfade duration for future transitions
     crossfade_duration_secs: f64,
+    // Drop the now playing metadata when playback stops instead of keeping the last track
+    clear_now_playing_on_stop: bool,
+    // Seconds to jump back when resuming a paused track
+    resume_rewind_secs: u32,
+    // Set while the current track is paused so play() knows it is a resume
+    resuming_from_pause: bool,
+    // Set while paused with the pipeline shut down so other apps can use the audio device,
+    // play() reopens it and seeks back to the paused position
+    device_released: bool,
+    // Decode up to the exact position for restores and chapter jumps instead of the keyframe
+    accurate_seeking: bool,
+    // Volume ramps when starting and when pausing or stopping, zero plays or cuts right away
+    fade_in: Duration,
+    fade_out: Duration,
+    // Volume envelope shared by crossfades and the fades above
+    fade_curve: FadeCurve,
     // True between an about-to-finish notification and the subsequent STREAM_START,
     // indicating a gapless transition is in-flight
     gapless_pending: bool,
@@ -71,6 +121,15 @@ pub struct PlaybackService {
     // session order changes
     pending_gapless_track_id: Option<TrackId>,
     crossfade: Option<CrossfadeState>,
+    // Position to jump to once the active player has prerolled, seeking earlier is ignored
+    pending_seek: Option<f32>,
+    // Where queued tracks opened with a start position begin, by entry id
+    start_offsets: HashMap<u32, f32>,
+    // Silent lead-in and tail to skip, with the track they were found for
+    silence_bounds: Option<(TrackId, SilenceBounds)>,
+    // Bus message history is only collected while the debug panel is enabled
+    debug_enabled: bool,
+    debug_log: VecDeque<String>,
 }
 
 impl PlaybackService {
@@ -84,11 +143,27 @@ impl PlaybackService {
             repeat_mode: RepeatMode::All,
             repeat_enabled: false,
             transition_mode: PlaybackTransitionMode::Gapless,
+            gapless_within_albums: false,
             output_volume: 1.0,
+            manual_gains: HashMap::new(),
+            replay_gain_normalization: false,
             crossfade_duration_secs: DEFAULT_CROSSFADE_DURATION_SECS as f64,
+            clear_now_playing_on_stop: false,
+            resume_rewind_secs: 0,
+            resuming_from_pause: false,
+            device_released: false,
+            accurate_seeking: false,
+            fade_in: Duration::ZERO,
+            fade_out: Duration::from_millis(DEFAULT_FADE_OUT_MS as u64),
+            fade_curve: FadeCurve::default(),
             gapless_pending: false,
             pending_gapless_track_id: None,
             crossfade: None,
+            pending_seek: None,
+            start_offsets: HashMap::new(),
+            silence_bounds: None,
+            debug_enabled: false,
+            debug_log: VecDeque::with_capacity(DEBUG_LOG_CAPACITY),
         }
     }
 
@@ -110,6 +185,35 @@ impl PlaybackService {
         self.state.session.as_ref()
     }
 
+    pub fn set_debug_enabled(&mut self, enabled: bool) {
+        self.debug_enabled = enabled;
+
+        if !enabled {
+            self.debug_log.clear();
+        }
+    }
+
+    pub fn debug_info(&self) -> PlaybackDebugInfo {
+        let playbin = &self.active_player().playbin;
+
+        PlaybackDebugInfo {
+            pipeline_state: format!("{:?}", playbin.current_state()),
+            uri: self.active_player().current_uri(),
+            last_bus_message: self.debug_log.back().cloned(),
+            position_secs: playbin
+                .query_position::<gst::ClockTime>()
+                .map(|position| position.mseconds() as f32 / 1000.0),
+            duration_secs: playbin
+                .query_duration::<gst::ClockTime>()
+                .map(|duration| duration.mseconds() as f32 / 1000.0),
+        }
+    }
+
+    /// Most recent bus messages, newest last
+    pub fn debug_log(&self) -> impl Iterator<Item = &String> {
+        self.debug_log.iter()
+    }
+
     pub fn set_dragging_slider(&mut self, dragging: bool) {
         self.state.dragging_slider = dragging;
     }
@@ -124,9 +228,7 @@ impl PlaybackService {
         self.repeat_mode = mode;
         self.repeat_enabled = enabled;
 
-        if matches!(self.transition_mode, PlaybackTransitionMode::Gapless) {
-            self.queue_next_uri();
-        }
+        self.queue_next_uri();
     }
 
     pub fn set_transition_mode(&mut self, mode: PlaybackTransitionMode) {
@@ -135,45 +237,154 @@ impl PlaybackService {
         self.pending_gapless_track_id = None;
         self.collapse_to_active_player();
 
-        match self.transition_mode {
-            PlaybackTransitionMode::Gapless => self.queue_next_uri(),
-            PlaybackTransitionMode::Crossfade => self.active_player().set_queued_uri(None),
-        }
+        self.queue_next_uri();
 
         self.apply_output_volume();
     }
 
     // Playback Control
 
+    pub fn set_gapless_within_albums(&mut self, enabled: bool) {
+        if self.gapless_within_albums == enabled {
+            return;
+        }
+
+        self.gapless_within_albums = enabled;
+        self.queue_next_uri();
+    }
+
     pub fn set_crossfade_duration_secs(&mut self, duration_secs: i32) {
         self.crossfade_duration_secs =
             duration_secs.clamp(MIN_CROSSFADE_DURATION_SECS, MAX_CROSSFADE_DURATION_SECS) as f64;
     }
 
+    pub fn set_clear_now_playing_on_stop(&mut self, clear: bool) {
+        self.clear_now_playing_on_stop = clear;
+
+        if clear && self.state.status == PlaybackStatus::Stopped {
+            self.state.now_playing = None;
+        }
+    }
+
+    pub fn set_resume_rewind_secs(&mut self, secs: u32) {
+        self.resume_rewind_secs = secs.min(MAX_RESUME_REWIND_SECS);
+    }
+
+    pub fn set_accurate_seeking(&mut self, accurate: bool) {
+        self.accurate_seeking = accurate;
+    }
+
+    pub fn set_fade_durations(&mut self, fade_in_ms: u32, fade_out_ms: u32) {
+        self.fade_in = Duration::from_millis(fade_in_ms.min(MAX_FADE_MS) as u64);
+        self.fade_out = Duration::from_millis(fade_out_ms.min(MAX_FADE_MS) as u64);
+    }
+
+    pub fn set_fade_curve(&mut self, curve: FadeCurve) {
+        self.fade_curve = curve;
+        // A crossfade in progress picks up the new shape on its next step
+        if self.crossfade.is_some() {
+            self.apply_output_volume();
+        }
+    }
+
     pub fn play(&mut self) {
         if !self.has_current_track() {
             self.clear_session();
             return;
         }
 
+        // Restore metadata that may have been dropped when playback last stopped
+        if self.state.now_playing.is_none() {
+            self.update_now_playing();
+        }
+
+        // A stopped pipeline starts over at the beginning of the file
+        if self.state.status == PlaybackStatus::Stopped
+            && self.pending_seek.is_none()
+            && self.cue_range().is_some()
+        {
+            self.pending_seek = Some(0.0);
+        }
+
+        if self.resuming_from_pause && self.resume_rewind_secs > 0 {
+            let position = (self.state.progress - self.resume_rewind_secs as f32).max(0.0);
+            self.seek_accurate(position);
+            self.state.progress = position;
+        }
+        self.resuming_from_pause = false;
+
+        if self.device_released {
+            self.device_released = false;
+            self.pending_seek = Some(self.state.progress);
+        }
+
         self.active_player_mut().play();
+        // Also restores the volume a faded pause or stop left at zero
+        self.apply_output_volume();
+
+        if !self.fade_in.is_zero() && self.crossfade.is_none() {
+            let volume = self.active_player().volume();
+            self.active_player()
+                .fade_in(volume, self.fade_in, self.fade_curve);
+        }
+
         self.state.status = PlaybackStatus::Playing;
     }
 
     pub fn pause(&mut self) {
         self.collapse_to_active_player();
-        self.active_player_mut().pause();
+
+        // The pipeline only pauses once the fade out has finished
+        if self.fade_out.is_zero() || self.state.status != PlaybackStatus::Playing {
+            self.active_player_mut().pause();
+        } else {
+            self.active_player()
+                .fade_out_to(gst::State::Paused, self.fade_out, self.fade_curve);
+        }
+
         self.state.status = PlaybackStatus::Paused;
+        self.resuming_from_pause = true;
     }
 
-    pub fn stop(&mut self) {
+    /// Shut down the paused pipeline so the audio device is free for other apps
+    pub fn release_device(&mut self) {
+        if self.state.status != PlaybackStatus::Paused || self.device_released {
+            return;
+        }
+
         self.stop_all_players();
+        self.device_released = true;
+        log::info!("Released the audio device while paused");
+    }
+
+    pub fn device_released(&self) -> bool {
+        self.device_released
+    }
+
+    pub fn stop(&mut self) {
+        if self.fade_out.is_zero() || self.state.status != PlaybackStatus::Playing {
+            self.stop_all_players();
+        } else {
+            // The active player fades out on its own, the other one is cut right away
+            let slot = self.active_slot;
+            self.stop_slot(slot.other());
+            self.player(slot).set_queued_uri(None);
+            let _ = self.player(slot).take_about_to_finish();
+            self.player(slot)
+                .fade_out_to(gst::State::Null, self.fade_out, self.fade_curve);
+        }
         self.active_slot = PlayerSlot::Primary;
         self.gapless_pending = false;
         self.pending_gapless_track_id = None;
         self.crossfade = None;
         self.state.status = PlaybackStatus::Stopped;
         self.state.progress = 0.0;
+        self.resuming_from_pause = false;
+        self.device_released = false;
+
+        if self.clear_now_playing_on_stop {
+            self.state.now_playing = None;
+        }
     }
 
     pub fn play_pause(&mut self) {
@@ -188,17 +399,124 @@ impl PlaybackService {
         self.apply_output_volume();
     }
 
+    /// Both players share the balance so crossfades don't shift sides
+    pub fn set_balance(&mut self, balance: f32) {
+        self.primary_player.set_balance(balance);
+        self.secondary_player.set_balance(balance);
+    }
+
+    /// Replace every manual gain, e.g. after the library is loaded or rescanned
+    pub fn set_manual_gains(&mut self, gains: HashMap<TrackId, f32>) {
+        self.manual_gains = gains;
+        self.apply_output_volume();
+    }
+
+    pub fn set_manual_gain(&mut self, id: &str, gain_db: Option<f32>) {
+        match gain_db {
+            Some(gain_db) => self.manual_gains.insert(id.to_string(), gain_db),
+            None => self.manual_gains.remove(id),
+        };
+        self.apply_output_volume();
+    }
+
+    pub fn set_replay_gain_normalization(&mut self, enabled: bool) {
+        self.replay_gain_normalization = enabled;
+        self.apply_output_volume();
+    }
+
+    /// Seek within the current track to the nearest keyframe, fast enough for scrubbing
     pub fn seek(&mut self, time: f32) {
+        self.seek_with_flags(time, None);
+    }
+
+    /// Seek for restored positions and chapter jumps, exact when accurate seeking is enabled.
+    /// The decoder has to run from the previous keyframe, which takes a moment on long VBR
+    /// files, but a keyframe seek there can land seconds away from the requested position.
+    pub fn seek_accurate(&mut self, time: f32) {
+        let flags = self.accurate_seeking.then_some(gst::SeekFlags::ACCURATE);
+        self.seek_with_flags(time, flags);
+    }
+
+    /// Cue tracks are offset to their place in the file. Positions past the end of the track,
+    /// which MPRIS clients can ask for, land on the end so the track finishes as usual.
+    /// Without `flags` the seek goes to the nearest keyframe.
+    fn seek_with_flags(&mut self, time: f32, flags: Option<gst::SeekFlags>) {
+        // A released pipeline can't seek, play() seeks to the progress once it's reopened
+        if self.device_released {
+            self.state.progress = time.max(0.0);
+            return;
+        }
+
+        // Neither can a stopped one, playing starts from the new position
+        if self.state.status == PlaybackStatus::Stopped {
+            self.seek_when_ready(time);
+            self.state.progress = time.max(0.0);
+            return;
+        }
+
         self.collapse_to_active_player();
+        let (start, end) = self.cue_range().unwrap_or((0.0, None));
+        let end = end.or_else(|| {
+            self.active_player()
+                .playbin
+                .query_duration::<gst::ClockTime>()
+                .map(|duration| duration.mseconds() as f32 / 1000.0)
+        });
+        let position = time.max(0.0) + start;
+        let Ok(position) =
+            Duration::try_from_secs_f32(end.map_or(position, |end| position.min(end)))
+        else {
+            return;
+        };
 
-        if let Err(err) = self.active_player().playbin.seek_simple(
-            gst::SeekFlags::FLUSH | gst::SeekFlags::KEY_UNIT,
-            gst::ClockTime::from_seconds(time as u64),
-        ) {
-            eprintln!("Failed to seek: {:?}", err);
+        let player = self.active_player();
+        let result = match flags {
+            Some(flags) => player.seek_with_flags(position, flags),
+            None => player.seek(position),
+        };
+        if let Err(err) = result {
+            log::warn!("Failed to seek: {:?}", err);
         }
     }
 
+    /// Seek once the current track is ready to play, for positions requested while loading
+    pub fn seek_when_ready(&mut self, time: f32) {
+        self.pending_seek = Some(time.max(0.0));
+    }
+
+    /// Skip the silent lead-in and tail of the current track. The lead-in is only skipped while
+    /// playback is still inside it, seeking back to the start afterwards plays it.
+    pub fn skip_silence(&mut self, track_id: &str, bounds: SilenceBounds) {
+        if self.get_current_track_id().as_deref() != Some(track_id)
+            || self
+                .silence_bounds
+                .as_ref()
+                .is_some_and(|(id, _)| id == track_id)
+        {
+            return;
+        }
+
+        self.silence_bounds = Some((track_id.to_string(), bounds));
+
+        if self.state.progress < bounds.start && self.pending_seek.is_none() {
+            // Before preroll there's no position and the seek has to wait
+            if self
+                .active_player()
+                .playbin
+                .query_position::<gst::ClockTime>()
+                .is_some()
+            {
+                self.seek(bounds.start);
+            } else {
+                self.pending_seek = Some(bounds.start);
+            }
+        }
+    }
+
+    pub fn clear_silence_bounds(&mut self) {
+        self.silence_bounds = None;
+    }
+
     // Session Management
 
     /// Start a new playback session from a playlist
@@ -236,6 +554,8 @@ impl PlaybackService {
         self.gapless_pending = false;
         self.pending_gapless_track_id = None;
         self.crossfade = None;
+        self.pending_seek = None;
+        self.start_offsets.clear();
 
         self.state.session = Some(PlaybackSession {
             playlist_id: playlist.id(),
@@ -247,47 +567,43 @@ impl PlaybackService {
         self.load_current_track();
     }
 
-    /// Update shuffle setting for current session
-    pub fn update_session_shuffle(&mut self, playlist: &Playlist, shuffle: bool) -> bool {
-        let Some(session) = &self.state.session else {
+    /// Add a track to the end of the current session, starting `start_secs` into it when
+    /// given. Returns false when nothing is queued.
+    pub fn enqueue(&mut self, track: Track, start_secs: Option<f32>) -> bool {
+        let Some(session) = &mut self.state.session else {
             return false;
         };
 
-        if session.playlist_id != playlist.id() {
-            return false;
+        if let Some(start_secs) = start_secs {
+            self.start_offsets
+                .insert(track.entry_id, start_secs.max(0.0));
         }
+        session.order.push(track);
 
-        let current_track_id = self.get_current_track_id();
-        let mut new_order = playlist.tracks().to_vec();
+        self.queue_next_uri();
 
-        if shuffle {
-            new_order.shuffle(&mut rand::rng());
-        }
+        true
+    }
 
-        let new_index = if let Some(ref id) = current_track_id {
-            new_order
-                .iter()
-                .position(|t| {
-                    t.metadata
-                        .id
-                        .as_ref()
-                        .is_some_and(|track_id| track_id == id)
-                })
-                .unwrap_or(0)
-        } else {
-            0
+    /// Update shuffle setting for current session. Only the upcoming tracks are reordered so
+    /// the current track keeps playing and the history stays as it was played.
+    pub fn update_session_shuffle(&mut self, playlist: &Playlist, shuffle: bool) -> bool {
+        let Some(session) = &mut self.state.session else {
+            return false;
         };
 
-        self.state.session = Some(PlaybackSession {
-            playlist_id: session.playlist_id,
-            order: new_order,
-            index: new_index,
-        });
+        if session.playlist_id != playlist.id() {
+            return false;
+        }
 
-        if matches!(self.transition_mode, PlaybackTransitionMode::Gapless) {
-            self.queue_next_uri();
+        if shuffle {
+            session.shuffle_remaining();
+        } else {
+            session.unshuffle_remaining(playlist.tracks());
         }
 
+        self.queue_next_uri();
+
         true
     }
 
@@ -345,9 +661,7 @@ impl PlaybackService {
 
         self.update_now_playing();
 
-        if matches!(self.transition_mode, PlaybackTransitionMode::Gapless) {
-            self.queue_next_uri();
-        }
+        self.queue_next_uri();
 
         true
     }
@@ -414,6 +728,23 @@ impl PlaybackService {
         self.update_now_playing();
     }
 
+    /// Skip to the track at `index` of the session order, for jumping ahead in the queue
+    pub fn play_session_index(&mut self, index: usize) {
+        self.collapse_to_active_player();
+
+        let Some(session) = &mut self.state.session else {
+            return;
+        };
+        if index >= session.order.len() {
+            return;
+        }
+        session.index = index;
+
+        self.load_current_track();
+        self.play();
+        self.update_now_playing();
+    }
+
     pub fn prev(&mut self, repeat_mode: RepeatMode) {
         self.collapse_to_active_player();
 
@@ -447,9 +778,7 @@ impl PlaybackService {
     pub fn tick(&mut self) -> Vec<PlaybackEvent> {
         let mut events = Vec::new();
 
-        if matches!(self.transition_mode, PlaybackTransitionMode::Gapless)
-            && self.active_player().take_about_to_finish()
-        {
+        if self.active_player().take_about_to_finish() && self.gapless_handoff_to_next() {
             self.gapless_pending = true;
         }
 
@@ -474,8 +803,21 @@ impl PlaybackService {
                 .playbin
                 .query_position::<gst::ClockTime>()
             {
-                self.state.progress = pos.mseconds() as f32 / 1000.0;
+                let position = pos.mseconds() as f32 / 1000.0;
+                let (start, end) = self.cue_range().unwrap_or((0.0, None));
+                self.state.progress = (position - start).max(0.0);
                 events.push(PlaybackEvent::PositionUpdate(self.state.progress));
+
+                // The file plays on past a cue track or a silent tail, so their end is found
+                // here instead of EOS
+                let end = end.or_else(|| self.silence_end());
+                if end.is_some_and(|end| position >= end)
+                    && self.state.status == PlaybackStatus::Playing
+                    && self.pending_seek.is_none()
+                    && self.crossfade.is_none()
+                {
+                    events.push(PlaybackEvent::TrackEnded);
+                }
             }
         }
 
@@ -560,9 +902,7 @@ impl PlaybackService {
         self.pending_gapless_track_id = None;
         self.active_player().set_queued_uri(None);
 
-        if matches!(self.transition_mode, PlaybackTransitionMode::Gapless) {
-            self.queue_next_uri();
-        }
+        self.queue_next_uri();
 
         self.apply_output_volume();
     }
@@ -579,22 +919,78 @@ impl PlaybackService {
 
     fn apply_output_volume(&mut self) {
         let output_volume = self.output_volume;
+        let current_gain = self
+            .current_track()
+            .map_or(1.0, |track| self.gain_factor(track));
 
         if let Some(crossfade) = &self.crossfade {
             let fade_in_slot = self.active_slot;
             let fade_out_slot = crossfade.fading_out_slot;
+            let fade_out_gain = crossfade.fading_out_gain;
             let ratio = self.crossfade_ratio();
 
+            let fade_in_volume = curve_gain(self.fade_curve, ratio);
+            let fade_out_volume = curve_gain(self.fade_curve, 1.0 - ratio);
+
             self.player_mut(fade_in_slot)
-                .set_volume(output_volume * ratio);
+                .set_volume(output_volume * current_gain * fade_in_volume);
             self.player_mut(fade_out_slot)
-                .set_volume(output_volume * (1.0 - ratio));
+                .set_volume(output_volume * fade_out_gain * fade_out_volume);
         } else {
-            self.active_player_mut().set_volume(output_volume);
+            self.active_player_mut()
+                .set_volume(output_volume * current_gain);
             self.player_mut(self.active_slot.other()).set_volume(0.0);
         }
     }
 
+    fn current_track(&self) -> Option<&Track> {
+        let session = self.state.session.as_ref()?;
+        session.order.get(session.index)
+    }
+
+    /// Gain applied to the current track, `None` when it plays at its own level
+    pub fn applied_gain(&self) -> Option<AppliedGain> {
+        let track = self.current_track()?;
+        let replay_gain_db = self.replay_gain_db(track);
+        let gain_db = self.manual_gain_db(track) + replay_gain_db.unwrap_or(0.0);
+
+        (gain_db != 0.0).then_some(AppliedGain {
+            gain_db: gain_db as f32,
+            replay_gain: replay_gain_db.is_some(),
+        })
+    }
+
+    fn manual_gain_db(&self, track: &Track) -> f64 {
+        track
+            .metadata
+            .id
+            .as_ref()
+            .and_then(|id| self.manual_gains.get(id))
+            .map_or(0.0, |gain_db| *gain_db as f64)
+    }
+
+    // The track's ReplayGain when normalizing, lowered as far as its peak needs to not clip
+    fn replay_gain_db(&self, track: &Track) -> Option<f64> {
+        if !self.replay_gain_normalization {
+            return None;
+        }
+
+        let gain_db = track.metadata.replay_gain_track_db?;
+        let headroom_db = track
+            .metadata
+            .replay_gain_track_peak
+            .filter(|peak| *peak > 0.0)
+            .map_or(f64::INFINITY, |peak| -20.0 * peak.log10());
+
+        Some(gain_db.min(headroom_db))
+    }
+
+    // Linear volume multiplier for a track's manual gain and ReplayGain combined
+    fn gain_factor(&self, track: &Track) -> f64 {
+        let gain_db = self.manual_gain_db(track) + self.replay_gain_db(track).unwrap_or(0.0);
+        10f64.powf(gain_db / 20.0)
+    }
+
     fn crossfade_duration_secs(&self, track_duration_secs: f64) -> f64 {
         self.crossfade_duration_secs
             .min((track_duration_secs / 2.0).max(0.0))
@@ -615,6 +1011,21 @@ impl PlaybackService {
             return None;
         }
 
+        // Both tracks would need their place in the file, cue tracks and tracks with a start
+        // position just change over
+        let next_is_seeked_into = session
+            .order
+            .get(next_index)
+            .is_some_and(|track| self.is_seeked_into(track));
+        if next_is_seeked_into || self.cue_range().is_some() {
+            return None;
+        }
+
+        // The next track continues the album and is already queued for a gapless handoff
+        if self.gapless_handoff_to_next() {
+            return None;
+        }
+
         let duration = self
             .active_player()
             .playbin
@@ -623,7 +1034,10 @@ impl PlaybackService {
             .active_player()
             .playbin
             .query_position::<gst::ClockTime>()?;
-        let duration_secs = duration.mseconds() as f32 / 1000.0;
+        // A skipped silent tail crossfades from where the audio ends
+        let duration_secs = self
+            .silence_end()
+            .unwrap_or(duration.mseconds() as f32 / 1000.0);
         let position_secs = position.mseconds() as f32 / 1000.0;
 
         if duration_secs <= 0.0 || position_secs <= 0.0 {
@@ -637,6 +1051,10 @@ impl PlaybackService {
             return None;
         }
 
+        let fading_out_gain = session
+            .order
+            .get(session.index)
+            .map_or(1.0, |track| self.gain_factor(track));
         let next_track = session.order.get(next_index)?;
         let next_uri = Url::from_file_path(&next_track.path).ok()?.to_string();
         let fading_out_slot = self.active_slot;
@@ -654,6 +1072,7 @@ impl PlaybackService {
         self.active_slot = fade_in_slot;
         self.crossfade = Some(CrossfadeState {
             fading_out_slot,
+            fading_out_gain,
             started_at: Instant::now(),
             duration_secs: crossfade_duration_secs,
         });
@@ -663,6 +1082,7 @@ impl PlaybackService {
         }
 
         self.update_now_playing();
+        self.silence_bounds = None;
         self.state.progress = 0.0;
         self.apply_output_volume();
 
@@ -686,21 +1106,77 @@ impl PlaybackService {
         while let Some(msg) = bus.pop() {
             use gst::MessageView;
 
+            if self.debug_enabled {
+                self.record_bus_message(slot, &msg);
+            }
+
             match msg.view() {
                 MessageView::Eos(..) => self.handle_eos(slot, events),
                 MessageView::StreamStart(..) => self.handle_stream_start(slot, events),
+                MessageView::AsyncDone(..) if slot == self.active_slot => {
+                    if let Some(time) = self.pending_seek.take() {
+                        self.seek_accurate(time);
+                    }
+                }
                 MessageView::Error(err) => self.handle_error(slot, err.error().to_string(), events),
+                // Playbin follows this with an error, which moves playback on
+                MessageView::Element(..) if pbutils::is_missing_plugin_message(&msg) => {
+                    events.push(PlaybackEvent::MissingPlugin(
+                        pbutils::missing_plugin_message_get_description(&msg).to_string(),
+                    ));
+                }
                 _ => (),
             }
         }
     }
 
+    fn record_bus_message(&mut self, slot: PlayerSlot, msg: &gst::Message) {
+        use gst::MessageView;
+
+        let from_playbin = msg
+            .src()
+            .is_some_and(|src| src == self.player(slot).playbin.upcast_ref::<gst::Object>());
+
+        let entry = match msg.view() {
+            // Element state changes are noisy, only the pipeline itself is interesting
+            MessageView::StateChanged(change) if from_playbin => {
+                format!("state {:?} -> {:?}", change.old(), change.current())
+            }
+            MessageView::StateChanged(..) => return,
+            MessageView::Error(err) => format!("error: {}", err.error()),
+            MessageView::Warning(warning) => format!("warning: {}", warning.error()),
+            _ => format!(
+                "{:?} from {}",
+                msg.type_(),
+                msg.src()
+                    .map(|src| src.name().to_string())
+                    .unwrap_or_default()
+            ),
+        };
+
+        if self.debug_log.len() >= DEBUG_LOG_CAPACITY {
+            self.debug_log.pop_front();
+        }
+
+        self.debug_log.push_back(format!(
+            "{} {:?}: {}",
+            Local::now().format("%H:%M:%S%.3f"),
+            slot,
+            entry
+        ));
+    }
+
     fn handle_eos(&mut self, slot: PlayerSlot, events: &mut Vec<PlaybackEvent>) {
         if self.is_fading_out_slot(slot) {
             self.finish_crossfade();
             return;
         }
 
+        // A track ending during a pause or stop fade out shouldn't start the next one
+        if self.state.status != PlaybackStatus::Playing {
+            return;
+        }
+
         if slot != self.active_slot {
             self.stop_slot(slot);
             return;
@@ -711,10 +1187,7 @@ impl PlaybackService {
     }
 
     fn handle_stream_start(&mut self, slot: PlayerSlot, events: &mut Vec<PlaybackEvent>) {
-        if matches!(self.transition_mode, PlaybackTransitionMode::Gapless)
-            && slot == self.active_slot
-            && self.gapless_pending
-        {
+        if slot == self.active_slot && self.gapless_pending {
             self.gapless_pending = false;
             self.advance_session_after_gapless();
             events.push(PlaybackEvent::GaplessTrackAdvanced);
@@ -745,24 +1218,34 @@ impl PlaybackService {
     }
 
     fn load_current_track(&mut self) {
+        self.device_released = false;
+        self.silence_bounds = None;
         self.collapse_to_active_player();
 
+        let mut is_cue_track = false;
+        let mut start_offset = None;
         if let Some(session) = &self.state.session {
             if let Some(track) = session.order.get(session.index) {
-                if let Ok(url) = Url::from_file_path(&track.path) {
+                is_cue_track = track.metadata.is_cue_track();
+                start_offset = self.start_offsets.remove(&track.entry_id);
+                if let Ok(url) = Url::from_file_path(track.metadata.media_file(&track.path)) {
                     self.stop_slot(self.active_slot);
                     self.active_player_mut().load(url.as_str());
                 }
             }
         }
 
-        if matches!(self.transition_mode, PlaybackTransitionMode::Gapless) {
-            self.queue_next_uri();
-        } else {
-            self.active_player().set_queued_uri(None);
+        // Cue tracks start at their offset once the file has prerolled
+        if let Some(start_offset) = start_offset {
+            self.pending_seek = Some(start_offset);
+        } else if is_cue_track {
+            self.pending_seek = Some(0.0);
         }
 
+        self.queue_next_uri();
+
         self.state.progress = 0.0;
+        self.resuming_from_pause = false;
         self.apply_output_volume();
     }
 
@@ -777,6 +1260,31 @@ impl PlaybackService {
         self.state.now_playing = None;
     }
 
+    /// Cue tracks and queued tracks with a start position begin with a seek
+    fn is_seeked_into(&self, track: &Track) -> bool {
+        track.metadata.is_cue_track() || self.start_offsets.contains_key(&track.entry_id)
+    }
+
+    /// Start and end of the current cue track in seconds, `None` for whole files
+    fn cue_range(&self) -> Option<(f32, Option<f32>)> {
+        let session = self.state.session.as_ref()?;
+        let metadata = &session.order.get(session.index)?.metadata;
+        let start = metadata.cue_start_ms? as f32 / 1000.0;
+
+        Some((start, metadata.cue_end_ms.map(|end| end as f32 / 1000.0)))
+    }
+
+    /// Where the silent tail of the current track starts, when it's skipped
+    fn silence_end(&self) -> Option<f32> {
+        let (track_id, bounds) = self.silence_bounds.as_ref()?;
+
+        if self.get_current_track_id().as_ref() != Some(track_id) {
+            return None;
+        }
+
+        bounds.end
+    }
+
     fn get_current_track_id(&self) -> Option<String> {
         self.state
             .session
@@ -810,10 +1318,34 @@ impl PlaybackService {
         }
     }
 
+    /// Whether the current track hands over to the next one through about-to-finish. Always
+    /// in gapless mode, and in crossfade mode for the next track of the same album when
+    /// gapless within albums is enabled.
+    fn gapless_handoff_to_next(&self) -> bool {
+        match self.transition_mode {
+            PlaybackTransitionMode::Gapless => true,
+            PlaybackTransitionMode::Crossfade => {
+                let Some(session) = self.state.session.as_ref() else {
+                    return false;
+                };
+                let current = session.order.get(session.index);
+                let next = self
+                    .compute_next_index()
+                    .filter(|index| *index != session.index)
+                    .and_then(|index| session.order.get(index));
+
+                self.gapless_within_albums
+                    && current.zip(next).is_some_and(|(current, next)| {
+                        current.metadata.continues_album(&next.metadata)
+                    })
+            }
+        }
+    }
+
     /// Pre-queue the next track URI in the active player so GStreamer can transition
     /// gaplessly when about-to-finish fires.
     fn queue_next_uri(&mut self) {
-        if !matches!(self.transition_mode, PlaybackTransitionMode::Gapless) {
+        if !self.gapless_handoff_to_next() {
             self.active_player().set_queued_uri(None);
             return;
         }
@@ -823,6 +1355,8 @@ impl PlaybackService {
                 .session
                 .as_ref()
                 .and_then(|session| session.order.get(idx))
+                // About-to-finish can only start the next track from its beginning
+                .filter(|track| !self.is_seeked_into(track))
                 .and_then(|track| {
                     Url::from_file_path(&track.path)
                         .ok()
@@ -867,7 +1401,9 @@ impl PlaybackService {
 
         self.pending_gapless_track_id = None;
         self.update_now_playing();
+        self.silence_bounds = None;
         self.queue_next_uri();
         self.state.progress = 0.0;
+        self.apply_output_volume();
     }
 }
@@ -1,8 +1,10 @@
 use crate::app::PlaylistId;
 use crate::constants::PLAYLISTS_DIR;
+use crate::fl;
+use crate::helpers::write_atomic;
 use crate::playlist::{Playlist, Track};
 use anyhow::{Result, anyhow};
-use std::{collections::HashSet, fs, path::PathBuf, sync::Arc};
+use std::{collections::HashSet, fs, io::Write, path::PathBuf, sync::Arc};
 use xdg::BaseDirectories;
 
 pub struct PlaylistService {
@@ -24,7 +26,18 @@ impl PlaylistService {
         for track in library_tracks {
             library.push(track);
         }
+
+        let mut favorites = Playlist::favorites();
+        for track in library
+            .tracks()
+            .iter()
+            .filter(|track| track.metadata.favorite)
+        {
+            favorites.push(track.clone());
+        }
+
         self.playlists.push(library);
+        self.playlists.push(favorites);
 
         // Load user playlists
         let playlist_dir = self.playlist_dir()?;
@@ -59,12 +72,35 @@ impl PlaylistService {
         Ok(id)
     }
 
+    /// Duplicate a playlist under an unused "(Copy)" name, returning the new playlist's id
+    pub fn duplicate(&mut self, id: PlaylistId) -> Result<PlaylistId> {
+        let source = self.get(id)?;
+
+        let name = self.unused_name(fl!("playlist-copy-name", name = source.name()));
+
+        let playlist = source.duplicate(name);
+        let new_id = playlist.id();
+
+        self.playlists.push(playlist);
+        self.save(new_id)?;
+
+        Ok(new_id)
+    }
+
+    /// `base_name`, or with the first number appended that no playlist uses yet
+    pub fn unused_name(&self, base_name: String) -> String {
+        std::iter::once(base_name.clone())
+            .chain((2..).map(|n| format!("{} {}", base_name, n)))
+            .find(|name| !self.playlists.iter().any(|p| p.name() == name))
+            .unwrap_or(base_name)
+    }
+
     /// Rename playlist
     pub fn rename(&mut self, id: PlaylistId, new_name: String) -> Result<()> {
         let playlist = self.get_mut(id)?;
 
-        if playlist.is_library() {
-            return Err(anyhow!("Cannot rename library"));
+        if !playlist.is_user() {
+            return Err(anyhow!("Cannot rename {}", playlist.name()));
         }
 
         playlist.set_name(new_name);
@@ -75,10 +111,10 @@ impl PlaylistService {
 
     /// Delete playlist
     pub fn delete(&mut self, id: PlaylistId) -> Result<()> {
-        // Make sure it isn't the library
+        // Make sure it isn't the library or favorites
         let playlist = self.get(id)?;
-        if playlist.is_library() {
-            return Err(anyhow!("Cannot delete library"));
+        if !playlist.is_user() {
+            return Err(anyhow!("Cannot delete {}", playlist.name()));
         }
 
         // Remove file
@@ -126,7 +162,7 @@ impl PlaylistService {
             playlist.push(track);
         }
 
-        if !playlist.is_library() {
+        if playlist.is_user() {
             self.save(playlist_id)?;
         }
 
@@ -137,8 +173,8 @@ impl PlaylistService {
     pub fn remove_selected(&mut self, playlist_id: PlaylistId) -> Result<()> {
         let playlist = self.get_mut(playlist_id)?;
 
-        if playlist.is_library() {
-            return Err(anyhow!("Cannot remove tracks from library"));
+        if !playlist.is_user() {
+            return Err(anyhow!("Cannot remove tracks from {}", playlist.name()));
         }
 
         playlist.remove_selected();
@@ -179,6 +215,14 @@ impl PlaylistService {
             .ok_or_else(|| anyhow!("Library not found"))
     }
 
+    /// Get a mutable reference to the favorites playlist
+    pub fn get_favorites_mut(&mut self) -> Result<&mut Playlist> {
+        self.playlists
+            .iter_mut()
+            .find(|p| p.is_favorites())
+            .ok_or_else(|| anyhow!("Favorites not found"))
+    }
+
     /// Get all playlists
     pub fn all(&self) -> &[Playlist] {
         &self.playlists
@@ -186,21 +230,21 @@ impl PlaylistService {
 
     /// Get all user playlists
     pub fn user_playlists(&self) -> impl Iterator<Item = &Playlist> {
-        self.playlists.iter().filter(|p| !p.is_library())
+        self.playlists.iter().filter(|p| p.is_user())
     }
 
     /// Save playlist to disk
     pub fn save(&self, id: PlaylistId) -> Result<()> {
         let playlist = self.get(id)?;
 
-        if playlist.is_library() {
+        if !playlist.is_user() {
             return Ok(());
         }
 
         let file_path = self.playlist_file_path(id)?;
 
         let content = serde_json::to_string_pretty(playlist)?;
-        fs::write(file_path, content)?;
+        write_atomic(&file_path, |writer| writer.write_all(content.as_bytes()))?;
 
         Ok(())
     }
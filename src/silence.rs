@@ -0,0 +1,101 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use crate::constants::{SILENCE_DETECTION_INTERVAL_MS, SKIP_SILENCE_MIN_SECS};
+use gst::prelude::*;
+use gstreamer as gst;
+use std::path::Path;
+use url::Url;
+
+/// Silent lead-in and tail of a track, in seconds from its start
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SilenceBounds {
+    /// Where the audio first rises above the threshold, 0.0 when the lead-in is too short
+    pub start: f32,
+    /// Where the audio last falls below the threshold, `None` when the tail is too short
+    pub end: Option<f32>,
+}
+
+/// Decode a file through the `level` element and find where its audio starts and ends.
+/// Everything between the first and last reading above `threshold_db` counts as the track,
+/// so quiet passages in the middle are never skipped. `None` when there's nothing worth
+/// skipping, including tracks that are silent throughout.
+pub fn detect(file: &Path, threshold_db: f64) -> Result<Option<SilenceBounds>, String> {
+    gst::init().map_err(|err| format!("failed to initialize GStreamer: {err}"))?;
+
+    let uri = Url::from_file_path(file).map_err(|_| "Failed to create URI".to_string())?;
+    let pipeline = gst::parse::launch(&format!(
+        "uridecodebin uri=\"{}\" ! audioconvert \
+         ! level name=level interval={} post-messages=true ! fakesink sync=false",
+        uri,
+        SILENCE_DETECTION_INTERVAL_MS * 1_000_000
+    ))
+    .map_err(|err| format!("failed to create pipeline: {err}"))?;
+
+    let bus = pipeline
+        .bus()
+        .ok_or_else(|| "pipeline has no bus".to_string())?;
+
+    pipeline
+        .set_state(gst::State::Playing)
+        .map_err(|err| format!("failed to start pipeline: {err}"))?;
+
+    let mut readings = 0;
+    let mut first_audible = None;
+    let mut last_audible = None;
+    let mut result = Ok(());
+
+    for msg in bus.iter_timed(gst::ClockTime::NONE) {
+        match msg.view() {
+            gst::MessageView::Element(element) => {
+                let Some(structure) = element.structure() else {
+                    continue;
+                };
+                if structure.name() != "level" {
+                    continue;
+                }
+
+                // One peak per channel in dB, the loudest decides
+                if let Ok(peaks) = structure.get::<gst::glib::ValueArray>("peak") {
+                    let peak_db = peaks
+                        .iter()
+                        .filter_map(|value| value.get::<f64>().ok())
+                        .fold(f64::NEG_INFINITY, f64::max);
+                    if peak_db > threshold_db {
+                        first_audible.get_or_insert(readings);
+                        last_audible = Some(readings);
+                    }
+                    readings += 1;
+                }
+            }
+            gst::MessageView::Eos(_) => break,
+            gst::MessageView::Error(err) => {
+                result = Err(err.error().to_string());
+                break;
+            }
+            _ => {}
+        }
+    }
+
+    let _ = pipeline.set_state(gst::State::Null);
+    result?;
+
+    let (Some(first_audible), Some(last_audible)) = (first_audible, last_audible) else {
+        return Ok(None);
+    };
+
+    let interval_secs = SILENCE_DETECTION_INTERVAL_MS as f32 / 1000.0;
+    let start = first_audible as f32 * interval_secs;
+    let end = (last_audible + 1) as f32 * interval_secs;
+    let duration = readings as f32 * interval_secs;
+
+    let bounds = SilenceBounds {
+        start: if start >= SKIP_SILENCE_MIN_SECS {
+            start
+        } else {
+            0.0
+        },
+        end: (duration - end >= SKIP_SILENCE_MIN_SECS).then_some(end),
+    };
+
+    Ok((bounds.start > 0.0 || bounds.end.is_some()).then_some(bounds))
+}
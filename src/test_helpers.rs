@@ -0,0 +1,42 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use std::fs;
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+
+/// A folder of its own in the temp directory, removed with everything in it when dropped
+pub struct TempDir(PathBuf);
+
+impl TempDir {
+    /// The area and name keep tests running at the same time out of each other's folders
+    pub fn new(area: &str, name: &str) -> Self {
+        let dir = std::env::temp_dir().join(format!(
+            "ethereal-waves-{}-{}-{}",
+            area,
+            std::process::id(),
+            name
+        ));
+        fs::create_dir_all(&dir).unwrap();
+        Self(dir)
+    }
+}
+
+impl Deref for TempDir {
+    type Target = Path;
+
+    fn deref(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl AsRef<Path> for TempDir {
+    fn as_ref(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
@@ -0,0 +1,140 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use crate::app::{AppModel, Message};
+use crate::constants::{COMMAND_PALETTE_LIBRARY_RESULTS, COMMAND_PALETTE_MAX_RESULTS};
+use crate::fl;
+use crate::playback_state::PlaybackStatus;
+use crate::ui::menu;
+use cosmic::widget::menu::{Action as _, Item};
+use std::collections::BTreeSet;
+
+/// Palette entries matching every word of `query`, the first one runs on Enter. Actions and
+/// pages come first, tracks, albums and artists of the library follow once something is typed.
+pub fn entries(app: &AppModel, query: &str) -> Vec<(String, Message)> {
+    let words: Vec<String> = query.split_whitespace().map(str::to_lowercase).collect();
+    let matches = |label: &str| {
+        let label = label.to_lowercase();
+        words.iter().all(|word| label.contains(word.as_str()))
+    };
+
+    let mut entries: Vec<(String, Message)> = actions(app)
+        .into_iter()
+        .chain(pages(app))
+        .filter(|(label, _)| matches(label))
+        .collect();
+
+    if !words.is_empty() {
+        entries.extend(library_entries(app, &matches));
+    }
+
+    entries.truncate(COMMAND_PALETTE_MAX_RESULTS);
+    entries
+}
+
+/// Menu bar items that can run right now, plus the transport controls the menus don't have.
+/// Items of submenus, like the playlists to add to, are left out.
+fn actions(app: &AppModel) -> Vec<(String, Message)> {
+    let play_pause = match app.playback_service.status() {
+        PlaybackStatus::Stopped | PlaybackStatus::Paused => fl!("play"),
+        _ => fl!("pause"),
+    };
+
+    let menu_actions = menu::menus(app)
+        .into_iter()
+        .flat_map(|(_, items)| items)
+        .filter_map(|item| match item {
+            Item::Button(label, _, action) | Item::CheckBox(label, _, _, action) => {
+                Some((label, action.message()))
+            }
+            _ => None,
+        });
+
+    [
+        (play_pause, Message::PlayPause),
+        (fl!("next"), Message::Next),
+        (fl!("previous"), Message::Previous),
+    ]
+    .into_iter()
+    .chain(menu_actions)
+    .collect()
+}
+
+/// The library and playlists in nav bar order
+fn pages(app: &AppModel) -> Vec<(String, Message)> {
+    let ordered = app
+        .state
+        .playlist_nav_order
+        .iter()
+        .filter_map(|id| app.playlist_service.get(*id).ok());
+    let unordered = app
+        .playlist_service
+        .user_playlists()
+        .filter(|playlist| !app.state.playlist_nav_order.contains(&playlist.id()));
+
+    app.playlist_service
+        .get_library()
+        .ok()
+        .into_iter()
+        .chain(ordered)
+        .chain(unordered)
+        .map(|playlist| {
+            (
+                fl!("palette-go-to", name = playlist.name()),
+                Message::ShowPlaylist(playlist.id()),
+            )
+        })
+        .collect()
+}
+
+/// Library tracks by title, and albums and artists by name. Tracks are revealed in the library,
+/// albums and artists become a library search.
+fn library_entries(app: &AppModel, matches: &impl Fn(&str) -> bool) -> Vec<(String, Message)> {
+    let mut tracks = BTreeSet::new();
+    let mut albums = BTreeSet::new();
+    let mut artists = BTreeSet::new();
+
+    for metadata in app.library.media.values() {
+        if let (Some(title), Some(id)) = (&metadata.title, &metadata.id) {
+            if matches(title) {
+                tracks.insert((title.clone(), metadata.artist.clone(), id.clone()));
+            }
+        }
+        if let Some(album) = metadata.album.as_ref().filter(|album| matches(album)) {
+            albums.insert(album.clone());
+        }
+        if let Some(artist) = metadata.artist.as_ref().filter(|artist| matches(artist)) {
+            artists.insert(artist.clone());
+        }
+    }
+
+    let tracks = tracks
+        .into_iter()
+        .take(COMMAND_PALETTE_LIBRARY_RESULTS)
+        .map(|(title, artist, id)| {
+            let label = match artist {
+                Some(artist) => fl!("palette-track-by", title = title, artist = artist),
+                None => fl!("palette-track", title = title),
+            };
+            (label, Message::RevealInLibrary(id))
+        });
+    let albums = albums
+        .into_iter()
+        .take(COMMAND_PALETTE_LIBRARY_RESULTS)
+        .map(|album| {
+            (
+                fl!("palette-album", album = album.as_str()),
+                Message::SearchLibrary(album),
+            )
+        });
+    let artists = artists
+        .into_iter()
+        .take(COMMAND_PALETTE_LIBRARY_RESULTS)
+        .map(|artist| {
+            (
+                fl!("palette-artist", artist = artist.as_str()),
+                Message::SearchLibrary(artist),
+            )
+        });
+
+    tracks.chain(albums).chain(artists).collect()
+}
@@ -1,16 +1,25 @@
 // SPDX-License-Identifier: GPL-3.0
 
-use crate::app::{AppModel, Message, TrackDropData};
-use crate::constants::{FOOTER_CONDENSED_BREAKPOINT, LIBRARY_TRACK_DROP_PREFIX};
+use crate::app::{AppModel, ContextPage, ListViewModel, Message, TrackDropData};
+use crate::config::ListColumn;
+use crate::constants::{
+    FOOTER_CONDENSED_BREAKPOINT, FOOTER_QUEUE_LENGTH, FOOTER_QUEUE_ROW_HEIGHT,
+    FOOTER_QUEUE_VISIBLE_ROWS, FOOTER_VOLUME_SLIDER_WIDTH, LIBRARY_TRACK_DROP_PREFIX,
+    NAV_BAR_WIDTH, SEEK_SLIDER_HEIGHT, WAVEFORM_BAR_WIDTH, WAVEFORM_HEIGHT,
+};
 use crate::fl;
+use crate::format::*;
 use crate::helpers::*;
 use crate::library::MediaMetaData;
+use crate::page::track_row::{TrackRowLayout, track_row};
 use crate::playback_state::PlaybackStatus;
+use crate::playlist::Track;
+use crate::ui::icon_button::icon_button;
 use cosmic::widget::tooltip::Position;
 use cosmic::{
-    Element, cosmic_theme,
+    Application, Element, cosmic_theme,
     iced::{
-        Alignment, Font, Length,
+        Alignment, Border, Color, Font, Length,
         clipboard::dnd::DndAction,
         font::{self, Weight},
     },
@@ -26,7 +35,13 @@ pub fn footer<'a>(app: &AppModel) -> Element<'a, Message> {
         ..
     } = theme::active().cosmic().spacing;
 
-    let is_condensed = app.state.window_width < FOOTER_CONDENSED_BREAKPOINT;
+    // The footer sits beside the nav bar unless the nav bar is hidden or overlaid
+    let footer_width = if app.core().nav_bar_active() && !app.core().is_condensed() {
+        app.state.window_width - NAV_BAR_WIDTH
+    } else {
+        app.state.window_width
+    };
+    let is_condensed = footer_width < FOOTER_CONDENSED_BREAKPOINT;
     let progress_bar_height = Length::Fixed(4.0);
     let now_playing = if app.playback_service.now_playing().is_some() {
         app.playback_service.now_playing().unwrap()
@@ -39,11 +54,23 @@ pub fn footer<'a>(app: &AppModel) -> Element<'a, Message> {
 
     // Update progress area
     if app.is_updating {
+        // Until the scan knows how many files there are, show a full but faded bar
+        let is_indeterminate = app.update_total == 0.0;
+        let scan_percent = if is_indeterminate {
+            100.0
+        } else {
+            app.update_percent
+        };
+
         let updating_col = widget::column()
             .spacing(space_xxs)
-            .push(widget::row().push(
-                widget::progress_bar(0.0..=100.0, app.update_percent).girth(progress_bar_height),
-            ))
+            .push(
+                widget::row().push(
+                    widget::progress_bar(0.0..=100.0, scan_percent)
+                        .girth(progress_bar_height)
+                        .class(scan_progress_class(is_indeterminate)),
+                ),
+            )
             .push(
                 widget::row()
                     .push(widget::text(if app.update_progress == 0.0 {
@@ -53,8 +80,12 @@ pub fn footer<'a>(app: &AppModel) -> Element<'a, Message> {
                     }))
                     .push(widget::space::horizontal())
                     .push(widget::tooltip(
-                        widget::button::icon(widget::icon::from_name("process-stop-symbolic"))
-                            .on_press(Message::CancelLibraryUpdate),
+                        icon_button(
+                            widget::icon::from_name("process-stop-symbolic"),
+                            fl!("cancel-update"),
+                        )
+                        .padding(space_xxs)
+                        .on_press(Message::CancelLibraryUpdate),
                         widget::text(fl!("cancel-update")),
                         Position::Bottom,
                     ))
@@ -63,14 +94,38 @@ pub fn footer<'a>(app: &AppModel) -> Element<'a, Message> {
             .push(widget::space::vertical().height(space_xs));
 
         content = content.push(updating_col);
+    } else if let Some((track_count, _)) = app.scan_complete {
+        let complete_col = widget::column()
+            .spacing(space_xxs)
+            .push(
+                widget::row().push(
+                    widget::progress_bar(0.0..=100.0, 100.0)
+                        .girth(progress_bar_height)
+                        .class(scan_progress_class(false)),
+                ),
+            )
+            .push(widget::text(fl!(
+                "scan-complete",
+                count = track_count,
+                tracks = format_count(track_count)
+            )))
+            .push(widget::space::vertical().height(space_xs));
+
+        content = content.push(complete_col);
     }
 
     let mut handle: Option<Arc<cosmic::widget::image::Handle>> = None;
+    let mut artwork_accent: Option<Color> = None;
 
     if let Some(now_playing) = &app.playback_service.now_playing() {
         if let Some(artwork_filename) = &now_playing.artwork_filename {
             app.image_store.request(artwork_filename.clone());
             handle = app.image_store.get(&artwork_filename.clone());
+
+            if app.config.tint_from_artwork {
+                app.image_store.request_accent(artwork_filename.clone());
+                artwork_accent = app.image_store.accent(artwork_filename);
+            }
         }
     }
 
@@ -81,12 +136,12 @@ pub fn footer<'a>(app: &AppModel) -> Element<'a, Message> {
 
     let volume_icon = if app.state.muted {
         "audio-volume-muted-symbolic"
+    } else if app.config.volume <= 0.33 {
+        "audio-volume-low-symbolic"
+    } else if app.config.volume <= 0.66 {
+        "audio-volume-medium-symbolic"
     } else {
-        match app.state.volume {
-            0..=33 => "audio-volume-low-symbolic",
-            34..=66 => "audio-volume-medium-symbolic",
-            _ => "audio-volume-high-symbolic",
-        }
+        "audio-volume-high-symbolic"
     };
 
     let controls: Element<_> = if is_condensed {
@@ -95,7 +150,16 @@ pub fn footer<'a>(app: &AppModel) -> Element<'a, Message> {
         full_footer(app, now_playing, play_icon, volume_icon, handle)
     };
 
-    widget::layer_container(content.push(controls))
+    let content: Element<_> = if app.config.tint_from_artwork {
+        widget::container(content.push(controls))
+            .width(Length::Fill)
+            .class(artwork_tint_class(artwork_accent))
+            .into()
+    } else {
+        content.push(controls).into()
+    };
+
+    widget::layer_container(content)
         .layer(cosmic_theme::Layer::Primary)
         .into()
 }
@@ -122,14 +186,15 @@ fn condensed_footer<'a>(
         .clone()
         .title
         .filter(|title| !title.is_empty())
+        .map(|title| display_text(&title))
         .unwrap_or_else(|| fl!("one-track-selected"));
     let artwork = footer_artwork(handle, artwork_size, artwork_drag_data, artwork_drag_label);
 
-    let title_text = now_playing.title.as_deref().unwrap_or_default().to_string();
+    let title_text = display_text(now_playing.title.as_deref().unwrap_or_default());
     let by_text = join_non_empty(
         &[
-            now_playing.album.as_deref().unwrap_or_default(),
-            now_playing.artist.as_deref().unwrap_or_default(),
+            &display_text(now_playing.album.as_deref().unwrap_or_default()),
+            &display_text(now_playing.artist.as_deref().unwrap_or_default()),
         ],
         " - ",
     );
@@ -155,14 +220,7 @@ fn condensed_footer<'a>(
         .spacing(space_xxs)
         .width(Length::Fill)
         .push(widget::text(format_time(app.playback_service.progress())))
-        .push(
-            widget::slider(
-                0.0..=now_playing.duration.unwrap_or(0.0),
-                app.playback_service.progress(),
-                Message::SliderSeek,
-            )
-            .on_release(Message::ReleaseSlider),
-        )
+        .push(seek_slider(app, now_playing.duration.unwrap_or(0.0)))
         .push(widget::text(format_time_left(
             app.playback_service.progress(),
             now_playing.duration.unwrap_or(0.0),
@@ -174,34 +232,38 @@ fn condensed_footer<'a>(
         .push(artwork)
         .push(widget::space::horizontal())
         .push(widget::tooltip(
-            widget::button::icon(widget::icon::from_name("media-skip-backward-symbolic"))
-                .on_press(Message::Previous)
-                .padding(space_xs)
-                .icon_size(space_m),
+            icon_button(
+                widget::icon::from_name("media-skip-backward-symbolic").size(space_m),
+                fl!("previous"),
+            )
+            .on_press(Message::Previous)
+            .padding(space_xs),
             widget::text(fl!("previous")),
             Position::Bottom,
         ))
         .push(widget::tooltip(
-            widget::button::icon(widget::icon::from_name(play_icon))
-                .on_press(Message::PlayPause)
-                .padding(space_xs)
-                .icon_size(space_l),
-            widget::text(fl!("play")),
+            icon_button(
+                widget::icon::from_name(play_icon).size(space_l),
+                play_label(app),
+            )
+            .on_press(Message::PlayPause)
+            .padding(space_xs),
+            widget::text(play_label(app)),
             Position::Bottom,
         ))
         .push(widget::tooltip(
-            widget::button::icon(widget::icon::from_name("media-skip-forward-symbolic"))
-                .on_press(Message::Next)
-                .padding(space_xs)
-                .icon_size(space_m),
+            icon_button(
+                widget::icon::from_name("media-skip-forward-symbolic").size(space_m),
+                fl!("next"),
+            )
+            .on_press(Message::Next)
+            .padding(space_xs),
             widget::text(fl!("next")),
             Position::Bottom,
         ))
         .push(widget::space::horizontal())
-        .push(
-            widget::button::icon(widget::icon::from_name(volume_icon))
-                .on_press(Message::ToggleMute),
-        );
+        .push_maybe(gain_indicator(app))
+        .push(mute_button(app, volume_icon));
 
     widget::column()
         .spacing(space_xxs)
@@ -234,6 +296,7 @@ fn full_footer<'a>(
         .clone()
         .title
         .filter(|title| !title.is_empty())
+        .map(|title| display_text(&title))
         .unwrap_or_else(|| fl!("one-track-selected"));
     let artwork = footer_artwork(handle, artwork_size, artwork_drag_data, artwork_drag_label);
 
@@ -241,22 +304,27 @@ fn full_footer<'a>(
     if app.playback_service.now_playing().is_some() {
         now_playing_text = now_playing_text
             .push(
-                widget::text(now_playing.clone().title.unwrap_or(String::new()))
-                    .wrapping(cosmic::iced_core::text::Wrapping::WordOrGlyph)
-                    .font(Font {
-                        weight: Weight::Bold,
-                        ..Font::default()
-                    }),
+                widget::text(display_text(
+                    now_playing.title.as_deref().unwrap_or_default(),
+                ))
+                .wrapping(cosmic::iced_core::text::Wrapping::WordOrGlyph)
+                .font(Font {
+                    weight: Weight::Bold,
+                    ..Font::default()
+                }),
             )
             .push(
-                widget::text(now_playing.clone().album.unwrap_or(String::new())).font(Font {
+                widget::text(display_text(
+                    now_playing.album.as_deref().unwrap_or_default(),
+                ))
+                .font(Font {
                     style: font::Style::Italic,
                     ..Font::default()
                 }),
             )
-            .push(widget::text(
-                now_playing.clone().artist.unwrap_or(String::new()),
-            ))
+            .push(widget::text(display_text(
+                now_playing.artist.as_deref().unwrap_or_default(),
+            )))
     }
 
     let now_playing_column = widget::column().width(Length::FillPortion(1)).push(
@@ -276,14 +344,7 @@ fn full_footer<'a>(
                 .spacing(space_xxs)
                 .width(Length::Fill)
                 .push(widget::text(format_time(app.playback_service.progress())))
-                .push(
-                    widget::slider(
-                        0.0..=now_playing.duration.unwrap_or(0.0),
-                        app.playback_service.progress(),
-                        Message::SliderSeek,
-                    )
-                    .on_release(Message::ReleaseSlider),
-                )
+                .push(seek_slider(app, now_playing.duration.unwrap_or(0.0)))
                 .push(widget::text(format_time_left(
                     app.playback_service.progress(),
                     now_playing.duration.unwrap_or(0.0),
@@ -299,48 +360,100 @@ fn full_footer<'a>(
                 .width(Length::Fill)
                 .push(widget::space::horizontal().width(Length::Fill))
                 .push(widget::tooltip(
-                    widget::button::icon(widget::icon::from_name("media-skip-backward-symbolic"))
-                        .on_press(Message::Previous)
-                        .padding(space_xs)
-                        .icon_size(space_m),
+                    icon_button(
+                        widget::icon::from_name("media-skip-backward-symbolic").size(space_m),
+                        fl!("previous"),
+                    )
+                    .on_press(Message::Previous)
+                    .padding(space_xs),
                     widget::text(fl!("previous")),
                     Position::Bottom,
                 ))
                 .push(widget::tooltip(
-                    widget::button::icon(widget::icon::from_name(play_icon))
-                        .on_press(Message::PlayPause)
-                        .padding(space_xs)
-                        .icon_size(space_l),
-                    widget::text(fl!("play")),
+                    icon_button(
+                        widget::icon::from_name(play_icon).size(space_l),
+                        play_label(app),
+                    )
+                    .on_press(Message::PlayPause)
+                    .padding(space_xs),
+                    widget::text(play_label(app)),
                     Position::Bottom,
                 ))
                 .push(widget::tooltip(
-                    widget::button::icon(widget::icon::from_name("media-skip-forward-symbolic"))
-                        .on_press(Message::Next)
-                        .padding(space_xs)
-                        .icon_size(space_m),
+                    icon_button(
+                        widget::icon::from_name("media-skip-forward-symbolic").size(space_m),
+                        fl!("next"),
+                    )
+                    .on_press(Message::Next)
+                    .padding(space_xs),
                     widget::text(fl!("next")),
                     Position::Bottom,
                 ))
                 .push(widget::space::horizontal().width(Length::Fill)),
         );
 
-    let other_controls_column = widget::column().width(Length::FillPortion(1)).push(
-        widget::row()
-            .align_y(Alignment::Center)
-            .spacing(space_xxs)
-            .push(widget::space::horizontal().width(Length::FillPortion(1)))
-            .push(
-                widget::button::icon(widget::icon::from_name(volume_icon))
-                    .on_press(Message::ToggleMute),
-            )
-            .push(
-                widget::column().push(
-                    widget::slider(0..=100, app.state.volume, Message::SetVolume)
-                        .width(Length::Fixed(150.0)),
-                ),
-            ),
-    );
+    let other_controls_column = widget::column()
+        .width(Length::FillPortion(1))
+        .push(
+            widget::row()
+                .align_y(Alignment::Center)
+                .spacing(space_xxs)
+                .push(widget::space::horizontal().width(Length::FillPortion(1)))
+                .push_maybe(now_playing.id.clone().map(|id| {
+                    let is_favorite = app
+                        .library
+                        .from_id(&id)
+                        .is_some_and(|(_, metadata)| metadata.favorite);
+                    let icon = if is_favorite {
+                        "starred-symbolic"
+                    } else {
+                        "non-starred-symbolic"
+                    };
+                    widget::tooltip(
+                        icon_button(widget::icon::from_name(icon), fl!("favorite"))
+                            .padding(space_xxs)
+                            .on_press(Message::ToggleFavorite(id)),
+                        widget::text(fl!("favorite")),
+                        Position::Bottom,
+                    )
+                }))
+                .push_maybe((!now_playing.chapters.is_empty()).then(|| {
+                    widget::tooltip(
+                        icon_button(
+                            widget::icon::from_name("view-list-symbolic"),
+                            fl!("chapters"),
+                        )
+                        .padding(space_xxs)
+                        .on_press(Message::ToggleContextPage(ContextPage::Chapters)),
+                        widget::text(fl!("chapters")),
+                        Position::Bottom,
+                    )
+                }))
+                .push(widget::tooltip(
+                    icon_button(
+                        widget::icon::from_name("dialog-information-symbolic"),
+                        fl!("track-info"),
+                    )
+                    .padding(space_xxs)
+                    .on_press_maybe(now_playing.id.clone().map(Message::ShowTrackInfo)),
+                    widget::text(fl!("track-info")),
+                    Position::Bottom,
+                ))
+                .push(widget::tooltip(
+                    icon_button(
+                        widget::icon::from_name("find-location-symbolic"),
+                        fl!("reveal-in-library"),
+                    )
+                    .padding(space_xxs)
+                    .on_press_maybe(now_playing.id.clone().map(Message::RevealInLibrary)),
+                    widget::text(fl!("reveal-in-library")),
+                    Position::Bottom,
+                ))
+                .push_maybe(gain_indicator(app))
+                .push(mute_button(app, volume_icon))
+                .push(widget::column().push(volume_slider(app))),
+        )
+        .push(queue_mini_view(app));
 
     let control_row = widget::row()
         .spacing(space_xxs)
@@ -364,6 +477,137 @@ fn full_footer<'a>(
         .into()
 }
 
+/// Name of the play button, for its tooltip and screen readers
+fn play_label(app: &AppModel) -> String {
+    match app.playback_service.status() {
+        PlaybackStatus::Stopped | PlaybackStatus::Paused => fl!("play"),
+        _ => fl!("pause"),
+    }
+}
+
+fn mute_button<'a>(app: &AppModel, volume_icon: &str) -> widget::Button<'a, Message> {
+    let name = if app.state.muted {
+        fl!("unmute")
+    } else {
+        fl!("mute")
+    };
+
+    icon_button(widget::icon::from_name(volume_icon), name)
+        .padding(theme::active().cosmic().spacing.space_xxs)
+        .on_press(Message::ToggleMute)
+}
+
+/// Screen readers announce a slider's value and range, the description adds the value the way
+/// it's shown
+fn volume_slider<'a>(app: &AppModel) -> Element<'a, Message> {
+    let slider = widget::slider(0.0..=1.0, app.config.volume, Message::SetVolume)
+        .step(0.01)
+        .width(Length::Fixed(FOOTER_VOLUME_SLIDER_WIDTH));
+    #[cfg(feature = "a11y")]
+    let slider = slider
+        .name(fl!("volume"))
+        .description(format_percent(app.config.volume * 100.0, 0));
+
+    slider.into()
+}
+
+/// Gain applied to the playing track, shown beside the volume so a quieter or louder track
+/// isn't a surprise
+fn gain_indicator<'a>(app: &AppModel) -> Option<Element<'a, Message>> {
+    let applied_gain = app.playback_service.applied_gain()?;
+    let gain = format_signed_decimal(applied_gain.gain_db as f64, 1);
+
+    Some(
+        widget::tooltip(
+            widget::text::caption(if applied_gain.replay_gain {
+                fl!("applied-replay-gain", gain = gain)
+            } else {
+                fl!("applied-gain", gain = gain)
+            }),
+            widget::text(fl!("applied-gain-description")),
+            Position::Bottom,
+        )
+        .into(),
+    )
+}
+
+/// The next few tracks of the queue below the volume controls, pressing one skips ahead to
+/// it. The playing track is already shown in the center column so nothing is highlighted.
+fn queue_mini_view<'a>(app: &AppModel) -> Element<'a, Message> {
+    let cosmic_theme::Spacing { space_xxs, .. } = theme::active().cosmic().spacing;
+    let expanded = app.config.show_footer_queue;
+
+    let header = widget::button::custom(
+        widget::row()
+            .spacing(space_xxs)
+            .align_y(Alignment::Center)
+            .push(
+                widget::icon::from_name(if expanded {
+                    "pan-down-symbolic"
+                } else {
+                    "pan-end-symbolic"
+                })
+                .size(16),
+            )
+            .push(widget::text::heading(fl!("up-next"))),
+    )
+    .class(theme::Button::Text)
+    .on_press(Message::ToggleFooterQueue(!expanded));
+
+    let Some(session) = app.playback_service.session().filter(|_| expanded) else {
+        return header.into();
+    };
+
+    let view_model = ListViewModel::compact(FOOTER_QUEUE_ROW_HEIGHT, app.config.path_fallbacks);
+    let layout = TrackRowLayout {
+        view_model: &view_model,
+        columns: &[ListColumn::Title, ListColumn::Artist],
+        track_number_column_width: 0.0,
+    };
+
+    let rows: Vec<Element<'a, Message>> = session
+        .order
+        .iter()
+        .enumerate()
+        .skip(session.index + 1)
+        .take(FOOTER_QUEUE_LENGTH)
+        .map(|(index, track)| {
+            let is_favorite = app
+                .library
+                .media
+                .get(&track.path)
+                .map(|metadata| metadata.favorite);
+            let track = Track {
+                selected: false,
+                ..track.clone()
+            };
+
+            track_row(
+                &track,
+                index - session.index,
+                false,
+                is_favorite,
+                &layout,
+                Message::PlayQueueIndex(index),
+            )
+            .into()
+        })
+        .collect();
+
+    if rows.is_empty() {
+        return header.into();
+    }
+
+    widget::column()
+        .push(header)
+        .push(
+            widget::scrollable(widget::column::with_children(rows)).height(Length::Fixed(
+                FOOTER_QUEUE_ROW_HEIGHT * FOOTER_QUEUE_VISIBLE_ROWS,
+            )),
+        )
+        .into()
+}
+
 fn current_track_drop_data(app: &AppModel) -> Option<TrackDropData> {
     app.playback_service
         .now_playing()
@@ -435,3 +679,124 @@ fn footer_artwork<'a>(
 
     draggable_artwork(artwork, drag_data, drag_label)
 }
+
+/// Seek slider with a tooltip showing the time under the pointer, plus a waveform of the
+/// track when the preview is enabled and its peaks have been computed
+fn seek_slider<'a>(app: &AppModel, duration: f32) -> Element<'a, Message> {
+    let cosmic_theme::Spacing { space_xxs, .. } = theme::active().cosmic().spacing;
+    let progress = app.playback_service.progress();
+
+    // The pointer position only arrives in pixels, the slider width is needed to map it
+    let slider = cosmic::iced::widget::responsive(move |size| {
+        let slider = widget::slider(0.0..=duration, progress, Message::SliderSeek)
+            .on_release(Message::ReleaseSlider);
+        #[cfg(feature = "a11y")]
+        let slider = slider.name(fl!("seek")).description(fl!(
+            "seek-position",
+            position = format_time(progress),
+            duration = format_time(duration)
+        ));
+
+        widget::mouse_area(slider)
+            .on_move(move |point| {
+                Message::SeekHover(Some((point.x / size.width.max(1.0)).clamp(0.0, 1.0)))
+            })
+            .on_exit(Message::SeekHover(None))
+            .into()
+    });
+    let slider = widget::container(slider)
+        .width(Length::Fill)
+        .height(Length::Fixed(SEEK_SLIDER_HEIGHT));
+
+    let Some(hover) = app.seek_hover.filter(|_| duration > 0.0) else {
+        return slider.into();
+    };
+
+    let preview = widget::column()
+        .spacing(space_xxs)
+        .align_x(Alignment::Center)
+        .push_maybe(
+            app.current_waveform()
+                .map(|peaks| waveform_thumbnail(peaks, hover)),
+        )
+        .push(widget::text(format_time(hover * duration)));
+
+    widget::tooltip(slider, preview, Position::FollowCursor).into()
+}
+
+fn waveform_thumbnail<'a>(peaks: &[f32], hover: f32) -> Element<'a, Message> {
+    let hover_bucket = (hover * peaks.len() as f32) as usize;
+
+    peaks
+        .iter()
+        .enumerate()
+        .fold(
+            widget::row()
+                .spacing(1)
+                .align_y(Alignment::End)
+                .height(Length::Fixed(WAVEFORM_HEIGHT)),
+            |row, (bucket, peak)| {
+                let height = (peak.clamp(0.0, 1.0) * WAVEFORM_HEIGHT).max(1.0);
+                let before_hover = bucket <= hover_bucket;
+
+                row.push(
+                    widget::container(widget::space::horizontal())
+                        .width(Length::Fixed(WAVEFORM_BAR_WIDTH))
+                        .height(Length::Fixed(height))
+                        .class(waveform_bar_class(before_hover)),
+                )
+            },
+        )
+        .into()
+}
+
+// Bars up to the hovered position use the accent color, like the filled part of the slider
+fn waveform_bar_class(before_hover: bool) -> theme::Container<'static> {
+    theme::Container::custom(move |theme| {
+        let cosmic = theme.cosmic();
+        let color = if before_hover {
+            Color::from(cosmic.accent_color())
+        } else {
+            let mut color = Color::from(cosmic.on_bg_color());
+            color.a = 0.4;
+            color
+        };
+
+        widget::container::Style {
+            background: Some(color.into()),
+            ..Default::default()
+        }
+    })
+}
+
+// A faint wash of the artwork's color over the footer, the theme accent until it's known or
+// when the artwork has no usable color
+fn artwork_tint_class(accent: Option<Color>) -> theme::Container<'static> {
+    theme::Container::custom(move |theme| {
+        let mut color = accent.unwrap_or_else(|| Color::from(theme.cosmic().accent_color()));
+        color.a = 0.12;
+
+        widget::container::Style {
+            background: Some(color.into()),
+            ..Default::default()
+        }
+    })
+}
+
+// The scan bar uses a neutral color so it is not mistaken for the accent colored seek slider
+fn scan_progress_class(indeterminate: bool) -> theme::ProgressBar {
+    theme::ProgressBar::Custom(Box::new(move |theme| {
+        let cosmic = theme.cosmic();
+        let mut bar = Color::from(cosmic.on_bg_color());
+        bar.a = if indeterminate { 0.3 } else { 0.7 };
+
+        widget::progress_bar::Style {
+            background: Color::from(cosmic.bg_divider()).into(),
+            bar: bar.into(),
+            border: Border {
+                radius: cosmic.radius_xs().into(),
+                ..Border::default()
+            },
+        }
+    }))
+}
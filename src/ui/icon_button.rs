@@ -0,0 +1,14 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use cosmic::{theme, widget};
+
+/// Icon-only button styled like `widget::button::icon`, with a name for screen readers since
+/// they can't describe the icon itself and the icon builder has no way to set one
+pub fn icon_button<'a, Message: Clone + 'a>(
+    icon: widget::icon::Named,
+    name: String,
+) -> widget::Button<'a, Message> {
+    widget::button::custom(icon.icon())
+        .class(theme::Button::Icon)
+        .name(name)
+}
@@ -0,0 +1,32 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use crate::app::{AppModel, Message};
+use cosmic::{
+    Element, cosmic_theme,
+    iced::{Alignment, Length},
+    theme, widget,
+};
+
+/// Letters shown in the strip, '#' collects entries that don't start with a letter
+const JUMP_LETTERS: &str = "#ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+/// A–Z column beside the list or grid, each letter scrolls to the first entry starting with it
+pub fn jump_strip<'a>(app: &AppModel) -> Element<'a, Message> {
+    let cosmic_theme::Spacing { space_xxxs, .. } = theme::active().cosmic().spacing;
+
+    let letters = JUMP_LETTERS.chars().map(|letter| {
+        widget::button::text(letter.to_string())
+            .padding([0, space_xxxs])
+            .on_press_maybe(app.can_jump().then_some(Message::JumpToLetter(letter)))
+            .into()
+    });
+
+    // Scrolls when the window is too short to fit every letter
+    widget::scrollable(
+        widget::column::with_children(letters.collect::<Vec<_>>())
+            .align_x(Alignment::Center)
+            .padding([space_xxxs, 0]),
+    )
+    .height(Length::Fill)
+    .into()
+}
@@ -1,6 +1,6 @@
 // SPDX-License-Identifier: GPL-3.0
 
-use crate::app::{AppModel, MenuAction, Message, ViewMode};
+use crate::app::{AppModel, MenuAction, Message, ShareFormat, ViewMode};
 use crate::constants::MENU_WIDGET_ID;
 use crate::fl;
 use crate::playback_state::RepeatMode;
@@ -18,19 +18,39 @@ static MENU_ID: LazyLock<cosmic::widget::Id> =
     LazyLock::new(|| cosmic::widget::Id::new(MENU_WIDGET_ID));
 
 pub fn menu_bar<'a>(app: &AppModel) -> Element<'a, Message> {
+    responsive_menu_bar()
+        .item_height(ItemHeight::Dynamic(40))
+        .item_width(ItemWidth::Uniform(250))
+        .spacing(1.0)
+        .into_element(
+            app.core(),
+            &app.key_binds,
+            MENU_ID.clone(),
+            Message::Surface,
+            menus(app),
+        )
+}
+
+/// The menus of the menu bar and their items, the command palette lists the same items
+pub fn menus(app: &AppModel) -> Vec<(String, Vec<menu::Item<MenuAction, String>>)> {
     let selected_playlist = app
         .view_playlist
         .and_then(|id| app.playlist_service.get(id).ok());
     let has_playlist = selected_playlist.is_some();
-    let selected_playlist_is_library = selected_playlist
+    let selected_playlist_is_user = selected_playlist
         .as_ref()
-        .is_some_and(|playlist| playlist.is_library());
+        .is_some_and(|playlist| playlist.is_user());
 
     let selected_count = selected_playlist
         .as_ref()
         .map(|playlist| playlist.selected_iter().count())
         .unwrap_or(0);
 
+    let has_queue = app
+        .playback_service
+        .session()
+        .is_some_and(|session| !session.order.is_empty());
+
     let repeat_one = app.state.repeat_mode == RepeatMode::One;
     let repeat_all = app.state.repeat_mode == RepeatMode::All;
     let list_view = app.config.view_mode == ViewMode::List;
@@ -81,11 +101,41 @@ pub fn menu_bar<'a>(app: &AppModel) -> Element<'a, Message> {
             MenuAction::TrackInfoPanel,
             selected_count > 0,
         ),
+        menu_button_optional(
+            fl!("export-selected-menu"),
+            MenuAction::ExportSelected,
+            selected_count > 0 && !app.is_updating,
+        ),
+        menu_button_optional(
+            fl!("rescan-selected-menu"),
+            MenuAction::RescanSelected,
+            selected_count > 0 && !app.is_updating && app.initial_load_complete,
+        ),
         menu::Item::Divider,
         menu_button_optional(
             fl!("update-library"),
             MenuAction::UpdateLibrary,
-            !app.is_updating,
+            !app.is_updating && app.initial_load_complete,
+        ),
+        menu_button_optional(
+            fl!("preview-library-update-menu"),
+            MenuAction::PreviewLibraryUpdate,
+            !app.is_updating && app.initial_load_complete,
+        ),
+        menu_button_optional(
+            fl!("undo-library-update-menu"),
+            MenuAction::UndoLastScan,
+            !app.is_updating && app.library_undo.is_some(),
+        ),
+        menu_button_optional(
+            fl!("analyze-loudness"),
+            MenuAction::AnalyzeLoudness,
+            !app.is_updating && app.initial_load_complete,
+        ),
+        menu::Item::Button(
+            fl!("reset-play-stats-menu"),
+            None,
+            MenuAction::ResetPlayStats,
         ),
         menu::Item::Divider,
         menu::Item::Button(fl!("quit"), None, MenuAction::Quit),
@@ -93,27 +143,48 @@ pub fn menu_bar<'a>(app: &AppModel) -> Element<'a, Message> {
 
     let playlist_items = vec![
         menu::Item::Button(fl!("new-playlist-menu"), None, MenuAction::NewPlaylist),
+        menu_button_optional(
+            fl!("import-folder-as-playlist-menu"),
+            MenuAction::ImportFolderAsPlaylist,
+            !app.is_updating && app.initial_load_complete,
+        ),
         menu_button_optional(
             fl!("rename-playlist-menu"),
             MenuAction::RenamePlaylist,
-            has_playlist && !selected_playlist_is_library,
+            has_playlist && selected_playlist_is_user,
+        ),
+        menu_button_optional(
+            fl!("duplicate-playlist-menu"),
+            MenuAction::DuplicatePlaylist(
+                selected_playlist
+                    .as_ref()
+                    .map(|playlist| playlist.id())
+                    .unwrap_or_default(),
+            ),
+            has_playlist,
         ),
         menu_button_optional(
             fl!("delete-playlist-menu"),
             MenuAction::DeletePlaylist,
-            has_playlist && !selected_playlist_is_library,
+            has_playlist && selected_playlist_is_user,
         ),
         menu::Item::Divider,
         menu::Item::Folder(fl!("add-selected-to"), selected_playlist_list),
         menu_button_optional(
             fl!("remove-selected"),
             MenuAction::RemoveSelectedFromPlaylist,
-            has_playlist && !selected_playlist_is_library,
+            has_playlist && selected_playlist_is_user,
         ),
         menu::Item::Divider,
         menu::Item::Folder(fl!("add-now-playing-to"), now_playing_playlist_list),
+        menu_button_optional(
+            fl!("save-queue-as-playlist-menu"),
+            MenuAction::SaveQueue,
+            has_queue,
+        ),
         menu::Item::Divider,
         menu::Item::Button(fl!("select-all"), None, MenuAction::SelectAll),
+        menu_button_optional(fl!("go-to-track-menu"), MenuAction::GoToTrack, has_playlist),
         menu::Item::Divider,
         menu_button_optional(fl!("move-up"), MenuAction::MoveNavUp, has_playlist),
         menu_button_optional(fl!("move-down"), MenuAction::MoveNavDown, has_playlist),
@@ -130,6 +201,51 @@ pub fn menu_bar<'a>(app: &AppModel) -> Element<'a, Message> {
         menu::Item::Button(fl!("volume-down"), None, MenuAction::VolumeDown),
         menu::Item::Button(mute_label, None, MenuAction::ToggleMute),
         menu::Item::Divider,
+        menu_button_optional(
+            fl!("go-to-now-playing"),
+            MenuAction::GoToNowPlaying,
+            has_queue,
+        ),
+        menu_button_optional(
+            fl!("favorite-now-playing"),
+            MenuAction::ToggleFavoriteNowPlaying,
+            app.playback_service.now_playing().is_some(),
+        ),
+        menu_button_optional(
+            fl!("reveal-now-playing"),
+            MenuAction::RevealNowPlayingInLibrary,
+            app.playback_service
+                .now_playing()
+                .is_some_and(|now_playing| now_playing.id.is_some()),
+        ),
+        menu_button_optional(
+            fl!("quick-add-now-playing"),
+            MenuAction::QuickAddCurrent,
+            app.playback_service.now_playing().is_some(),
+        ),
+        menu_button_optional(
+            fl!("copy-now-playing"),
+            MenuAction::CopyNowPlaying(ShareFormat::Plain),
+            app.playback_service.now_playing().is_some(),
+        ),
+        menu_button_optional(
+            fl!("copy-now-playing-markdown"),
+            MenuAction::CopyNowPlaying(ShareFormat::Markdown),
+            app.playback_service.now_playing().is_some(),
+        ),
+        menu::Item::Divider,
+        menu_button_optional(
+            fl!("shuffle-library"),
+            MenuAction::ShuffleLibrary,
+            !app.library.media.is_empty(),
+        ),
+        menu_button_optional(
+            fl!("bpm-radio"),
+            MenuAction::StartBpmRadio,
+            app.playback_service
+                .now_playing()
+                .is_some_and(|now_playing| now_playing.bpm.is_some()),
+        ),
         menu::Item::CheckBox(
             fl!("shuffle"),
             None,
@@ -171,30 +287,40 @@ pub fn menu_bar<'a>(app: &AppModel) -> Element<'a, Message> {
             MenuAction::SetViewMode(ViewMode::Grid),
         ),
         menu::Item::Divider,
+        menu::Item::CheckBox(
+            fl!("show-footer"),
+            None,
+            !app.state.footer_hidden,
+            MenuAction::ToggleFooter,
+        ),
+        menu::Item::Divider,
         menu::Item::Button(fl!("zoom-in"), None, MenuAction::ZoomIn),
         menu::Item::Button(fl!("zoom-out"), None, MenuAction::ZoomOut),
         menu::Item::Divider,
+        menu::Item::Button(
+            fl!("command-palette-menu"),
+            None,
+            MenuAction::CommandPalette,
+        ),
         menu::Item::Button(fl!("settings-menu"), None, MenuAction::Settings),
         menu::Item::Divider,
+        menu::Item::Button(fl!("scan-report-menu"), None, MenuAction::ScanReport),
         menu::Item::Button(fl!("about-ethereal-waves"), None, MenuAction::About),
     ];
 
-    responsive_menu_bar()
-        .item_height(ItemHeight::Dynamic(40))
-        .item_width(ItemWidth::Uniform(250))
-        .spacing(1.0)
-        .into_element(
-            app.core(),
-            &app.key_binds,
-            MENU_ID.clone(),
-            Message::Surface,
-            vec![
-                (fl!("file"), file_items),
-                (fl!("playlist"), playlist_items),
-                (fl!("playback"), playback_items),
-                (fl!("view"), view_items),
-            ],
-        )
+    let help_items = vec![menu::Item::Button(
+        fl!("view-logs-menu"),
+        None,
+        MenuAction::Logs,
+    )];
+
+    vec![
+        (fl!("file"), file_items),
+        (fl!("playlist"), playlist_items),
+        (fl!("playback"), playback_items),
+        (fl!("view"), view_items),
+        (fl!("help"), help_items),
+    ]
 }
 
 const fn menu_button_optional(
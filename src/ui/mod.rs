@@ -1,2 +1,5 @@
+pub mod command_palette;
 pub mod footer;
+pub mod icon_button;
+pub mod jump_strip;
 pub mod menu;
@@ -0,0 +1,110 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use crate::constants::{WAVEFORM_BUCKETS, WAVEFORM_DIR};
+use crate::helpers::write_atomic;
+use gst::prelude::*;
+use gstreamer as gst;
+use std::{fs, io, path::Path};
+use url::Url;
+use xdg::BaseDirectories;
+
+/// Load the cached peaks for a track, `None` when they haven't been computed yet
+pub fn load(xdg_dirs: &BaseDirectories, track_id: &str) -> Option<Vec<f32>> {
+    let path = xdg_dirs.find_data_file(format!("{}/{}.json", WAVEFORM_DIR, track_id))?;
+    let content = fs::read_to_string(path).ok()?;
+
+    serde_json::from_str(&content).ok()
+}
+
+pub fn save(xdg_dirs: &BaseDirectories, track_id: &str, peaks: &[f32]) -> io::Result<()> {
+    let path = xdg_dirs.place_data_file(format!("{}/{}.json", WAVEFORM_DIR, track_id))?;
+
+    write_atomic(&path, |writer| {
+        serde_json::to_writer(writer, peaks).map_err(io::Error::from)
+    })
+}
+
+/// Decode a file through the `level` element and reduce it to `WAVEFORM_BUCKETS` peak
+/// values from 0.0 to 1.0. This decodes the whole file, so it's only run when asked for.
+pub fn compute_peaks(file: &Path, duration_secs: f32) -> Result<Vec<f32>, String> {
+    if duration_secs <= 0.0 {
+        return Err("unknown duration".to_string());
+    }
+
+    gst::init().map_err(|err| format!("failed to initialize GStreamer: {err}"))?;
+
+    let uri = Url::from_file_path(file).map_err(|_| "Failed to create URI".to_string())?;
+    // A few level readings per bucket so short peaks aren't missed
+    let interval_ns =
+        ((duration_secs as f64 * 1_000_000_000.0) / (WAVEFORM_BUCKETS * 4) as f64) as u64;
+
+    let pipeline = gst::parse::launch(&format!(
+        "uridecodebin uri=\"{}\" ! audioconvert \
+         ! level name=level interval={} post-messages=true ! fakesink sync=false",
+        uri,
+        interval_ns.max(10_000_000)
+    ))
+    .map_err(|err| format!("failed to create pipeline: {err}"))?;
+
+    let bus = pipeline
+        .bus()
+        .ok_or_else(|| "pipeline has no bus".to_string())?;
+
+    pipeline
+        .set_state(gst::State::Playing)
+        .map_err(|err| format!("failed to start pipeline: {err}"))?;
+
+    let mut readings = Vec::new();
+    let mut result = Ok(());
+
+    for msg in bus.iter_timed(gst::ClockTime::NONE) {
+        match msg.view() {
+            gst::MessageView::Element(element) => {
+                let Some(structure) = element.structure() else {
+                    continue;
+                };
+                if structure.name() != "level" {
+                    continue;
+                }
+
+                // One peak per channel in dB, keep the loudest
+                if let Ok(peaks) = structure.get::<gst::glib::ValueArray>("peak") {
+                    let peak_db = peaks
+                        .iter()
+                        .filter_map(|value| value.get::<f64>().ok())
+                        .fold(f64::NEG_INFINITY, f64::max);
+                    readings.push(10f64.powf(peak_db / 20.0).clamp(0.0, 1.0) as f32);
+                }
+            }
+            gst::MessageView::Eos(_) => break,
+            gst::MessageView::Error(err) => {
+                result = Err(err.error().to_string());
+                break;
+            }
+            _ => {}
+        }
+    }
+
+    let _ = pipeline.set_state(gst::State::Null);
+    result?;
+
+    if readings.is_empty() {
+        return Err("no level readings".to_string());
+    }
+
+    Ok(reduce(&readings, WAVEFORM_BUCKETS))
+}
+
+// Take the maximum of each evenly sized chunk
+fn reduce(readings: &[f32], buckets: usize) -> Vec<f32> {
+    (0..buckets)
+        .map(|bucket| {
+            let start = bucket * readings.len() / buckets;
+            let end = ((bucket + 1) * readings.len() / buckets).max(start + 1);
+            readings[start..end.min(readings.len())]
+                .iter()
+                .copied()
+                .fold(0.0, f32::max)
+        })
+        .collect()
+}